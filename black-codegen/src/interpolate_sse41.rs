@@ -34,12 +34,15 @@ THE SOFTWARE.
 // is fairly redundant. Would be open to insights in how best to leverage
 // AVX to help accellerate some of this and other code in the rasterizer.
 //
-// Some optimizations are possible with _mm_dp_ps which is available in
-// sse4.1. To get Rust inlining these intrinsics, run the compiler with.
+// The weighted sums are reduced with _mm_dp_ps, which requires sse4.1.
+// To get Rust inlining these intrinsics, run the compiler with.
 //  
 // $ RUSTFLAGS="-C target-feature=+sse4.1"
 // $ cargo run --release
 //
+// This backend is exposed as #[derive(InterpolateSse41)]. The tests in
+// black/tests/interpolate.rs check it against the scalar backend.
+//
 // ------------------------------------------------------------------------
 
 use super::metadata::StructMeta;
@@ -65,6 +68,12 @@ fn impl_new_function(s: &StructMeta) -> quote::Tokens {
                 #field_name: Vec2::new(0.0, 0.0)
             }
         }
+        "f32" => {
+            let field_name = quote::Ident::from(field.name.clone());
+            quote! {
+                #field_name: 0.0
+            }
+        }
         _ => panic!("Cannot interpolate type '{}'", field.kind),
     });
     let name = quote::Ident::from(s.name.clone());
@@ -131,6 +140,17 @@ fn impl_correct_function(s: &StructMeta) -> quote::Tokens {
                 }
             }
         }
+        "f32" => {
+            let field_name = quote::Ident::from(field.name.clone());
+            quote! {
+                #field_name: unsafe {
+                    let d0 = _mm_set_ss(*w);
+                    let d1 = _mm_set_ss(v.#field_name);
+                    let d2 = _mm_div_ss(d1, d0);
+                    _mm_cvtss_f32(d2)
+                }
+            }
+        }
         _ => panic!("Cannot interpolate type '{}'", field.kind),
     });
     let name = quote::Ident::from(s.name.clone());
@@ -162,21 +182,13 @@ fn impl_interpolate_function(s: &StructMeta) -> quote::Tokens {
                     let r3 = _mm_set_ps(v0.#field_name.z, v1.#field_name.z, v2.#field_name.z, 0.0);
                     let r4 = _mm_set_ps(v0.#field_name.w, v1.#field_name.w, v2.#field_name.w, 0.0);
 
-                    let m0 = _mm_mul_ps(r0, r1);
-                    let m0 = _mm_hadd_ps(m0, m0);
-                    let m0 = _mm_hadd_ps(m0, m0);
+                    let m0 = _mm_dp_ps(r1, r0, 0xF1);
 
-                    let m1 = _mm_mul_ps(r0, r2);
-                    let m1 = _mm_hadd_ps(m1, m1);
-                    let m1 = _mm_hadd_ps(m1, m1);
+                    let m1 = _mm_dp_ps(r2, r0, 0xF1);
 
-                    let m2 = _mm_mul_ps(r0, r3);
-                    let m2 = _mm_hadd_ps(m2, m2);
-                    let m2 = _mm_hadd_ps(m2, m2);
+                    let m2 = _mm_dp_ps(r3, r0, 0xF1);
 
-                    let m3 = _mm_mul_ps(r0, r4);
-                    let m3 = _mm_hadd_ps(m3, m3);
-                    let m3 = _mm_hadd_ps(m3, m3);
+                    let m3 = _mm_dp_ps(r4, r0, 0xF1);
 
                     let u0 = _mm_cvtss_f32(m0);
                     let u1 = _mm_cvtss_f32(m1);
@@ -201,17 +213,11 @@ fn impl_interpolate_function(s: &StructMeta) -> quote::Tokens {
                     let r2 = _mm_set_ps(v0.#field_name.y, v1.#field_name.y, v2.#field_name.y, 0.0);
                     let r3 = _mm_set_ps(v0.#field_name.z, v1.#field_name.z, v2.#field_name.z, 0.0);
 
-                    let m0 = _mm_mul_ps(r0, r1);
-                    let m0 = _mm_hadd_ps(m0, m0);
-                    let m0 = _mm_hadd_ps(m0, m0);
+                    let m0 = _mm_dp_ps(r1, r0, 0xF1);
 
-                    let m1 = _mm_mul_ps(r0, r2);
-                    let m1 = _mm_hadd_ps(m1, m1);
-                    let m1 = _mm_hadd_ps(m1, m1);
+                    let m1 = _mm_dp_ps(r2, r0, 0xF1);
 
-                    let m2 = _mm_mul_ps(r0, r3);
-                    let m2 = _mm_hadd_ps(m2, m2);
-                    let m2 = _mm_hadd_ps(m2, m2);
+                    let m2 = _mm_dp_ps(r3, r0, 0xF1);
 
                     let u0 = _mm_cvtss_f32(m0);
                     let u1 = _mm_cvtss_f32(m1);
@@ -234,13 +240,9 @@ fn impl_interpolate_function(s: &StructMeta) -> quote::Tokens {
                     let r1 = _mm_set_ps(v0.#field_name.x, v1.#field_name.x, v2.#field_name.x, 0.0);
                     let r2 = _mm_set_ps(v0.#field_name.y, v1.#field_name.y, v2.#field_name.y, 0.0);
 
-                    let m0 = _mm_mul_ps(r0, r1);
-                    let m0 = _mm_hadd_ps(m0, m0);
-                    let m0 = _mm_hadd_ps(m0, m0);
+                    let m0 = _mm_dp_ps(r1, r0, 0xF1);
 
-                    let m1 = _mm_mul_ps(r0, r2);
-                    let m1 = _mm_hadd_ps(m1, m1);
-                    let m1 = _mm_hadd_ps(m1, m1);
+                    let m1 = _mm_dp_ps(r2, r0, 0xF1);
 
                     let u0 = _mm_cvtss_f32(m0);
                     let u1 = _mm_cvtss_f32(m1);
@@ -254,6 +256,21 @@ fn impl_interpolate_function(s: &StructMeta) -> quote::Tokens {
                 }
             }
         }
+        "f32" => {
+            let field_name = quote::Ident::from(field.name.clone());
+            quote! {
+                #field_name: unsafe {
+                    let r0 = _mm_set_ps(*w0, *w1, *w2, 0.0);
+                    let r1 = _mm_set_ps(v0.#field_name, v1.#field_name, v2.#field_name, 0.0);
+
+                    let m0 = _mm_dp_ps(r1, r0, 0xF1);
+
+                    let d0 = _mm_set_ss(*w);
+                    let d2 = _mm_div_ss(m0, d0);
+                    _mm_cvtss_f32(d2)
+                }
+            }
+        }
         _ => panic!("Cannot interpolate type '{}'", field.kind),
     });
     let name = quote::Ident::from(s.name.clone());
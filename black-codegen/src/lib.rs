@@ -40,6 +40,7 @@ mod metadata;
 use proc_macro::TokenStream;
 use metadata::read_struct_metadata;
 use interpolate::impl_interpolate;
+use interpolate_sse41::impl_interpolate as impl_interpolate_sse41;
 
 #[proc_macro_derive(Interpolate)]
 pub fn interpolate(input: TokenStream) -> TokenStream {
@@ -49,3 +50,15 @@ pub fn interpolate(input: TokenStream) -> TokenStream {
     let gen = impl_interpolate(&meta);
     gen.parse().unwrap()
 }
+
+/// Derives Interpolate using explicit SSE intrinsics. Produces the same
+/// results as #[derive(Interpolate)] within floating point tolerance and
+/// is only available on x86 and x86_64 targets.
+#[proc_macro_derive(InterpolateSse41)]
+pub fn interpolate_sse41(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = syn::parse_derive_input(&s).unwrap();
+    let meta = read_struct_metadata(&ast);
+    let gen = impl_interpolate_sse41(&meta);
+    gen.parse().unwrap()
+}
@@ -28,6 +28,7 @@ THE SOFTWARE.
 
 use super::Vec3;
 
+#[derive(Debug, Clone, Copy)]
 pub struct BoundingBox {
     pub min: Vec3,
     pub max: Vec3
@@ -0,0 +1,144 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::BoundingBox;
+use super::Ray;
+use super::Triangle;
+use super::Vec3;
+
+const LEAF_SIZE: usize = 4;
+
+#[derive(Debug, Clone)]
+enum BvhNode {
+    Leaf   { bounds: BoundingBox, start: usize, count: usize },
+    Branch { bounds: BoundingBox, left: usize, right: usize },
+}
+
+/// Bvh
+///
+/// A bounding volume hierarchy over a set of triangles for fast ray
+/// queries. Nodes are split at the median centroid along their longest
+/// axis and leaves hold a small number of triangles.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    triangles: Vec<Triangle>,
+    indices:   Vec<usize>,
+    nodes:     Vec<BvhNode>,
+}
+impl Bvh {
+    pub fn new(triangles: &[Triangle]) -> Bvh {
+        let mut bvh = Bvh {
+            triangles: triangles.to_vec(),
+            indices:   (0..triangles.len()).collect(),
+            nodes:     vec![],
+        };
+        if !triangles.is_empty() {
+            bvh.build(0, triangles.len());
+        }
+        bvh
+    }
+
+    /// Returns the index and distance of the closest triangle hit by the ray.
+    pub fn intersect(&self, ray: &Ray) -> Option<(usize, f32)> {
+        let mut closest: Option<(usize, f32)> = None;
+        let mut stack = vec![];
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(node) = stack.pop() {
+            let bounds = match &self.nodes[node] {
+                BvhNode::Leaf   { bounds, .. } => bounds,
+                BvhNode::Branch { bounds, .. } => bounds,
+            };
+            match Ray::intersect_box(ray, bounds) {
+                Some(distance) => match closest {
+                    Some((_, nearest)) if distance > nearest => continue,
+                    _ => {}
+                },
+                None => continue,
+            }
+            match &self.nodes[node] {
+                BvhNode::Leaf { start, count, .. } => {
+                    for index in &self.indices[*start..*start + *count] {
+                        if let Some(distance) = Ray::intersect_triangle(ray, &self.triangles[*index]) {
+                            match closest {
+                                Some((_, nearest)) if distance >= nearest => {}
+                                _ => closest = Some((*index, distance)),
+                            }
+                        }
+                    }
+                }
+                BvhNode::Branch { left, right, .. } => {
+                    stack.push(*right);
+                    stack.push(*left);
+                }
+            }
+        }
+        closest
+    }
+
+    fn build(&mut self, start: usize, count: usize) -> usize {
+        let bounds = self.bounds(start, count);
+        let node = self.nodes.len();
+        if count <= LEAF_SIZE {
+            self.nodes.push(BvhNode::Leaf { bounds, start, count });
+            return node;
+        }
+        let axis = Vec3::sub(&bounds.max, &bounds.min).max_axis();
+        let triangles = &self.triangles;
+        self.indices[start..start + count].sort_by(|i0, i1| {
+            let n0 = Self::centroid(&triangles[*i0])[axis];
+            let n1 = Self::centroid(&triangles[*i1])[axis];
+            n0.partial_cmp(&n1).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        // reserve this node, children are pushed after it.
+        self.nodes.push(BvhNode::Leaf { bounds, start, count });
+        let half  = count / 2;
+        let left  = self.build(start, half);
+        let right = self.build(start + half, count - half);
+        self.nodes[node] = BvhNode::Branch { bounds, left, right };
+        node
+    }
+
+    fn bounds(&self, start: usize, count: usize) -> BoundingBox {
+        let mut min = Vec3::all(f32::MAX);
+        let mut max = Vec3::all(f32::MIN);
+        for index in &self.indices[start..start + count] {
+            let triangle = &self.triangles[*index];
+            for v in [triangle.v0, triangle.v1, triangle.v2].iter() {
+                min = Vec3::min(&min, v);
+                max = Vec3::max(&max, v);
+            }
+        }
+        BoundingBox::new(min, max)
+    }
+
+    fn centroid(triangle: &Triangle) -> Vec3 {
+        Vec3::scale(&Vec3::add(&Vec3::add(&triangle.v0, &triangle.v1), &triangle.v2), 1.0 / 3.0)
+    }
+}
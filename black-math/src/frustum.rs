@@ -28,31 +28,9 @@ THE SOFTWARE.
 
 use super::Mat4;
 use super::Plane;
-use super::Ray;
 use super::Vec3;
 use std::fmt::{Display, Error, Formatter};
 
-#[inline(always)]
-fn compute_intersection_ray(p0: &Plane, p1: &Plane) -> Ray {
-    let n0 = Plane::normal(&p0);
-    let n1 = Plane::normal(&p1);
-    let v0 = Vec3::cross(&n0, &n1);
-    let num = Vec3::length_sq(&v0);
-    let v1 = Vec3::scale(&n1, -p0.d);
-    let v2 = Vec3::scale(&n0, p1.d);
-    let v3 = Vec3::add(&v1, &v2);
-    let v4 = Vec3::cross(&v3, &v0);
-    let v5 = Vec3::new(v4.x / num, v4.y / num, v4.z / num);
-    Ray::new(v5, v0)
-}
-
-#[inline(always)]
-fn compute_intersection_vector(plane: &Plane, ray: &Ray) -> Vec3 {
-    let n0 = Plane::normal(&plane);
-    let num = (-plane.d - Vec3::dot(&n0, &ray.position)) / Vec3::dot(&n0, &ray.direction);
-    Vec3::add(&ray.position, &Vec3::scale(&ray.direction, num))
-}
-
 #[derive(Debug, Clone)]
 pub struct Frustum {
     pub near: Plane,
@@ -64,7 +42,8 @@ pub struct Frustum {
     pub corners: Vec<Vec3>,
 }
 impl Frustum {
-    /// Constructs a new Frustum from the given projection Mat4.
+    /// Constructs a new Frustum from the given combined view projection Mat4
+    /// (view * projection). Planes are normalized with normals facing outward.
     pub fn new(m0: &Mat4) -> Frustum {
         let mut planes = vec![
             // near
@@ -112,19 +91,21 @@ impl Frustum {
             plane.c = plane.c / len;
             plane.d = plane.d / len;
         }
-        let mut corners: Vec<Vec3> = Vec::with_capacity(8);
-        let mut ray = compute_intersection_ray(&planes[0], &planes[2]);
-        corners[0] = compute_intersection_vector(&planes[4], &ray);
-        corners[3] = compute_intersection_vector(&planes[5], &ray);
-        ray = compute_intersection_ray(&planes[3], &planes[0]);
-        corners[1] = compute_intersection_vector(&planes[4], &ray);
-        corners[2] = compute_intersection_vector(&planes[5], &ray);
-        ray = compute_intersection_ray(&planes[2], &planes[1]);
-        corners[4] = compute_intersection_vector(&planes[4], &ray);
-        corners[7] = compute_intersection_vector(&planes[5], &ray);
-        ray = compute_intersection_ray(&planes[1], &planes[3]);
-        corners[5] = compute_intersection_vector(&planes[4], &ray);
-        corners[6] = compute_intersection_vector(&planes[5], &ray);
+        // corners are near then far, each ordered top left, top right,
+        // bottom right, bottom left. Degenerate matrices yield zero corners.
+        let corner = |p0: usize, p1: usize, p2: usize| {
+            Plane::intersect_planes(&planes[p0], &planes[p1], &planes[p2]).unwrap_or_else(Vec3::zero)
+        };
+        let corners = vec![
+            corner(0, 2, 4),
+            corner(0, 3, 4),
+            corner(0, 3, 5),
+            corner(0, 2, 5),
+            corner(1, 2, 4),
+            corner(1, 3, 4),
+            corner(1, 3, 5),
+            corner(1, 2, 5),
+        ];
         Frustum {
             near: planes[0].clone(),
             far: planes[1].clone(),
@@ -135,6 +116,10 @@ impl Frustum {
             corners,
         }
     }
+    /// Constructs a new Frustum from the given view and projection matrices.
+    pub fn from_view_projection(view: &Mat4, projection: &Mat4) -> Frustum {
+        Frustum::new(&Mat4::mul(view, projection))
+    }
     #[inline(always)]
     pub fn equals(f0: &Frustum, f1: &Frustum) -> bool {
         Plane::equals(&f0.near, &f1.near)
@@ -37,6 +37,11 @@ mod sphere;
 mod ray;
 mod bounding_box;
 mod frustum;
+mod bvh;
+mod winding;
+mod rng;
+pub mod noise;
+pub mod skinning;
 
 pub use mat4::Mat4;
 pub use vec2::Vec2;
@@ -49,3 +54,6 @@ pub use sphere::Sphere;
 pub use ray::Ray;
 pub use bounding_box::BoundingBox;
 pub use frustum::Frustum;
+pub use bvh::Bvh;
+pub use winding::Winding;
+pub use rng::Rng;
@@ -29,10 +29,20 @@ THE SOFTWARE.
 use super::Plane;
 use super::Quaternion;
 use super::Vec3;
+use super::Vec4;
 use std::fmt::{Display, Error, Formatter};
 use std::ops::{Add, Div, Mul, Sub};
 
+/// A 4x4 matrix stored row major, where mRC is row R and column C.
+///
+/// Vectors are row vectors multiplied on the left, `v * m`, so the
+/// translation lives in row 4 (m41, m42, m43). Transforms compose left to
+/// right: `v * (a * b)` applies a, then b. A typical vertex transform is
+/// `position * (model * (view * projection))`. The layout is C compatible,
+/// 16 contiguous f32 values with no padding, so each row may be loaded as
+/// four contiguous f32 values.
 #[derive(Debug, Clone, Copy)]
+#[repr(C)]
 pub struct Mat4 {
     pub m11: f32, pub m12: f32, pub m13: f32, pub m14: f32,
     pub m21: f32, pub m22: f32, pub m23: f32, pub m24: f32,
@@ -53,6 +63,12 @@ impl Mat4 {
             m41, m42, m43, m44,
         }
     }
+    /// Returns the matrix as a slice of 16 f32 values in row major order.
+    #[inline(always)]
+    pub fn as_f32_slice(&self) -> &[f32] {
+        // Safety: Mat4 is repr(C) with 16 f32 fields and no padding.
+        unsafe { std::slice::from_raw_parts(self as *const Mat4 as *const f32, 16) }
+    }
     #[inline(always)]
     pub fn equals(m0: &Mat4, m1: &Mat4) -> bool {
         m0.m11 == m1.m11 &&
@@ -72,6 +88,19 @@ impl Mat4 {
         m0.m43 == m1.m43 &&
         m0.m44 == m1.m44
     }
+    /// Returns true if each component of m0 is within epsilon of the
+    /// corresponding component of m1.
+    #[inline(always)]
+    pub fn approx_equals(m0: &Mat4, m1: &Mat4, epsilon: f32) -> bool {
+        m0.as_f32_slice().iter().zip(m1.as_f32_slice().iter()).all(|(n0, n1)| (n0 - n1).abs() <= epsilon)
+    }
+    /// Returns true if each component of m0 is within epsilon of the
+    /// identity matrix. Useful to assert a matrix times its inverse, or a
+    /// rotation times its transpose, has not drifted.
+    #[inline(always)]
+    pub fn is_identity(m0: &Mat4, epsilon: f32) -> bool {
+        Mat4::approx_equals(m0, &Mat4::identity(), epsilon)
+    }
     #[inline(always)]
     pub fn zero() -> Mat4 {
         Mat4::new(
@@ -102,6 +131,46 @@ impl Mat4 {
             v0.x, 0.0, 0.0, 0.0, 0.0, v0.y, 0.0, 0.0, 0.0, 0.0, v0.z, 0.0, 0.0, 0.0, 0.0, 1.0,
         )
     }
+    /// Creates a shear. Each coefficient names the coordinate being offset
+    /// followed by the coordinate it is offset by, such that
+    /// x' = x + (xy * y) + (xz * z), and likewise for y and z.
+    #[inline(always)]
+    pub fn shear(xy: f32, xz: f32, yx: f32, yz: f32, zx: f32, zy: f32) -> Mat4 {
+        Mat4::new(
+            1.0, yx,  zx,  0.0,
+            xy,  1.0, zy,  0.0,
+            xz,  yz,  1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        )
+    }
+    /// Creates a mirror across the yz plane, negating x.
+    #[inline(always)]
+    pub fn mirror_x() -> Mat4 {
+        Mat4::scale(&Vec3::new(-1.0, 1.0, 1.0))
+    }
+    /// Creates a mirror across the xz plane, negating y.
+    #[inline(always)]
+    pub fn mirror_y() -> Mat4 {
+        Mat4::scale(&Vec3::new(1.0, -1.0, 1.0))
+    }
+    /// Creates a mirror across the xy plane, negating z.
+    #[inline(always)]
+    pub fn mirror_z() -> Mat4 {
+        Mat4::scale(&Vec3::new(1.0, 1.0, -1.0))
+    }
+    /// Creates a transform from the given axes and translation. The axes are
+    /// placed in rows 0 to 2 and the translation in row 3. Being right handed,
+    /// row 2 holds the negated forward axis. This is the inverse of the right,
+    /// up, forward and translation_part accessors.
+    #[inline(always)]
+    pub fn from_basis(right: &Vec3, up: &Vec3, forward: &Vec3, translation: &Vec3) -> Mat4 {
+        Mat4::new(
+            right.x,       right.y,       right.z,       0.0,
+            up.x,          up.y,          up.z,          0.0,
+            -forward.x,    -forward.y,    -forward.z,    0.0,
+            translation.x, translation.y, translation.z, 1.0,
+        )
+    }
     #[inline(always)]
     pub fn rotation_x(radians: f32) -> Mat4 {
         let cos = f32::cos(radians);
@@ -158,6 +227,28 @@ impl Mat4 {
             1.0,
         )
     }
+    /// Creates the shortest rotation taking the direction of from to the
+    /// direction of to. Neither vector need be unit length. Antiparallel
+    /// vectors rotate half a turn about an axis perpendicular to from.
+    #[inline(always)]
+    pub fn rotation_between(from: &Vec3, to: &Vec3) -> Mat4 {
+        let n0 = Vec3::normalize(from);
+        let n1 = Vec3::normalize(to);
+        let n2 = Vec3::dot(&n0, &n1);
+        if n2 >= 1.0 - 1e-6 {
+            return Mat4::identity();
+        }
+        if n2 <= -1.0 + 1e-6 {
+            let (axis, _) = Vec3::orthonormal_basis(&n0);
+            return Mat4::from_axis_angle(&axis, std::f32::consts::PI);
+        }
+        let axis = Vec3::normalize(&Vec3::cross(&n0, &n1));
+        Mat4::from_axis_angle(&axis, f32::acos(n2))
+    }
+    /// Creates a right handed perspective projection looking down -z, mapping
+    /// view depth near..far to NDC z 0.0..1.0. Use with Mat4::look_at. This
+    /// is the convention used by the rasterizer and the example, with front
+    /// faces wound counter clockwise.
     #[inline(always)]
     pub fn perspective_fov(fov: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
         let n0 = 1.0 / f32::tan(fov * 0.5);
@@ -181,6 +272,55 @@ impl Mat4 {
         m0.m43 = (near * far) / (near - far);
         m0
     }
+    /// Creates a left handed perspective projection looking down +z, mapping
+    /// view depth near..far to NDC z 0.0..1.0. Use with Mat4::look_at_lh.
+    /// The left handed pair mirrors the image in x relative to the right
+    /// handed pair, so meshes authored for left handed (DirectX) conventions
+    /// should be drawn with Winding::Clockwise.
+    #[inline(always)]
+    pub fn perspective_fov_lh(fov: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+        let n0 = 1.0 / f32::tan(fov * 0.5);
+        let n1 = n0 / aspect;
+        let mut m0 = Mat4::zero();
+        m0.m11 = n1;
+        m0.m22 = n0;
+        m0.m33 = far / (far - near);
+        m0.m34 = 1.0;
+        m0.m43 = -(near * far) / (far - near);
+        m0
+    }
+    /// Creates a perspective projection with the far plane at infinity. View
+    /// depth d maps to an NDC depth of 1.0 - near / d, so the near plane maps
+    /// to 0.0 and distant points approach but never reach 1.0.
+    #[inline(always)]
+    pub fn perspective_fov_infinite(fov: f32, aspect: f32, near: f32) -> Mat4 {
+        let n0 = 1.0 / f32::tan(fov * 0.5);
+        let n1 = n0 / aspect;
+        let mut m0 = Mat4::zero();
+        m0.m11 = n1;
+        m0.m22 = n0;
+        m0.m33 = -1.0;
+        m0.m34 = -1.0;
+        m0.m43 = -near;
+        m0
+    }
+    /// Creates a reversed-Z perspective projection mapping the near plane to a
+    /// depth of 1.0 and the far plane to 0.0, which distributes floating point
+    /// depth precision more evenly across the view distance. Depth buffers used
+    /// with this projection should be cleared to 0.0 and use a greater than
+    /// depth comparison, see DepthConfig::reverse_z() in the rasterizer.
+    #[inline(always)]
+    pub fn perspective_fov_reverse_z(fov: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+        let n0 = 1.0 / f32::tan(fov * 0.5);
+        let n1 = n0 / aspect;
+        let mut m0 = Mat4::zero();
+        m0.m11 = n1;
+        m0.m22 = n0;
+        m0.m33 = near / (far - near);
+        m0.m34 = -1.0;
+        m0.m43 = (near * far) / (far - near);
+        m0
+    }
     #[inline(always)]
     pub fn perspective(width: f32, height: f32, near: f32, far: f32) -> Mat4 {
         let mut m0 = Mat4::zero();
@@ -230,6 +370,7 @@ impl Mat4 {
         m0.m44 = 0.0;
         m0
     }
+    /// Creates a right handed orthographic projection looking down -z.
     #[inline(always)]
     pub fn orthographic(width: f32, height: f32, near: f32, far: f32) -> Mat4 {
         let mut m0 = Mat4::zero();
@@ -251,6 +392,17 @@ impl Mat4 {
         m0.m44 = 1.0;
         m0
     }
+    /// Creates a left handed orthographic projection looking down +z.
+    #[inline(always)]
+    pub fn orthographic_lh(width: f32, height: f32, near: f32, far: f32) -> Mat4 {
+        let mut m0 = Mat4::zero();
+        m0.m11 = 2.0 / width;
+        m0.m22 = 2.0 / height;
+        m0.m33 = 1.0 / (far - near);
+        m0.m43 = near / (near - far);
+        m0.m44 = 1.0;
+        m0
+    }
     #[inline(always)]
     pub fn orthographic_offset(
         left: f32,
@@ -279,6 +431,8 @@ impl Mat4 {
         m0.m44 = 1.0;
         m0
     }
+    /// Creates a right handed view matrix. The camera looks down -z in view
+    /// space with +x to the right.
     #[inline(always)]
     pub fn look_at(position: &Vec3, target: &Vec3, up: &Vec3) -> Mat4 {
         let mut m0 = Mat4::zero();
@@ -303,6 +457,29 @@ impl Mat4 {
         m0.m44 = 1.0;
         m0
     }
+    /// Creates a left handed view matrix. The camera looks down +z in view
+    /// space with +x to the right.
+    #[inline(always)]
+    pub fn look_at_lh(position: &Vec3, target: &Vec3, up: &Vec3) -> Mat4 {
+        let mut m0 = Mat4::zero();
+        let v0 = Vec3::normalize(&Vec3::sub(target, position));
+        let v1 = Vec3::normalize(&Vec3::cross(up, &v0));
+        let v2 = Vec3::cross(&v0, &v1);
+        m0.m11 = v1.x;
+        m0.m12 = v2.x;
+        m0.m13 = v0.x;
+        m0.m21 = v1.y;
+        m0.m22 = v2.y;
+        m0.m23 = v0.y;
+        m0.m31 = v1.z;
+        m0.m32 = v2.z;
+        m0.m33 = v0.z;
+        m0.m41 = -Vec3::dot(&v1, position);
+        m0.m42 = -Vec3::dot(&v2, position);
+        m0.m43 = -Vec3::dot(&v0, position);
+        m0.m44 = 1.0;
+        m0
+    }
     #[inline(always)]
     pub fn from_quaternion(q0: &Quaternion) -> Mat4 {
         let mut m0 = Mat4::zero();
@@ -555,8 +732,21 @@ impl Mat4 {
         m2.m44 = m0.m44 - m1.m44;
         m2
     }
+    /// Returns m0 * m1, the transform that applies m0 then m1. With the simd
+    /// feature enabled this uses SSE when the CPU supports it.
     #[inline(always)]
     pub fn mul(m0: &Mat4, m1: &Mat4) -> Mat4 {
+        #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            if cfg!(target_feature = "sse") || is_x86_feature_detected!("sse") {
+                return unsafe { Mat4::mul_sse(m0, m1) };
+            }
+        }
+        Mat4::mul_scalar(m0, m1)
+    }
+    /// Returns m0 * m1 without SIMD.
+    #[inline(always)]
+    pub fn mul_scalar(m0: &Mat4, m1: &Mat4) -> Mat4 {
         let mut m2 = Mat4::zero();
         m2.m11 = (((m0.m11 * m1.m11) + (m0.m12 * m1.m21)) + (m0.m13 * m1.m31)) + (m0.m14 * m1.m41);
         m2.m12 = (((m0.m11 * m1.m12) + (m0.m12 * m1.m22)) + (m0.m13 * m1.m32)) + (m0.m14 * m1.m42);
@@ -576,6 +766,129 @@ impl Mat4 {
         m2.m44 = (((m0.m41 * m1.m14) + (m0.m42 * m1.m24)) + (m0.m43 * m1.m34)) + (m0.m44 * m1.m44);
         m2
     }
+    /// Returns m0 * m1 using SSE. Each output row is the sum of the rows of
+    /// m1 scaled by the broadcast elements of the matching row of m0.
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[target_feature(enable = "sse")]
+    #[inline]
+    unsafe fn mul_sse(m0: &Mat4, m1: &Mat4) -> Mat4 {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        let n0 = m1 as *const Mat4 as *const f32;
+        let r1 = _mm_loadu_ps(n0);
+        let r2 = _mm_loadu_ps(n0.add(4));
+        let r3 = _mm_loadu_ps(n0.add(8));
+        let r4 = _mm_loadu_ps(n0.add(12));
+        let row = |n0: f32, n1: f32, n2: f32, n3: f32| {
+            let mut out = [0.0; 4];
+            let n4 = _mm_add_ps(
+                _mm_add_ps(
+                    _mm_add_ps(
+                        _mm_mul_ps(_mm_set1_ps(n0), r1),
+                        _mm_mul_ps(_mm_set1_ps(n1), r2)),
+                    _mm_mul_ps(_mm_set1_ps(n2), r3)),
+                _mm_mul_ps(_mm_set1_ps(n3), r4));
+            _mm_storeu_ps(out.as_mut_ptr(), n4);
+            out
+        };
+        let [m11, m12, m13, m14] = row(m0.m11, m0.m12, m0.m13, m0.m14);
+        let [m21, m22, m23, m24] = row(m0.m21, m0.m22, m0.m23, m0.m24);
+        let [m31, m32, m33, m34] = row(m0.m31, m0.m32, m0.m33, m0.m34);
+        let [m41, m42, m43, m44] = row(m0.m41, m0.m42, m0.m43, m0.m44);
+        Mat4::new(
+            m11, m12, m13, m14,
+            m21, m22, m23, m24,
+            m31, m32, m33, m34,
+            m41, m42, m43, m44,
+        )
+    }
+    /// Transforms each point in input by m0, writing results to output. Only
+    /// the first min(input.len(), output.len()) points are transformed. Uses
+    /// SSE when available, see Mat4::mul.
+    pub fn transform_points(m0: &Mat4, input: &[Vec4], output: &mut [Vec4]) {
+        #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            if cfg!(target_feature = "sse") || is_x86_feature_detected!("sse") {
+                unsafe { Mat4::transform_points_sse(m0, input, output) };
+                return;
+            }
+        }
+        for (v0, v1) in input.iter().zip(output.iter_mut()) {
+            *v1 = Vec4::transform_scalar(v0, m0);
+        }
+    }
+    /// Transforms each Vec3 in input by m0 as a point (w = 1), writing results
+    /// to output. See Mat4::transform_points.
+    pub fn transform_points3(m0: &Mat4, input: &[Vec3], output: &mut [Vec3]) {
+        #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            if cfg!(target_feature = "sse") || is_x86_feature_detected!("sse") {
+                unsafe { Mat4::transform_points3_sse(m0, input, output) };
+                return;
+            }
+        }
+        for (v0, v1) in input.iter().zip(output.iter_mut()) {
+            *v1 = Vec3::transform(v0, m0);
+        }
+    }
+    /// Transforms points using SSE, loading the rows of m0 once for the batch.
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[target_feature(enable = "sse")]
+    unsafe fn transform_points_sse(m0: &Mat4, input: &[Vec4], output: &mut [Vec4]) {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        let n0 = m0 as *const Mat4 as *const f32;
+        let r1 = _mm_loadu_ps(n0);
+        let r2 = _mm_loadu_ps(n0.add(4));
+        let r3 = _mm_loadu_ps(n0.add(8));
+        let r4 = _mm_loadu_ps(n0.add(12));
+        let mut out = [0.0; 4];
+        for (v0, v1) in input.iter().zip(output.iter_mut()) {
+            let n1 = _mm_add_ps(
+                _mm_add_ps(
+                    _mm_add_ps(
+                        _mm_mul_ps(_mm_set1_ps(v0.x), r1),
+                        _mm_mul_ps(_mm_set1_ps(v0.y), r2)),
+                    _mm_mul_ps(_mm_set1_ps(v0.z), r3)),
+                _mm_mul_ps(_mm_set1_ps(v0.w), r4));
+            _mm_storeu_ps(out.as_mut_ptr(), n1);
+            *v1 = Vec4::new(out[0], out[1], out[2], out[3]);
+        }
+    }
+    /// Transforms Vec3 points using SSE. The translation row is added in place
+    /// of multiplying by w = 1.
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[target_feature(enable = "sse")]
+    unsafe fn transform_points3_sse(m0: &Mat4, input: &[Vec3], output: &mut [Vec3]) {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        let n0 = m0 as *const Mat4 as *const f32;
+        let r1 = _mm_loadu_ps(n0);
+        let r2 = _mm_loadu_ps(n0.add(4));
+        let r3 = _mm_loadu_ps(n0.add(8));
+        let r4 = _mm_loadu_ps(n0.add(12));
+        let mut out = [0.0; 4];
+        for (v0, v1) in input.iter().zip(output.iter_mut()) {
+            let n1 = _mm_add_ps(
+                _mm_add_ps(
+                    _mm_add_ps(
+                        _mm_mul_ps(_mm_set1_ps(v0.x), r1),
+                        _mm_mul_ps(_mm_set1_ps(v0.y), r2)),
+                    _mm_mul_ps(_mm_set1_ps(v0.z), r3)),
+                r4);
+            _mm_storeu_ps(out.as_mut_ptr(), n1);
+            *v1 = Vec3::new(out[0], out[1], out[2]);
+        }
+    }
     #[inline(always)]
     pub fn div(m0: &Mat4, m1: &Mat4) -> Mat4 {
         let mut m2 = Mat4::zero();
@@ -599,6 +912,89 @@ impl Mat4 {
     }
 }
 
+// ------------------------------------------------------------
+//
+// Instance functions.
+//
+// ------------------------------------------------------------
+
+impl Mat4 {
+    #[inline(always)]
+    pub fn row(&self, i: usize) -> Option<Vec4> {
+        match i {
+            0 => Some(Vec4::new(self.m11, self.m12, self.m13, self.m14)),
+            1 => Some(Vec4::new(self.m21, self.m22, self.m23, self.m24)),
+            2 => Some(Vec4::new(self.m31, self.m32, self.m33, self.m34)),
+            3 => Some(Vec4::new(self.m41, self.m42, self.m43, self.m44)),
+            _ => None
+        }
+    }
+    #[inline(always)]
+    pub fn col(&self, i: usize) -> Option<Vec4> {
+        match i {
+            0 => Some(Vec4::new(self.m11, self.m21, self.m31, self.m41)),
+            1 => Some(Vec4::new(self.m12, self.m22, self.m32, self.m42)),
+            2 => Some(Vec4::new(self.m13, self.m23, self.m33, self.m43)),
+            3 => Some(Vec4::new(self.m14, self.m24, self.m34, self.m44)),
+            _ => None
+        }
+    }
+    #[inline(always)]
+    pub fn set_row(&mut self, i: usize, v0: &Vec4) -> Option<()> {
+        let (n0, n1, n2, n3) = match i {
+            0 => (&mut self.m11, &mut self.m12, &mut self.m13, &mut self.m14),
+            1 => (&mut self.m21, &mut self.m22, &mut self.m23, &mut self.m24),
+            2 => (&mut self.m31, &mut self.m32, &mut self.m33, &mut self.m34),
+            3 => (&mut self.m41, &mut self.m42, &mut self.m43, &mut self.m44),
+            _ => return None
+        };
+        *n0 = v0.x;
+        *n1 = v0.y;
+        *n2 = v0.z;
+        *n3 = v0.w;
+        Some(())
+    }
+    #[inline(always)]
+    pub fn set_col(&mut self, i: usize, v0: &Vec4) -> Option<()> {
+        let (n0, n1, n2, n3) = match i {
+            0 => (&mut self.m11, &mut self.m21, &mut self.m31, &mut self.m41),
+            1 => (&mut self.m12, &mut self.m22, &mut self.m32, &mut self.m42),
+            2 => (&mut self.m13, &mut self.m23, &mut self.m33, &mut self.m43),
+            3 => (&mut self.m14, &mut self.m24, &mut self.m34, &mut self.m44),
+            _ => return None
+        };
+        *n0 = v0.x;
+        *n1 = v0.y;
+        *n2 = v0.z;
+        *n3 = v0.w;
+        Some(())
+    }
+    /// Returns the local x axis of this transform (row 0). Matrices in this
+    /// library are right handed with row vectors, so for a view matrix
+    /// created with look_at, call this on the inverted matrix to get the
+    /// camera axis in world space.
+    #[inline(always)]
+    pub fn right(&self) -> Vec3 {
+        Vec3::new(self.m11, self.m12, self.m13)
+    }
+    /// Returns the local y axis of this transform (row 1).
+    #[inline(always)]
+    pub fn up(&self) -> Vec3 {
+        Vec3::new(self.m21, self.m22, self.m23)
+    }
+    /// Returns the local forward axis of this transform. Being right handed,
+    /// forward is the negated z axis (row 2).
+    #[inline(always)]
+    pub fn forward(&self) -> Vec3 {
+        Vec3::new(-self.m31, -self.m32, -self.m33)
+    }
+    /// Returns the translation of this transform (row 3).
+    #[inline(always)]
+    pub fn translation_part(&self) -> Vec3 {
+        Vec3::new(self.m41, self.m42, self.m43)
+    }
+}
+
 // ------------------------------------------------------------
 //
 // Equality Operator
@@ -681,7 +1077,27 @@ impl Div<Mat4> for Mat4 {
 // ------------------------------------------------------------
 
 impl Display for Mat4 {
+    /// Formats all fields on one line. With the alternate flag, `{:#}`,
+    /// formats the matrix as four rows with right aligned columns, using
+    /// the given precision or 4 decimal places.
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if f.alternate() {
+            let precision = f.precision().unwrap_or(4);
+            let values = [
+                self.m11, self.m12, self.m13, self.m14,
+                self.m21, self.m22, self.m23, self.m24,
+                self.m31, self.m32, self.m33, self.m34,
+                self.m41, self.m42, self.m43, self.m44,
+            ].iter().map(|n| format!("{:.*}", precision, n)).collect::<Vec<_>>();
+            let width = values.iter().map(|n| n.len()).max().unwrap_or(0);
+            for (index, row) in values.chunks(4).enumerate() {
+                if index > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "[ {:>w$} {:>w$} {:>w$} {:>w$} ]", row[0], row[1], row[2], row[3], w = width)?;
+            }
+            return Ok(());
+        }
         write!(
             f,
             "Mat4 {{ m11: {}, m12: {}, m13: {}, m14: {}, m21: {}, m22: {}, m23: {}, m24: {}, m31: {}, m32: {}, m33: {}, m34: {}, m41: {}, m42: {}, m43: {}, m44: {}, }}",
@@ -0,0 +1,105 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+//! Deterministic, seedable 2D noise. All functions use integer hashing and
+//! plain float arithmetic so the same inputs produce the same outputs on
+//! every platform.
+
+#[inline(always)]
+fn hash(x: i32, y: i32, seed: u32) -> u32 {
+    let mut n0 = seed
+        ^ (x as u32).wrapping_mul(0x27d4_eb2d)
+        ^ (y as u32).wrapping_mul(0x1656_67b1);
+    n0 ^= n0 >> 15;
+    n0 = n0.wrapping_mul(0x2c1b_3c6d);
+    n0 ^= n0 >> 12;
+    n0 = n0.wrapping_mul(0x297a_2d39);
+    n0 ^= n0 >> 15;
+    n0
+}
+
+#[inline(always)]
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[inline(always)]
+fn lerp(a: f32, b: f32, amount: f32) -> f32 {
+    a + (b - a) * amount
+}
+
+#[inline(always)]
+fn gradient(x: i32, y: i32, seed: u32, dx: f32, dy: f32) -> f32 {
+    match hash(x, y, seed) & 7 {
+        0 =>  dx + dy,
+        1 => -dx + dy,
+        2 =>  dx - dy,
+        3 => -dx - dy,
+        4 =>  dx,
+        5 => -dx,
+        6 =>  dy,
+        _ => -dy,
+    }
+}
+
+/// Returns smoothly interpolated value noise at the given point, in the
+/// range 0.0 to 1.0. Values are random at integer coordinates.
+pub fn value_noise_2d(x: f32, y: f32, seed: u32) -> f32 {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let u = fade(x - x0);
+    let v = fade(y - y0);
+    let value = |x: i32, y: i32| (hash(x, y, seed) >> 8) as f32 / 16_777_215.0;
+    let n0 = lerp(value(ix, iy),     value(ix + 1, iy),     u);
+    let n1 = lerp(value(ix, iy + 1), value(ix + 1, iy + 1), u);
+    lerp(n0, n1, v).clamp(0.0, 1.0)
+}
+
+/// Returns gradient (perlin) noise at the given point, in the range -1.0
+/// to 1.0. Noise is 0.0 at integer coordinates.
+pub fn perlin_2d(x: f32, y: f32, seed: u32) -> f32 {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let dx = x - x0;
+    let dy = y - y0;
+    let u = fade(dx);
+    let v = fade(dy);
+    let n0 = lerp(
+        gradient(ix,     iy, seed, dx,       dy),
+        gradient(ix + 1, iy, seed, dx - 1.0, dy),
+        u,
+    );
+    let n1 = lerp(
+        gradient(ix,     iy + 1, seed, dx,       dy - 1.0),
+        gradient(ix + 1, iy + 1, seed, dx - 1.0, dy - 1.0),
+        u,
+    );
+    lerp(n0, n1, v).clamp(-1.0, 1.0)
+}
@@ -28,6 +28,7 @@ THE SOFTWARE.
 
 use super::BoundingBox;
 use super::Mat4;
+use super::Ray;
 use super::Sphere;
 use super::Vec3;
 use super::Vec4;
@@ -149,6 +150,44 @@ impl Plane {
         }
     }
     #[inline(always)]
+    pub fn intersect_ray(plane: &Plane, ray: &Ray) -> Option<Vec3> {
+        Ray::intersect_plane(ray, plane).map(|distance| Ray::at(ray, distance))
+    }
+    /// Returns the line where p0 and p1 meet as a ray with a unit direction
+    /// of cross(normal(p0), normal(p1)), positioned at the point on the line
+    /// nearest the origin. Returns None if the planes are parallel.
+    #[inline(always)]
+    pub fn intersect_line(p0: &Plane, p1: &Plane) -> Option<Ray> {
+        let n0 = Plane::normal(p0);
+        let n1 = Plane::normal(p1);
+        let n2 = Vec3::cross(&n0, &n1);
+        let n3 = Vec3::length_sq(&n2);
+        if n3 <= 1E-12 * Vec3::length_sq(&n0) * Vec3::length_sq(&n1) {
+            return None;
+        }
+        let v0 = Vec3::add(&Vec3::scale(&n1, -p0.d), &Vec3::scale(&n0, p1.d));
+        let v1 = Vec3::scale(&Vec3::cross(&v0, &n2), 1.0 / n3);
+        Some(Ray::new(v1, Vec3::normalize(&n2)))
+    }
+    /// Returns the point common to p0, p1 and p2. Returns None if any two
+    /// planes are parallel or the three planes share a line.
+    #[inline(always)]
+    pub fn intersect_planes(p0: &Plane, p1: &Plane, p2: &Plane) -> Option<Vec3> {
+        let n0 = Plane::normal(p0);
+        let n1 = Plane::normal(p1);
+        let n2 = Plane::normal(p2);
+        let c0 = Vec3::cross(&n1, &n2);
+        let c1 = Vec3::cross(&n2, &n0);
+        let c2 = Vec3::cross(&n0, &n1);
+        let det = Vec3::dot(&n0, &c0);
+        let len = Vec3::length(&n0) * Vec3::length(&n1) * Vec3::length(&n2);
+        if f32::abs(det) <= 1E-06 * len {
+            return None;
+        }
+        let v0 = Vec3::add(&Vec3::add(&Vec3::scale(&c0, -p0.d), &Vec3::scale(&c1, -p1.d)), &Vec3::scale(&c2, -p2.d));
+        Some(Vec3::scale(&v0, 1.0 / det))
+    }
+    #[inline(always)]
     pub fn intersect_sphere(plane: &Plane, sphere: &Sphere) -> PlaneIntersection {
         let n0 = (sphere.position.x * plane.a)
             + (sphere.position.y * plane.b)
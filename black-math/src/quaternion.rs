@@ -31,7 +31,10 @@ use super::Vec3;
 use std::fmt::{Display, Error, Formatter};
 use std::ops::{Add, Div, Mul, Sub};
 
+/// The layout is C compatible: 4 contiguous f32 values ordered x, y, z, w
+/// with no padding, so a Quaternion may be viewed as [f32; 4].
 #[derive(Debug, Clone, Copy)]
+#[repr(C)]
 pub struct Quaternion {
     pub x: f32,
     pub y: f32,
@@ -43,10 +46,32 @@ impl Quaternion {
     pub fn new(x: f32, y: f32, z: f32, w: f32) -> Quaternion {
         Quaternion { x, y, z, w }
     }
+    /// Returns the components of this Quaternion as a slice of 4 f32 values.
+    #[inline(always)]
+    pub fn as_f32_slice(&self) -> &[f32] {
+        // Safety: Quaternion is repr(C) with 4 f32 fields and no padding.
+        unsafe { std::slice::from_raw_parts(self as *const Quaternion as *const f32, 4) }
+    }
     #[inline(always)]
     pub fn equals(q0: &Quaternion, q1: &Quaternion) -> bool {
         q0.x == q1.x && q0.y == q1.y && q0.z == q1.z && q0.w == q1.w
     }
+    /// Returns true if each component of q0 is within epsilon of the
+    /// corresponding component of q1. Note q and -q are the same rotation
+    /// but are not approximately equal.
+    #[inline(always)]
+    pub fn approx_equals(q0: &Quaternion, q1: &Quaternion, epsilon: f32) -> bool {
+        (q0.x - q1.x).abs() <= epsilon &&
+        (q0.y - q1.y).abs() <= epsilon &&
+        (q0.z - q1.z).abs() <= epsilon &&
+        (q0.w - q1.w).abs() <= epsilon
+    }
+    /// Returns true if the length of q0 is within epsilon of 1.0, as
+    /// required for q0 to represent a rotation.
+    #[inline(always)]
+    pub fn is_normalized(q0: &Quaternion, epsilon: f32) -> bool {
+        (Quaternion::length(q0) - 1.0).abs() <= epsilon
+    }
     #[inline(always)]
     pub fn length(q0: &Quaternion) -> f32 {
         f32::sqrt((q0.x * q0.x) + (q0.y * q0.y) + (q0.z * q0.z) + (q0.w * q0.w))
@@ -55,6 +80,8 @@ impl Quaternion {
     pub fn length_sq(q0: &Quaternion) -> f32 {
         (q0.x * q0.x) + (q0.y * q0.y) + (q0.z * q0.z) + (q0.w * q0.w)
     }
+    /// Returns q0 scaled to unit length. q0 must have a non-zero, finite
+    /// length, otherwise the result is infinite or NaN.
     #[inline(always)]
     pub fn normalize(q0: &Quaternion) -> Quaternion {
         let len = 1.0 / f32::sqrt((q0.x * q0.x) + (q0.y * q0.y) + (q0.z * q0.z) + (q0.w * q0.w));
@@ -105,6 +132,57 @@ impl Quaternion {
             (n1 * q0.w) + (n0 * q1.w),
         )
     }
+    /// Spherical cubic interpolation between q0 and q1 with control
+    /// quaternions a and b. Chaining squad across keyframes with controls
+    /// from squad_control gives an orientation spline whose angular
+    /// velocity is continuous at the keyframes, where slerp is not. When
+    /// a equals q0 and b equals q1 this is slerp.
+    #[inline(always)]
+    pub fn squad(q0: &Quaternion, a: &Quaternion, b: &Quaternion, q1: &Quaternion, amount: f32) -> Quaternion {
+        let n0 = Quaternion::slerp(q0, q1, amount);
+        let n1 = Quaternion::slerp(a, b, amount);
+        Quaternion::slerp(&n0, &n1, 2.0 * amount * (1.0 - amount))
+    }
+    /// Returns the squad control quaternion for the unit keyframe q1 given
+    /// its neighbours q0 and q2. For the segment q1 to q2 use
+    /// squad(q1, squad_control(q0, q1, q2), squad_control(q1, q2, q3), q2, t).
+    /// At the ends of a path pass the end keyframe as its own neighbour.
+    #[inline(always)]
+    pub fn squad_control(q0: &Quaternion, q1: &Quaternion, q2: &Quaternion) -> Quaternion {
+        // keep the neighbours in the same hemisphere as q1.
+        let n0 = if Quaternion::dot(q0, q1) < 0.0 { Quaternion::negate(q0) } else { *q0 };
+        let n1 = if Quaternion::dot(q2, q1) < 0.0 { Quaternion::negate(q2) } else { *q2 };
+        let n2 = Quaternion::conjugate(q1);
+        let n3 = Quaternion::log(&Quaternion::mul(&n2, &n1));
+        let n4 = Quaternion::log(&Quaternion::mul(&n2, &n0));
+        let n5 = Quaternion::new(
+            -(n3.x + n4.x) * 0.25,
+            -(n3.y + n4.y) * 0.25,
+            -(n3.z + n4.z) * 0.25,
+            0.0,
+        );
+        Quaternion::normalize(&Quaternion::mul(q1, &Quaternion::exp(&n5)))
+    }
+    /// Returns the logarithm of the unit quaternion q0, a pure quaternion.
+    #[inline(always)]
+    fn log(q0: &Quaternion) -> Quaternion {
+        let n0 = f32::sqrt((q0.x * q0.x) + (q0.y * q0.y) + (q0.z * q0.z));
+        if n0 < 1e-6 {
+            return Quaternion::new(0.0, 0.0, 0.0, 0.0);
+        }
+        let n1 = f32::atan2(n0, q0.w) / n0;
+        Quaternion::new(q0.x * n1, q0.y * n1, q0.z * n1, 0.0)
+    }
+    /// Returns the exponential of the pure quaternion q0, a unit quaternion.
+    #[inline(always)]
+    fn exp(q0: &Quaternion) -> Quaternion {
+        let n0 = f32::sqrt((q0.x * q0.x) + (q0.y * q0.y) + (q0.z * q0.z));
+        if n0 < 1e-6 {
+            return Quaternion::new(q0.x, q0.y, q0.z, 1.0);
+        }
+        let n1 = f32::sin(n0) / n0;
+        Quaternion::new(q0.x * n1, q0.y * n1, q0.z * n1, f32::cos(n0))
+    }
     #[inline(always)]
     pub fn lerp(q0: &Quaternion, q1: &Quaternion, amount: f32) -> Quaternion {
         let mut q2 = Quaternion::new(0.0, 0.0, 0.0, 0.0);
@@ -254,6 +332,10 @@ impl Quaternion {
     pub fn negate(q0: &Quaternion) -> Quaternion {
         Quaternion::new(-q0.x, -q0.y, -q0.z, -q0.w)
     }
+    #[inline(always)]
+    pub fn rotate_vector(q0: &Quaternion, v0: &Vec3) -> Vec3 {
+        Vec3::transform_quaternion(v0, q0)
+    }
 }
 // ------------------------------------------------------------
 //
@@ -293,6 +375,12 @@ impl Mul<&Quaternion> for &Quaternion {
         Quaternion::mul(self, rhs)
     }
 }
+impl Mul<&Vec3> for &Quaternion {
+    type Output = Vec3;
+    fn mul(self, rhs: &Vec3) -> Vec3 {
+        Vec3::transform_quaternion(rhs, self)
+    }
+}
 impl Div<&Quaternion> for &Quaternion {
     type Output = Quaternion;
     fn div(self, rhs: &Quaternion) -> Quaternion {
@@ -324,6 +412,12 @@ impl Mul<Quaternion> for Quaternion {
         Quaternion::mul(&self, &rhs)
     }
 }
+impl Mul<Vec3> for Quaternion {
+    type Output = Vec3;
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        Vec3::transform_quaternion(&rhs, &self)
+    }
+}
 impl Div<Quaternion> for Quaternion {
     type Output = Quaternion;
     fn div(self, rhs: Quaternion) -> Quaternion {
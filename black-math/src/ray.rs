@@ -50,7 +50,11 @@ impl Ray {
         Vec3::equals(&r0.position, &r1.position) && Vec3::equals(&r0.direction, &r1.direction)
     }
     #[inline(always)]
-    pub fn intersect_plane(ray: Ray, plane: Plane) -> Option<f32> {
+    pub fn at(ray: &Ray, distance: f32) -> Vec3 {
+        Vec3::add(&ray.position, &Vec3::scale(&ray.direction, distance))
+    }
+    #[inline(always)]
+    pub fn intersect_plane(ray: &Ray, plane: &Plane) -> Option<f32> {
         let n0 =
             (plane.a * ray.direction.x) + (plane.b * ray.direction.y) + (plane.c * ray.direction.z);
         if f32::abs(n0) < 1E-05 {
@@ -99,36 +103,36 @@ impl Ray {
     }
 
     #[inline(always)]
-    pub fn intersect_box(r0: &Ray, b0: &BoundingBox) -> Option<f32> {
+    pub fn intersect_box(ray: &Ray, bounds: &BoundingBox) -> Option<f32> {
         let mut max_value = std::f32::MAX;
         let mut result = 0.0;
-        if f32::abs(r0.direction.x) < 1E-06 {
-            if (r0.position.x < b0.min.x) || (r0.position.x > b0.max.x) {
+        if f32::abs(ray.direction.x) < 1E-06 {
+            if (ray.position.x < bounds.min.x) || (ray.position.x > bounds.max.x) {
                 return None;
             }
         } else {
-            let n0 = 1.0 / r0.direction.x;
-            let mut n1 = (b0.min.x - r0.position.x) * n0;
-            let mut n2 = (b0.max.x - r0.position.x) * n0;
+            let n0 = 1.0 / ray.direction.x;
+            let mut n1 = (bounds.min.x - ray.position.x) * n0;
+            let mut n2 = (bounds.max.x - ray.position.x) * n0;
             if n1 > n2 {
                 let n3 = n1;
                 n1 = n2;
                 n2 = n3;
             }
             result = if n1 > result { n1 } else { result };
-            max_value = if n2 < result { n2 } else { result };
+            max_value = if n2 < max_value { n2 } else { max_value };
             if result > max_value {
                 return None;
             }
         }
-        if f32::abs(r0.direction.y) < 1E-06 {
-            if (r0.position.y < b0.min.y) || (r0.position.y > b0.max.y) {
+        if f32::abs(ray.direction.y) < 1E-06 {
+            if (ray.position.y < bounds.min.y) || (ray.position.y > bounds.max.y) {
                 return None;
             }
         } else {
-            let n0 = 1.0 / r0.direction.y;
-            let mut n1 = (b0.min.y - r0.position.y) * n0;
-            let mut n2 = (b0.max.y - r0.position.y) * n0;
+            let n0 = 1.0 / ray.direction.y;
+            let mut n1 = (bounds.min.y - ray.position.y) * n0;
+            let mut n2 = (bounds.max.y - ray.position.y) * n0;
             if n1 > n2 {
                 let n3 = n1;
                 n1 = n2;
@@ -140,14 +144,14 @@ impl Ray {
                 return None;
             }
         }
-        if f32::abs(r0.direction.z) < 1E-06 {
-            if (r0.position.z < b0.min.z) || (r0.position.z > b0.max.z) {
+        if f32::abs(ray.direction.z) < 1E-06 {
+            if (ray.position.z < bounds.min.z) || (ray.position.z > bounds.max.z) {
                 return None;
             }
         } else {
-            let n0 = 1.0 / r0.direction.z;
-            let mut n1 = (b0.min.z - r0.position.z) * n0;
-            let mut n2 = (b0.max.z - r0.position.z) * n0;
+            let n0 = 1.0 / ray.direction.z;
+            let mut n1 = (bounds.min.z - ray.position.z) * n0;
+            let mut n2 = (bounds.max.z - ray.position.z) * n0;
             if n1 > n2 {
                 let n3 = n1;
                 n1 = n2;
@@ -163,16 +167,16 @@ impl Ray {
     }
 
     #[inline(always)]
-    pub fn intersect_sphere(r0: &Ray, s0: &Sphere) -> Option<f32> {
-        let n0 = s0.position.x - r0.position.x;
-        let n1 = s0.position.y - r0.position.y;
-        let n2 = s0.position.z - r0.position.z;
+    pub fn intersect_sphere(ray: &Ray, sphere: &Sphere) -> Option<f32> {
+        let n0 = sphere.position.x - ray.position.x;
+        let n1 = sphere.position.y - ray.position.y;
+        let n2 = sphere.position.z - ray.position.z;
         let n3 = (n0 * n0) + (n1 * n1) + (n2 * n2);
-        let n4 = s0.radius * s0.radius;
+        let n4 = sphere.radius * sphere.radius;
         if n3 <= n4 {
             return Some(0.0);
         }
-        let n5 = (n0 * r0.direction.x) + (n1 * r0.direction.y) + (n2 * r0.direction.z);
+        let n5 = (n0 * ray.direction.x) + (n1 * ray.direction.y) + (n2 * ray.direction.z);
         if n5 < 0.0 {
             return None;
         }
@@ -183,6 +187,29 @@ impl Ray {
         let n7 = f32::sqrt(n4 - n6);
         Some(n5 - n7)
     }
+
+    /// Returns the near and far distances at which the ray enters and exits
+    /// the sphere. The near distance is negative if the ray starts inside
+    /// the sphere. Returns None if the ray misses or the sphere is behind it.
+    #[inline(always)]
+    pub fn intersect_sphere_interval(ray: &Ray, sphere: &Sphere) -> Option<(f32, f32)> {
+        let v0 = Vec3::sub(&ray.position, &sphere.position);
+        let n0 = Vec3::dot(&ray.direction, &ray.direction);
+        let n1 = Vec3::dot(&v0, &ray.direction);
+        let n2 = Vec3::dot(&v0, &v0) - (sphere.radius * sphere.radius);
+        let n3 = (n1 * n1) - (n0 * n2);
+        if n0 == 0.0 || n3 < 0.0 {
+            return None;
+        }
+        let n4 = f32::sqrt(n3);
+        let near = (-n1 - n4) / n0;
+        let far  = (-n1 + n4) / n0;
+        if far < 0.0 {
+            None
+        } else {
+            Some((near, far))
+        }
+    }
 }
 
 // ------------------------------------------------------------
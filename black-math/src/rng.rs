@@ -0,0 +1,98 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::{Vec2, Vec3};
+
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+const INCREMENT:  u64 = 109; // stream 54
+
+/// A small, deterministic pseudo random number generator (PCG32). Suitable
+/// for sampling and dithering, not for cryptography.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+impl Rng {
+    /// Creates a new generator from the given seed.
+    pub fn new(seed: u64) -> Rng {
+        let mut rng = Rng { state: 0 };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    /// Returns the next u32 in the sequence.
+    #[inline(always)]
+    pub fn next_u32(&mut self) -> u32 {
+        let n0 = self.state;
+        self.state = n0.wrapping_mul(MULTIPLIER).wrapping_add(INCREMENT);
+        let xorshifted = (((n0 >> 18) ^ n0) >> 27) as u32;
+        let rotation = (n0 >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    /// Returns the next f32 in the range [0, 1).
+    #[inline(always)]
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / 16_777_216.0)
+    }
+
+    /// Returns the next f32 in the range [min, max).
+    #[inline(always)]
+    pub fn next_range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Returns a random point inside the unit disk.
+    pub fn next_in_unit_disk(&mut self) -> Vec2 {
+        loop {
+            let n0 = Vec2::new(
+                self.next_range(-1.0, 1.0),
+                self.next_range(-1.0, 1.0)
+            );
+            if Vec2::dot(&n0, &n0) <= 1.0 {
+                return n0;
+            }
+        }
+    }
+
+    /// Returns a random point inside the unit sphere.
+    pub fn next_in_unit_sphere(&mut self) -> Vec3 {
+        loop {
+            let n0 = Vec3::new(
+                self.next_range(-1.0, 1.0),
+                self.next_range(-1.0, 1.0),
+                self.next_range(-1.0, 1.0)
+            );
+            if Vec3::dot(&n0, &n0) <= 1.0 {
+                return n0;
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+//! Linear blend skinning helpers for use inside VertexProgram::main. Each
+//! vertex is influenced by up to four bones from a matrix palette, with
+//! weights expected to sum to 1.0. Bones with a weight of 0.0 are skipped,
+//! so unused influences may carry any index.
+
+use super::{Mat4, Vec3, Vec4};
+
+/// Returns the bind pose position transformed by the weighted blend of the
+/// given bone matrices.
+pub fn skin_position(bind_pos: &Vec4, bones: &[Mat4], indices: [usize; 4], weights: [f32; 4]) -> Vec4 {
+    let mut n0 = Vec4::zero();
+    for (index, weight) in indices.iter().zip(weights.iter()) {
+        if *weight != 0.0 {
+            n0 = n0 + Vec4::transform(bind_pos, &bones[*index]) * *weight;
+        }
+    }
+    n0
+}
+
+/// Returns the bind pose normal transformed by the inverse transpose of the
+/// blended bone matrix, normalized.
+pub fn skin_normal(bind_normal: &Vec3, bones: &[Mat4], indices: [usize; 4], weights: [f32; 4]) -> Vec3 {
+    let m0 = Mat4::transpose(&Mat4::invert(&blend(bones, indices, weights)));
+    Vec3::normalize(&Vec3::transform_normal(bind_normal, &m0))
+}
+
+/// Returns the weighted sum of the given bone matrices.
+fn blend(bones: &[Mat4], indices: [usize; 4], weights: [f32; 4]) -> Mat4 {
+    let mut m0 = Mat4::zero();
+    for (index, weight) in indices.iter().zip(weights.iter()) {
+        if *weight != 0.0 {
+            m0 = Mat4::add(&m0, &Mat4::lerp(&Mat4::zero(), &bones[*index], *weight));
+        }
+    }
+    m0
+}
@@ -26,9 +26,12 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
+use super::Vec2;
 use super::Vec3;
 use super::Plane;
+use super::Winding;
 
+#[derive(Debug, Clone, Copy)]
 pub struct Triangle {
     pub v0: Vec3,
     pub v1: Vec3,
@@ -46,4 +49,29 @@ impl Triangle {
     pub fn plane(t0: &Triangle) -> Plane {
         Plane::from_points(&t0.v0, &t0.v1, &t0.v2)
     }
+    /// Returns the unit geometric normal of the triangle. The normal faces
+    /// toward a viewer who sees v0, v1, v2 wound counter clockwise. Used for
+    /// flat shading where a triangle shares one normal.
+    pub fn face_normal(t0: &Triangle) -> Vec3 {
+        let n0 = Vec3::sub(&t0.v1, &t0.v0);
+        let n1 = Vec3::sub(&t0.v2, &t0.v0);
+        Vec3::normalize(&Vec3::cross(&n0, &n1))
+    }
+    /// Returns the signed area of the 2D triangle a, b, c. The area is
+    /// positive when the points wind counter clockwise in a y up space
+    /// such as normalized device coordinates.
+    pub fn signed_area_2d(a: &Vec2, b: &Vec2, c: &Vec2) -> f32 {
+        Vec2::cross(&Vec2::sub(b, a), &Vec2::sub(c, a)) * 0.5
+    }
+    /// Returns true if the 2D triangle a, b, c is front facing for the
+    /// given winding. Points are expected in a y up space such as
+    /// normalized device coordinates. This matches the culling performed
+    /// by the rasterizer.
+    pub fn is_front_facing(a: &Vec2, b: &Vec2, c: &Vec2, winding: Winding) -> bool {
+        let n0 = Triangle::signed_area_2d(a, b, c);
+        match winding {
+            Winding::CounterClockwise => n0 > 0.0,
+            Winding::Clockwise        => n0 < 0.0,
+        }
+    }
 }
\ No newline at end of file
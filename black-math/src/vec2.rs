@@ -28,10 +28,14 @@ THE SOFTWARE.
 
 use super::Mat4;
 use std::fmt::{Display, Error, Formatter};
-use std::ops::{Add, Div, Mul, Sub};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, MulAssign, Sub};
 use std::ops::{Index, IndexMut};
 
+/// The layout is C compatible: 2 contiguous f32 values ordered x, y
+/// with no padding, so a Vec2 may be viewed as [f32; 2].
 #[derive(Debug, Clone, Copy)]
+#[repr(C)]
 pub struct Vec2 {
     pub x: f32,
     pub y: f32,
@@ -41,6 +45,18 @@ impl Vec2 {
     pub fn new(x: f32, y: f32) -> Vec2 {
         Vec2 { x, y }
     }
+    /// Returns the components of this Vec2 as a slice of 2 f32 values.
+    #[inline(always)]
+    pub fn as_f32_slice(&self) -> &[f32] {
+        // Safety: Vec2 is repr(C) with 2 f32 fields and no padding.
+        unsafe { std::slice::from_raw_parts(self as *const Vec2 as *const f32, 2) }
+    }
+    /// Returns a slice of Vec2 values as a flat slice of f32, 2 per Vec2.
+    #[inline(always)]
+    pub fn slice_as_f32(v0: &[Vec2]) -> &[f32] {
+        // Safety: Vec2 is repr(C) with 2 f32 fields and no padding.
+        unsafe { std::slice::from_raw_parts(v0.as_ptr() as *const f32, v0.len() * 2) }
+    }
     #[inline(always)]
     pub fn zero() -> Vec2 {
         Vec2::new(0.0, 0.0)
@@ -108,10 +124,48 @@ impl Vec2 {
         (v0.x * v1.x) + (v0.y * v1.y)
     }
     #[inline(always)]
+    pub fn cross(v0: &Vec2, v1: &Vec2) -> f32 {
+        (v0.x * v1.y) - (v0.y * v1.x)
+    }
+    #[inline(always)]
+    pub fn perp(v0: &Vec2) -> Vec2 {
+        Vec2::new(-v0.y, v0.x)
+    }
+    #[inline(always)]
+    pub fn rotate(v0: &Vec2, radians: f32) -> Vec2 {
+        let n0 = f32::cos(radians);
+        let n1 = f32::sin(radians);
+        Vec2::new((v0.x * n0) - (v0.y * n1), (v0.x * n1) + (v0.y * n0))
+    }
+    #[inline(always)]
+    pub fn angle(v0: &Vec2) -> f32 {
+        f32::atan2(v0.y, v0.x)
+    }
+    /// Returns v0 scaled to unit length. v0 must have a non-zero, finite
+    /// length, otherwise the result is infinite or NaN. Use try_normalize()
+    /// or normalize_or_zero() for inputs that may be degenerate.
+    #[inline(always)]
     pub fn normalize(v0: &Vec2) -> Vec2 {
         let len = 1.0 / f32::sqrt((v0.x * v0.x) + (v0.y * v0.y));
         Vec2::new(v0.x * len, v0.y * len)
     }
+    /// Returns v0 scaled to unit length, or None if the length of v0 is not
+    /// greater than epsilon or is not finite.
+    #[inline(always)]
+    pub fn try_normalize(v0: &Vec2, epsilon: f32) -> Option<Vec2> {
+        let len = Vec2::length(v0);
+        if len.is_finite() && len > epsilon {
+            let len = 1.0 / len;
+            Some(Vec2::new(v0.x * len, v0.y * len))
+        } else {
+            None
+        }
+    }
+    /// Returns v0 scaled to unit length, or zero if v0 cannot be normalized.
+    #[inline(always)]
+    pub fn normalize_or_zero(v0: &Vec2) -> Vec2 {
+        Vec2::try_normalize(v0, f32::EPSILON).unwrap_or_else(Vec2::zero)
+    }
     #[inline(always)]
     pub fn abs(v0: &Vec2) -> Vec2 {
         Vec2::new(
@@ -220,6 +274,32 @@ impl Vec2 {
         )
     }
     #[inline(always)]
+    pub fn bezier(v0: &Vec2, v1: &Vec2, v2: &Vec2, v3: &Vec2, amount: f32) -> Vec2 {
+        let n0 = 1.0 - amount;
+        let n1 = n0 * n0;
+        let n2 = n1 * n0;
+        let n3 = 3.0 * n1 * amount;
+        let n4 = 3.0 * n0 * amount * amount;
+        let n5 = amount * amount * amount;
+        Vec2::new(
+            (((v0.x * n2) + (v1.x * n3)) + (v2.x * n4)) + (v3.x * n5),
+            (((v0.y * n2) + (v1.y * n3)) + (v2.y * n4)) + (v3.y * n5),
+        )
+    }
+    /// Returns the derivative of the cubic bezier curve at amount. The
+    /// tangent at 0.0 points from v0 toward v1.
+    #[inline(always)]
+    pub fn bezier_tangent(v0: &Vec2, v1: &Vec2, v2: &Vec2, v3: &Vec2, amount: f32) -> Vec2 {
+        let n0 = 1.0 - amount;
+        let n2 = 3.0 * n0 * n0;
+        let n3 = 6.0 * n0 * amount;
+        let n4 = 3.0 * amount * amount;
+        Vec2::new(
+            (((v1.x - v0.x) * n2) + ((v2.x - v1.x) * n3)) + ((v3.x - v2.x) * n4),
+            (((v1.y - v0.y) * n2) + ((v2.y - v1.y) * n3)) + ((v3.y - v2.y) * n4),
+        )
+    }
+    #[inline(always)]
     pub fn transform(v0: &Vec2, m0: &Mat4) -> Vec2 {
         Vec2::new(
             ((v0.x * m0.m11) + (v0.y * m0.m21)) + m0.m41,
@@ -235,6 +315,39 @@ impl Vec2 {
     }
 }
 
+// ------------------------------------------------------------
+//
+// Instance functions.
+//
+// ------------------------------------------------------------
+
+impl Vec2 {
+    pub fn with_x(&self, x: f32) -> Vec2 {
+        Vec2::new(x, self.y)
+    }
+    pub fn with_y(&self, y: f32) -> Vec2 {
+        Vec2::new(self.x, y)
+    }
+    pub fn min_component(&self) -> f32 {
+        f32::min(self.x, self.y)
+    }
+    pub fn max_component(&self) -> f32 {
+        f32::max(self.x, self.y)
+    }
+    pub fn sum(&self) -> f32 {
+        self.x + self.y
+    }
+    /// Returns the index of the largest component. Ties resolve to the
+    /// lowest index.
+    pub fn max_axis(&self) -> usize {
+        let mut n0 = 0;
+        if self.y > self[n0] {
+            n0 = 1;
+        }
+        n0
+    }
+}
+
 // ------------------------------------------------------------
 //
 // Equality Operator
@@ -278,6 +391,12 @@ impl Mul<f32> for &Vec2 {
         Vec2::scale(self, rhs)
     }
 }
+impl Mul<&Vec2> for f32 {
+    type Output = Vec2;
+    fn mul(self, rhs: &Vec2) -> Vec2 {
+        Vec2::scale(rhs, self)
+    }
+}
 impl Div<&Vec2> for &Vec2 {
     type Output = Vec2;
     fn div(self, rhs: &Vec2) -> Vec2 {
@@ -315,6 +434,12 @@ impl Mul<f32> for Vec2 {
         Vec2::scale(&self, rhs)
     }
 }
+impl Mul<Vec2> for f32 {
+    type Output = Vec2;
+    fn mul(self, rhs: Vec2) -> Vec2 {
+        Vec2::scale(&rhs, self)
+    }
+}
 impl Div<Vec2> for Vec2 {
     type Output = Vec2;
     fn div(self, rhs: Vec2) -> Vec2 {
@@ -322,6 +447,45 @@ impl Div<Vec2> for Vec2 {
     }
 }
 
+// ------------------------------------------------------------
+//
+// Operator Overloads: T *= f32
+//
+// ------------------------------------------------------------
+
+impl MulAssign<f32> for Vec2 {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = Vec2::scale(self, rhs)
+    }
+}
+
+// ------------------------------------------------------------
+//
+// Iterators: Sum and Product
+//
+// ------------------------------------------------------------
+
+impl Sum<Vec2> for Vec2 {
+    fn sum<I: Iterator<Item = Vec2>>(iter: I) -> Vec2 {
+        iter.fold(Vec2::zero(), |acc, n| Vec2::add(&acc, &n))
+    }
+}
+impl<'a> Sum<&'a Vec2> for Vec2 {
+    fn sum<I: Iterator<Item = &'a Vec2>>(iter: I) -> Vec2 {
+        iter.fold(Vec2::zero(), |acc, n| Vec2::add(&acc, n))
+    }
+}
+impl Product<Vec2> for Vec2 {
+    fn product<I: Iterator<Item = Vec2>>(iter: I) -> Vec2 {
+        iter.fold(Vec2::one(), |acc, n| Vec2::mul(&acc, &n))
+    }
+}
+impl<'a> Product<&'a Vec2> for Vec2 {
+    fn product<I: Iterator<Item = &'a Vec2>>(iter: I) -> Vec2 {
+        iter.fold(Vec2::one(), |acc, n| Vec2::mul(&acc, n))
+    }
+}
+
 // ------------------------------------------------------------
 //
 // Indexer
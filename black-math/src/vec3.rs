@@ -28,15 +28,20 @@ THE SOFTWARE.
 
 
 use std::fmt::{Display, Error, Formatter};
-use std::ops::{Add, Div, Mul, Sub};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, MulAssign, Sub};
 use std::ops::{Index, IndexMut};
 
+use super::Vec2;
 use super::Vec4;
 use super::Mat4;
 use super::Quaternion;
 
 
+/// The layout is C compatible: 3 contiguous f32 values ordered x, y, z
+/// with no padding, so a Vec3 may be viewed as [f32; 3].
 #[derive(Debug, Clone, Copy)]
+#[repr(C)]
 pub struct Vec3 {
     pub x: f32,
     pub y: f32,
@@ -47,6 +52,18 @@ impl Vec3 {
     pub fn new(x: f32, y: f32, z: f32) -> Vec3 {
         Vec3 { x, y, z }
     }
+    /// Returns the components of this Vec3 as a slice of 3 f32 values.
+    #[inline(always)]
+    pub fn as_f32_slice(&self) -> &[f32] {
+        // Safety: Vec3 is repr(C) with 3 f32 fields and no padding.
+        unsafe { std::slice::from_raw_parts(self as *const Vec3 as *const f32, 3) }
+    }
+    /// Returns a slice of Vec3 values as a flat slice of f32, 3 per Vec3.
+    #[inline(always)]
+    pub fn slice_as_f32(v0: &[Vec3]) -> &[f32] {
+        // Safety: Vec3 is repr(C) with 3 f32 fields and no padding.
+        unsafe { std::slice::from_raw_parts(v0.as_ptr() as *const f32, v0.len() * 3) }
+    }
     #[inline(always)]
     pub fn all(x: f32) -> Vec3 {
         Vec3 { x, y: x, z: x }
@@ -121,11 +138,31 @@ impl Vec3 {
     pub fn dot(v0: &Vec3, v1: &Vec3) -> f32 {
         (v0.x * v1.x) + (v0.y * v1.y) + (v0.z * v1.z)
     }
+    /// Returns v0 scaled to unit length. v0 must have a non-zero, finite
+    /// length, otherwise the result is infinite or NaN. Use try_normalize()
+    /// or normalize_or_zero() for inputs that may be degenerate.
     #[inline(always)]
     pub fn normalize(v0: &Vec3) -> Vec3 {
         let len = 1.0 / f32::sqrt((v0.x * v0.x) + (v0.y * v0.y) + (v0.z * v0.z));
         Vec3::new(v0.x * len, v0.y * len, v0.z * len)
     }
+    /// Returns v0 scaled to unit length, or None if the length of v0 is not
+    /// greater than epsilon or is not finite.
+    #[inline(always)]
+    pub fn try_normalize(v0: &Vec3, epsilon: f32) -> Option<Vec3> {
+        let len = Vec3::length(v0);
+        if len.is_finite() && len > epsilon {
+            let len = 1.0 / len;
+            Some(Vec3::new(v0.x * len, v0.y * len, v0.z * len))
+        } else {
+            None
+        }
+    }
+    /// Returns v0 scaled to unit length, or zero if v0 cannot be normalized.
+    #[inline(always)]
+    pub fn normalize_or_zero(v0: &Vec3) -> Vec3 {
+        Vec3::try_normalize(v0, f32::EPSILON).unwrap_or_else(Vec3::zero)
+    }
     #[inline(always)]
     pub fn cross(v0: &Vec3, v1: &Vec3) -> Vec3 {
         Vec3::new(
@@ -134,6 +171,19 @@ impl Vec3 {
             (v0.x * v1.y) - (v0.y * v1.x)
         )
     }
+    /// Returns two unit vectors perpendicular to the unit vector n0 and to
+    /// each other, forming a right handed basis (t, b, n0). Uses the
+    /// branchless method of Duff et al. which is stable for all n0.
+    #[inline(always)]
+    pub fn orthonormal_basis(n0: &Vec3) -> (Vec3, Vec3) {
+        let sign = f32::copysign(1.0, n0.z);
+        let n1 = -1.0 / (sign + n0.z);
+        let n2 = n0.x * n0.y * n1;
+        (
+            Vec3::new(1.0 + sign * n0.x * n0.x * n1, sign * n2, -sign * n0.x),
+            Vec3::new(n2, sign + n0.y * n0.y * n1, -n0.y),
+        )
+    }
     #[inline(always)]
     pub fn reflect(v0: &Vec3, n0: &Vec3) -> Vec3 {
         let dot = Vec3::dot(v0, n0);
@@ -143,6 +193,23 @@ impl Vec3 {
             v0.z - ((2.0 * dot) * n0.z),
         )
     }
+    /// Returns the component of v0 parallel to v1, which need not be unit
+    /// length. Returns zero if v1 has zero length.
+    #[inline(always)]
+    pub fn project_onto(v0: &Vec3, v1: &Vec3) -> Vec3 {
+        let n0 = Vec3::length_sq(v1);
+        if n0 == 0.0 {
+            return Vec3::zero();
+        }
+        Vec3::scale(v1, Vec3::dot(v0, v1) / n0)
+    }
+    /// Returns the component of v0 perpendicular to v1, such that
+    /// project_onto(v0, v1) + reject_from(v0, v1) is v0. Returns v0 if v1
+    /// has zero length.
+    #[inline(always)]
+    pub fn reject_from(v0: &Vec3, v1: &Vec3) -> Vec3 {
+        Vec3::sub(v0, &Vec3::project_onto(v0, v1))
+    }
     #[inline(always)]
     pub fn abs(v0: &Vec3) -> Vec3 {
         Vec3::new(
@@ -188,6 +255,29 @@ impl Vec3 {
         z = if z < min.z { min.z } else { z };
         Vec3::new(x, y, z)
     }
+    /// Tone maps a linear HDR color into the range 0.0 to 1.0 with the
+    /// Reinhard operator c / (1 + c), per component. Negative components
+    /// map to 0.0 and large values approach but never reach 1.0.
+    #[inline(always)]
+    pub fn reinhard(v0: &Vec3) -> Vec3 {
+        let n0 = f32::max(v0.x, 0.0);
+        let n1 = f32::max(v0.y, 0.0);
+        let n2 = f32::max(v0.z, 0.0);
+        Vec3::new(n0 / (1.0 + n0), n1 / (1.0 + n1), n2 / (1.0 + n2))
+    }
+    /// Tone maps a linear HDR color into the range 0.0 to 1.0 with Krzysztof
+    /// Narkowicz's fit of the ACES filmic curve, per component. Contrast is
+    /// higher than reinhard and components saturate at 1.0 above roughly
+    /// 7.2. Negative components map to 0.0.
+    #[inline(always)]
+    pub fn aces_approx(v0: &Vec3) -> Vec3 {
+        let curve = |n0: f32| {
+            let n0 = f32::max(n0, 0.0);
+            let n1 = (n0 * (2.51 * n0 + 0.03)) / (n0 * (2.43 * n0 + 0.59) + 0.14);
+            n1.clamp(0.0, 1.0)
+        };
+        Vec3::new(curve(v0.x), curve(v0.y), curve(v0.z))
+    }
     #[inline(always)]
     pub fn lerp(v0: &Vec3, v1: &Vec3, amount: f32) -> Vec3 {
         Vec3::new(
@@ -196,6 +286,31 @@ impl Vec3 {
             v0.z + ((v1.z - v0.z) * amount),
         )
     }
+    /// Spherically interpolates between the unit vectors v0 and v1. Nearly
+    /// parallel inputs fall back to lerp. Nearly opposite inputs have no
+    /// unique plane of rotation, so v0 is rotated through an arbitrary axis
+    /// perpendicular to it, reaching -v0 at an amount of 1.0.
+    #[inline(always)]
+    pub fn slerp(v0: &Vec3, v1: &Vec3, amount: f32) -> Vec3 {
+        let n0 = Vec3::dot(v0, v1).clamp(-1.0, 1.0);
+        if n0 > 0.9995 {
+            return Vec3::lerp(v0, v1, amount);
+        }
+        if n0 < -0.9995 {
+            let (t0, _) = Vec3::orthonormal_basis(&Vec3::normalize(v0));
+            let n1 = amount * std::f32::consts::PI;
+            return Vec3::add(&Vec3::scale(v0, f32::cos(n1)), &Vec3::scale(&t0, f32::sin(n1) * Vec3::length(v0)));
+        }
+        let n1 = f32::acos(n0);
+        let n2 = 1.0 / f32::sin(n1);
+        let n3 = f32::sin((1.0 - amount) * n1) * n2;
+        let n4 = f32::sin(amount * n1) * n2;
+        Vec3::new(
+            (n3 * v0.x) + (n4 * v1.x),
+            (n3 * v0.y) + (n4 * v1.y),
+            (n3 * v0.z) + (n4 * v1.z),
+        )
+    }
     #[inline(always)]
     pub fn barycentric(
         v0: &Vec3,
@@ -265,13 +380,60 @@ impl Vec3 {
         )
     }
     #[inline(always)]
+    pub fn bezier(v0: &Vec3, v1: &Vec3, v2: &Vec3, v3: &Vec3, amount: f32) -> Vec3 {
+        let n0 = 1.0 - amount;
+        let n1 = n0 * n0;
+        let n2 = n1 * n0;
+        let n3 = 3.0 * n1 * amount;
+        let n4 = 3.0 * n0 * amount * amount;
+        let n5 = amount * amount * amount;
+        Vec3::new(
+            (((v0.x * n2) + (v1.x * n3)) + (v2.x * n4)) + (v3.x * n5),
+            (((v0.y * n2) + (v1.y * n3)) + (v2.y * n4)) + (v3.y * n5),
+            (((v0.z * n2) + (v1.z * n3)) + (v2.z * n4)) + (v3.z * n5),
+        )
+    }
+    /// Returns the derivative of the cubic bezier curve at amount. The
+    /// tangent at 0.0 points from v0 toward v1.
+    #[inline(always)]
+    pub fn bezier_tangent(v0: &Vec3, v1: &Vec3, v2: &Vec3, v3: &Vec3, amount: f32) -> Vec3 {
+        let n0 = 1.0 - amount;
+        let n2 = 3.0 * n0 * n0;
+        let n3 = 6.0 * n0 * amount;
+        let n4 = 3.0 * amount * amount;
+        Vec3::new(
+            (((v1.x - v0.x) * n2) + ((v2.x - v1.x) * n3)) + ((v3.x - v2.x) * n4),
+            (((v1.y - v0.y) * n2) + ((v2.y - v1.y) * n3)) + ((v3.y - v2.y) * n4),
+            (((v1.z - v0.z) * n2) + ((v2.z - v1.z) * n3)) + ((v3.z - v2.z) * n4),
+        )
+    }
+    /// Rotates point about the axis passing through pivot by the given
+    /// radians. Rotation is counter clockwise when looking down the axis
+    /// toward the pivot. The axis need not be unit length.
+    #[inline(always)]
+    pub fn rotate_around(point: &Vec3, pivot: &Vec3, axis: &Vec3, radians: f32) -> Vec3 {
+        let k  = Vec3::normalize(axis);
+        let v  = Vec3::sub(point, pivot);
+        let n0 = f32::cos(radians);
+        let n1 = f32::sin(radians);
+        let n2 = Vec3::dot(&k, &v) * (1.0 - n0);
+        let n3 = Vec3::cross(&k, &v);
+        Vec3::new(
+            pivot.x + (v.x * n0) + (n3.x * n1) + (k.x * n2),
+            pivot.y + (v.y * n0) + (n3.y * n1) + (k.y * n2),
+            pivot.z + (v.z * n0) + (n3.z * n1) + (k.z * n2),
+        )
+    }
+    /// Transforms v0 as a point (w = 1), applying the translation of m0.
+    #[inline(always)]
     pub fn transform(v0: &Vec3, m0: &Mat4) -> Vec3 {
         Vec3::new(
-            ((v0.x * m0.m11) + (v0.y * m0.m21)) + (v0.z * m0.m31),
-            ((v0.x * m0.m12) + (v0.y * m0.m22)) + (v0.z * m0.m32),
-            ((v0.x * m0.m13) + (v0.y * m0.m23)) + (v0.z * m0.m33),
+            (((v0.x * m0.m11) + (v0.y * m0.m21)) + (v0.z * m0.m31)) + m0.m41,
+            (((v0.x * m0.m12) + (v0.y * m0.m22)) + (v0.z * m0.m32)) + m0.m42,
+            (((v0.x * m0.m13) + (v0.y * m0.m23)) + (v0.z * m0.m33)) + m0.m43,
         )
     }
+    /// Transforms n0 as a direction (w = 0), ignoring the translation of m0.
     #[inline(always)]
     pub fn transform_normal(n0: &Vec3, m0: &Mat4) -> Vec3 {
         Vec3::new(
@@ -295,9 +457,9 @@ impl Vec3 {
         let n10 = q0.y * n2;
         let n11 = q0.z * n2;
         Vec3::new(
-            (v0.x * ((1.0 - n9) - n11)) + (v0.y * (n7 - n5)),
-            (v0.x * (n7 + n5)) + (v0.y * ((1.0 - n6) - n11)),
-            (v0.x * (n8 - n4)) + (v0.y * (n10 + n3)),
+            ((v0.x * ((1.0 - n9) - n11)) + (v0.y * (n7 - n5))) + (v0.z * (n8 + n4)),
+            ((v0.x * (n7 + n5)) + (v0.y * ((1.0 - n6) - n11))) + (v0.z * (n10 - n3)),
+            ((v0.x * (n8 - n4)) + (v0.y * (n10 + n3))) + (v0.z * ((1.0 - n6) - n9)),
         )
     }
 }
@@ -312,6 +474,45 @@ impl Vec3 {
     pub fn xyzw(&self) -> Vec4 {
         Vec4::new(self.x, self.y, self.z, 1.0)
     }
+    pub fn xy(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+    pub fn xyz0(&self) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, 0.0)
+    }
+    pub fn xyz1(&self) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, 1.0)
+    }
+    pub fn with_x(&self, x: f32) -> Vec3 {
+        Vec3::new(x, self.y, self.z)
+    }
+    pub fn with_y(&self, y: f32) -> Vec3 {
+        Vec3::new(self.x, y, self.z)
+    }
+    pub fn with_z(&self, z: f32) -> Vec3 {
+        Vec3::new(self.x, self.y, z)
+    }
+    pub fn min_component(&self) -> f32 {
+        f32::min(f32::min(self.x, self.y), self.z)
+    }
+    pub fn max_component(&self) -> f32 {
+        f32::max(f32::max(self.x, self.y), self.z)
+    }
+    pub fn sum(&self) -> f32 {
+        self.x + self.y + self.z
+    }
+    /// Returns the index of the largest component. Ties resolve to the
+    /// lowest index.
+    pub fn max_axis(&self) -> usize {
+        let mut n0 = 0;
+        if self.y > self[n0] {
+            n0 = 1;
+        }
+        if self.z > self[n0] {
+            n0 = 2;
+        }
+        n0
+    }
 }
 
 // ------------------------------------------------------------
@@ -357,6 +558,12 @@ impl Mul<f32> for &Vec3 {
         Vec3::scale(self, rhs)
     }
 }
+impl Mul<&Vec3> for f32 {
+    type Output = Vec3;
+    fn mul(self, rhs: &Vec3) -> Vec3 {
+        Vec3::scale(rhs, self)
+    }
+}
 impl Div<&Vec3> for &Vec3 {
     type Output = Vec3;
     fn div(self, rhs: &Vec3) -> Vec3 {
@@ -394,6 +601,12 @@ impl Mul<f32> for Vec3 {
         Vec3::scale(&self, rhs)
     }
 }
+impl Mul<Vec3> for f32 {
+    type Output = Vec3;
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        Vec3::scale(&rhs, self)
+    }
+}
 impl Div<Vec3> for Vec3 {
     type Output = Vec3;
     fn div(self, rhs: Vec3) -> Vec3 {
@@ -401,6 +614,45 @@ impl Div<Vec3> for Vec3 {
     }
 }
 
+// ------------------------------------------------------------
+//
+// Operator Overloads: T *= f32
+//
+// ------------------------------------------------------------
+
+impl MulAssign<f32> for Vec3 {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = Vec3::scale(self, rhs)
+    }
+}
+
+// ------------------------------------------------------------
+//
+// Iterators: Sum and Product
+//
+// ------------------------------------------------------------
+
+impl Sum<Vec3> for Vec3 {
+    fn sum<I: Iterator<Item = Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::zero(), |acc, n| Vec3::add(&acc, &n))
+    }
+}
+impl<'a> Sum<&'a Vec3> for Vec3 {
+    fn sum<I: Iterator<Item = &'a Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::zero(), |acc, n| Vec3::add(&acc, n))
+    }
+}
+impl Product<Vec3> for Vec3 {
+    fn product<I: Iterator<Item = Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::one(), |acc, n| Vec3::mul(&acc, &n))
+    }
+}
+impl<'a> Product<&'a Vec3> for Vec3 {
+    fn product<I: Iterator<Item = &'a Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::one(), |acc, n| Vec3::mul(&acc, n))
+    }
+}
+
 // ------------------------------------------------------------
 //
 // Indexer
@@ -28,14 +28,19 @@ THE SOFTWARE.
 
 
 use std::fmt::{Display, Error, Formatter};
-use std::ops::{Add, Div, Mul, Sub};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, MulAssign, Sub};
 use std::ops::{Index, IndexMut};
 
 use super::Mat4;
+use super::Vec2;
 use super::Vec3;
 use super::Quaternion;
 
+/// The layout is C compatible: 4 contiguous f32 values ordered x, y, z, w
+/// with no padding, so a Vec4 may be viewed as [f32; 4].
 #[derive(Debug, Clone, Copy)]
+#[repr(C)]
 pub struct Vec4 {
     pub x: f32,
     pub y: f32,
@@ -47,6 +52,18 @@ impl Vec4 {
     pub fn new(x: f32, y: f32, z: f32, w: f32) -> Vec4 {
         Vec4 { x, y, z, w }
     }
+    /// Returns the components of this Vec4 as a slice of 4 f32 values.
+    #[inline(always)]
+    pub fn as_f32_slice(&self) -> &[f32] {
+        // Safety: Vec4 is repr(C) with 4 f32 fields and no padding.
+        unsafe { std::slice::from_raw_parts(self as *const Vec4 as *const f32, 4) }
+    }
+    /// Returns a slice of Vec4 values as a flat slice of f32, 4 per Vec4.
+    #[inline(always)]
+    pub fn slice_as_f32(v0: &[Vec4]) -> &[f32] {
+        // Safety: Vec4 is repr(C) with 4 f32 fields and no padding.
+        unsafe { std::slice::from_raw_parts(v0.as_ptr() as *const f32, v0.len() * 4) }
+    }
     #[inline(always)]
     pub fn all(x: f32) -> Vec4 {
         Vec4 { x, y: x, z: x, w: x }
@@ -125,11 +142,31 @@ impl Vec4 {
     pub fn dot(v0: &Vec4, v1: &Vec4) -> f32 {
         (v0.x * v1.x) + (v0.y * v1.y) + (v0.z * v1.z) + (v0.w * v1.w)
     }
+    /// Returns v0 scaled to unit length. v0 must have a non-zero, finite
+    /// length, otherwise the result is infinite or NaN. Use try_normalize()
+    /// or normalize_or_zero() for inputs that may be degenerate.
     #[inline(always)]
     pub fn normalize(v0: &Vec4) -> Vec4 {
         let len = 1.0 / f32::sqrt((v0.x * v0.x) + (v0.y * v0.y) + (v0.z * v0.z) + (v0.w * v0.w));
         Vec4::new(v0.x * len, v0.y * len, v0.z * len, v0.w * len)
     }
+    /// Returns v0 scaled to unit length, or None if the length of v0 is not
+    /// greater than epsilon or is not finite.
+    #[inline(always)]
+    pub fn try_normalize(v0: &Vec4, epsilon: f32) -> Option<Vec4> {
+        let len = Vec4::length(v0);
+        if len.is_finite() && len > epsilon {
+            let len = 1.0 / len;
+            Some(Vec4::new(v0.x * len, v0.y * len, v0.z * len, v0.w * len))
+        } else {
+            None
+        }
+    }
+    /// Returns v0 scaled to unit length, or zero if v0 cannot be normalized.
+    #[inline(always)]
+    pub fn normalize_or_zero(v0: &Vec4) -> Vec4 {
+        Vec4::try_normalize(v0, f32::EPSILON).unwrap_or_else(Vec4::zero)
+    }
     #[inline(always)]
     pub fn abs(v0: &Vec4) -> Vec4 {
         Vec4::new(
@@ -265,8 +302,21 @@ impl Vec4 {
             (((v0.w * n2) + (v1.w * n3)) + (t0.w * n4)) + (t1.w * n5),
         )
     }
+    /// Returns the row vector v0 multiplied by m0, v0 * m0. With the simd
+    /// feature enabled this uses SSE when the CPU supports it.
     #[inline(always)]
     pub fn transform(v0: &Vec4, m0: &Mat4) -> Vec4 {
+        #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            if cfg!(target_feature = "sse") || is_x86_feature_detected!("sse") {
+                return unsafe { Vec4::transform_sse(v0, m0) };
+            }
+        }
+        Vec4::transform_scalar(v0, m0)
+    }
+    /// Returns v0 * m0 without SIMD.
+    #[inline(always)]
+    pub fn transform_scalar(v0: &Vec4, m0: &Mat4) -> Vec4 {
         Vec4::new(
             (((v0.x * m0.m11) + (v0.y * m0.m21)) + (v0.z * m0.m31)) + (v0.w * m0.m41),
             (((v0.x * m0.m12) + (v0.y * m0.m22)) + (v0.z * m0.m32)) + (v0.w * m0.m42),
@@ -274,6 +324,28 @@ impl Vec4 {
             (((v0.x * m0.m14) + (v0.y * m0.m24)) + (v0.z * m0.m34)) + (v0.w * m0.m44),
         )
     }
+    /// Returns v0 * m0 using SSE.
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[target_feature(enable = "sse")]
+    #[inline]
+    unsafe fn transform_sse(v0: &Vec4, m0: &Mat4) -> Vec4 {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        let mut out = [0.0; 4];
+        let m1 = m0 as *const Mat4 as *const f32;
+        let n0 = _mm_add_ps(
+            _mm_add_ps(
+                _mm_add_ps(
+                    _mm_mul_ps(_mm_set1_ps(v0.x), _mm_loadu_ps(m1)),
+                    _mm_mul_ps(_mm_set1_ps(v0.y), _mm_loadu_ps(m1.add(4)))),
+                _mm_mul_ps(_mm_set1_ps(v0.z), _mm_loadu_ps(m1.add(8)))),
+            _mm_mul_ps(_mm_set1_ps(v0.w), _mm_loadu_ps(m1.add(12))));
+        _mm_storeu_ps(out.as_mut_ptr(), n0);
+        Vec4::new(out[0], out[1], out[2], out[3])
+    }
     #[inline(always)]
     pub fn transform_quaternion(v0: &Vec4, q0: &Quaternion) -> Vec4 {
         let n0 = q0.x + q0.x;
@@ -289,10 +361,10 @@ impl Vec4 {
         let n10 = q0.y * n2;
         let n11 = q0.z * n2;
         Vec4::new(
-            (v0.x * ((1.0 - n9) - n11)) + (v0.y * (n7 - n5)),
-            (v0.x * (n7 + n5)) + (v0.y * ((1.0 - n6) - n11)),
-            (v0.x * (n8 - n4)) + (v0.y * (n10 + n3)),
-            0.0,
+            ((v0.x * ((1.0 - n9) - n11)) + (v0.y * (n7 - n5))) + (v0.z * (n8 + n4)),
+            ((v0.x * (n7 + n5)) + (v0.y * ((1.0 - n6) - n11))) + (v0.z * (n10 - n3)),
+            ((v0.x * (n8 - n4)) + (v0.y * (n10 + n3))) + (v0.z * ((1.0 - n6) - n9)),
+            v0.w,
         )
     }
 }
@@ -303,9 +375,54 @@ impl Vec4 {
 //
 // ------------------------------------------------------------
 impl Vec4 {
+    pub fn xy(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
     pub fn xyz(&self) -> Vec3 {
         Vec3::new(self.x, self.y, self.z)
     }
+    pub fn xyz0(&self) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, 0.0)
+    }
+    pub fn xyz1(&self) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, 1.0)
+    }
+    pub fn with_x(&self, x: f32) -> Vec4 {
+        Vec4::new(x, self.y, self.z, self.w)
+    }
+    pub fn with_y(&self, y: f32) -> Vec4 {
+        Vec4::new(self.x, y, self.z, self.w)
+    }
+    pub fn with_z(&self, z: f32) -> Vec4 {
+        Vec4::new(self.x, self.y, z, self.w)
+    }
+    pub fn with_w(&self, w: f32) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+    pub fn min_component(&self) -> f32 {
+        f32::min(f32::min(f32::min(self.x, self.y), self.z), self.w)
+    }
+    pub fn max_component(&self) -> f32 {
+        f32::max(f32::max(f32::max(self.x, self.y), self.z), self.w)
+    }
+    pub fn sum(&self) -> f32 {
+        self.x + self.y + self.z + self.w
+    }
+    /// Returns the index of the largest component. Ties resolve to the
+    /// lowest index.
+    pub fn max_axis(&self) -> usize {
+        let mut n0 = 0;
+        if self.y > self[n0] {
+            n0 = 1;
+        }
+        if self.z > self[n0] {
+            n0 = 2;
+        }
+        if self.w > self[n0] {
+            n0 = 3;
+        }
+        n0
+    }
 }
 
 // ------------------------------------------------------------
@@ -357,6 +474,12 @@ impl Mul<f32> for &Vec4 {
         Vec4::scale(self, rhs)
     }
 }
+impl Mul<&Vec4> for f32 {
+    type Output = Vec4;
+    fn mul(self, rhs: &Vec4) -> Vec4 {
+        Vec4::scale(rhs, self)
+    }
+}
 impl Div<&Vec4> for &Vec4 {
     type Output = Vec4;
     fn div(self, rhs: &Vec4) -> Vec4 {
@@ -400,12 +523,57 @@ impl Mul<f32> for Vec4 {
         Vec4::scale(&self, rhs)
     }
 }
+impl Mul<Vec4> for f32 {
+    type Output = Vec4;
+    fn mul(self, rhs: Vec4) -> Vec4 {
+        Vec4::scale(&rhs, self)
+    }
+}
 impl Div<Vec4> for Vec4 {
     type Output = Vec4;
     fn div(self, rhs: Vec4) -> Vec4 {
         Vec4::div(&self, &rhs)
     }
 }
+// ------------------------------------------------------------
+//
+// Operator Overloads: T *= f32
+//
+// ------------------------------------------------------------
+
+impl MulAssign<f32> for Vec4 {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = Vec4::scale(self, rhs)
+    }
+}
+
+// ------------------------------------------------------------
+//
+// Iterators: Sum and Product
+//
+// ------------------------------------------------------------
+
+impl Sum<Vec4> for Vec4 {
+    fn sum<I: Iterator<Item = Vec4>>(iter: I) -> Vec4 {
+        iter.fold(Vec4::zero(), |acc, n| Vec4::add(&acc, &n))
+    }
+}
+impl<'a> Sum<&'a Vec4> for Vec4 {
+    fn sum<I: Iterator<Item = &'a Vec4>>(iter: I) -> Vec4 {
+        iter.fold(Vec4::zero(), |acc, n| Vec4::add(&acc, n))
+    }
+}
+impl Product<Vec4> for Vec4 {
+    fn product<I: Iterator<Item = Vec4>>(iter: I) -> Vec4 {
+        iter.fold(Vec4::one(), |acc, n| Vec4::mul(&acc, &n))
+    }
+}
+impl<'a> Product<&'a Vec4> for Vec4 {
+    fn product<I: Iterator<Item = &'a Vec4>>(iter: I) -> Vec4 {
+        iter.fold(Vec4::one(), |acc, n| Vec4::mul(&acc, n))
+    }
+}
+
 // ------------------------------------------------------------
 //
 // Indexer
@@ -0,0 +1,93 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Bvh, Ray, Triangle, Vec3};
+
+struct Lcg(u32);
+impl Lcg {
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(1664525).wrapping_add(1013904223);
+        (self.0 >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+fn triangles() -> Vec<Triangle> {
+    let mut lcg = Lcg(7);
+    let mut triangles = vec![];
+    for _ in 0..200 {
+        let center = Vec3::new(lcg.next() * 20.0 - 10.0, lcg.next() * 20.0 - 10.0, lcg.next() * 20.0);
+        let size = 0.5 + lcg.next();
+        triangles.push(Triangle::new(
+            Vec3::new(center.x - size, center.y - size, center.z),
+            Vec3::new(center.x + size, center.y - size, center.z),
+            Vec3::new(center.x, center.y + size, center.z),
+        ));
+    }
+    triangles
+}
+
+fn brute_force(triangles: &[Triangle], ray: &Ray) -> Option<(usize, f32)> {
+    let mut closest: Option<(usize, f32)> = None;
+    for (index, triangle) in triangles.iter().enumerate() {
+        if let Some(distance) = Ray::intersect_triangle(ray, triangle) {
+            match closest {
+                Some((_, nearest)) if distance >= nearest => {}
+                _ => closest = Some((index, distance)),
+            }
+        }
+    }
+    closest
+}
+
+#[test]
+fn bvh_matches_brute_force() {
+    let triangles = triangles();
+    let bvh = Bvh::new(&triangles);
+    let mut lcg = Lcg(42);
+    let mut hits = 0;
+    for _ in 0..500 {
+        let origin = Vec3::new(lcg.next() * 20.0 - 10.0, lcg.next() * 20.0 - 10.0, -5.0);
+        let direction = Vec3::normalize(&Vec3::new(lcg.next() - 0.5, lcg.next() - 0.5, 1.0));
+        let ray = Ray::new(origin, direction);
+        let expect = brute_force(&triangles, &ray);
+        assert_eq!(bvh.intersect(&ray), expect);
+        if expect.is_some() {
+            hits += 1;
+        }
+    }
+    assert!(hits > 50);
+}
+
+#[test]
+fn bvh_empty_and_miss() {
+    let ray = Ray::new(Vec3::zero(), Vec3::unit_z());
+    assert_eq!(Bvh::new(&[]).intersect(&ray), None);
+    let bvh = Bvh::new(&triangles());
+    let away = Ray::new(Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, -1.0));
+    assert_eq!(bvh.intersect(&away), None);
+}
@@ -0,0 +1,97 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+// These tests pin down the matrix convention used throughout the crate:
+// row major storage, row vectors multiplied on the left, and transforms
+// that compose left to right.
+
+use black_math::{Mat4, Vec2, Vec3, Vec4};
+use std::f32::consts::FRAC_PI_2;
+
+fn approx3(a: &Vec3, b: &Vec3) -> bool {
+    (a.x - b.x).abs() < 1e-5 && (a.y - b.y).abs() < 1e-5 && (a.z - b.z).abs() < 1e-5
+}
+
+fn approx4(a: &Vec4, b: &Vec4) -> bool {
+    (a.x - b.x).abs() < 1e-5 && (a.y - b.y).abs() < 1e-5 && (a.z - b.z).abs() < 1e-5 && (a.w - b.w).abs() < 1e-5
+}
+
+#[test]
+fn translation_moves_points_but_not_directions() {
+    let t = Vec3::new(1.0, 2.0, 3.0);
+    let m = Mat4::translation(&t);
+    let p = Vec4::new(4.0, 5.0, 6.0, 1.0);
+    let d = Vec4::new(4.0, 5.0, 6.0, 0.0);
+    assert!(approx4(&Vec4::transform(&p, &m), &Vec4::new(5.0, 7.0, 9.0, 1.0)));
+    assert!(approx4(&Vec4::transform(&d, &m), &d));
+    assert!(approx4(&(p * m), &Vec4::new(5.0, 7.0, 9.0, 1.0)));
+    assert!(approx3(&Vec3::transform(&Vec3::new(4.0, 5.0, 6.0), &m), &Vec3::new(5.0, 7.0, 9.0)));
+    assert!(approx3(&Vec3::transform_normal(&Vec3::new(4.0, 5.0, 6.0), &m), &Vec3::new(4.0, 5.0, 6.0)));
+    let v = Vec2::transform(&Vec2::new(4.0, 5.0), &m);
+    assert!((v.x - 5.0).abs() < 1e-5 && (v.y - 7.0).abs() < 1e-5);
+}
+
+#[test]
+fn mul_applies_left_operand_first() {
+    let a = Mat4::rotation_z(FRAC_PI_2);
+    let b = Mat4::translation(&Vec3::new(10.0, 0.0, 0.0));
+    let p = Vec4::new(1.0, 0.0, 0.0, 1.0);
+
+    // rotate (1, 0, 0) to (0, 1, 0), then translate.
+    let expect = Vec4::new(10.0, 1.0, 0.0, 1.0);
+    assert!(approx4(&(p * Mat4::mul(&a, &b)), &expect));
+    assert!(approx4(&((p * a) * b), &expect));
+    assert!(approx4(&(p * (a * b)), &expect));
+
+    // the opposite order translates first.
+    assert!(approx4(&(p * (b * a)), &Vec4::new(0.0, 11.0, 0.0, 1.0)));
+}
+
+#[test]
+fn rotations_are_counter_clockwise_about_their_axis() {
+    let x = Vec3::unit_x();
+    let y = Vec3::unit_y();
+    let z = Vec3::unit_z();
+    assert!(approx3(&Vec3::transform(&y, &Mat4::rotation_x(FRAC_PI_2)), &z));
+    assert!(approx3(&Vec3::transform(&z, &Mat4::rotation_y(FRAC_PI_2)), &x));
+    assert!(approx3(&Vec3::transform(&x, &Mat4::rotation_z(FRAC_PI_2)), &y));
+}
+
+#[test]
+fn model_view_projection_composes_left_to_right() {
+    let model      = Mat4::translation(&Vec3::new(0.0, 0.0, -5.0));
+    let view       = Mat4::look_at(&Vec3::new(0.0, 0.0, 5.0), &Vec3::zero(), &Vec3::unit_y());
+    let projection = Mat4::perspective_fov(1.0, 1.0, 0.1, 100.0);
+    let p = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let a = p * (model * (view * projection));
+    let b = ((p * model) * view) * projection;
+    assert!(approx4(&a, &b));
+
+    // the point is 10 units in front of the camera.
+    assert!((a.w - 10.0).abs() < 1e-4);
+}
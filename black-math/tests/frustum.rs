@@ -0,0 +1,88 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Frustum, Mat4, Plane, Vec3};
+
+fn contains(frustum: &Frustum, point: &Vec3) -> bool {
+    [&frustum.near, &frustum.far, &frustum.left, &frustum.right, &frustum.top, &frustum.bottom]
+        .iter()
+        .all(|plane| Plane::dot3(plane, point) <= 0.0)
+}
+
+fn frustum() -> Frustum {
+    let view       = Mat4::look_at(&Vec3::new(0.0, 0.0, 5.0), &Vec3::zero(), &Vec3::unit_y());
+    let projection = Mat4::perspective_fov(1.0, 1.0, 0.1, 100.0);
+    Frustum::from_view_projection(&view, &projection)
+}
+
+#[test]
+fn from_view_projection_matches_combined_matrix() {
+    let view       = Mat4::look_at(&Vec3::new(0.0, 0.0, 5.0), &Vec3::zero(), &Vec3::unit_y());
+    let projection = Mat4::perspective_fov(1.0, 1.0, 0.1, 100.0);
+    assert_eq!(frustum(), Frustum::new(&(view * projection)));
+}
+
+#[test]
+fn classifies_points_inside_and_outside() {
+    let frustum = frustum();
+    assert!(contains(&frustum, &Vec3::zero()));
+    assert!(!contains(&frustum, &Vec3::new(0.0, 0.0, 50.0)));
+    assert!(!contains(&frustum, &Vec3::new(0.0, 0.0, -200.0)));
+    assert!(!contains(&frustum, &Vec3::new(50.0, 0.0, 0.0)));
+}
+
+#[test]
+fn planes_are_normalized() {
+    let frustum = frustum();
+    for plane in [&frustum.near, &frustum.far, &frustum.left, &frustum.right, &frustum.top, &frustum.bottom].iter() {
+        assert!((Vec3::length(&Plane::normal(plane)) - 1.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn corners_lie_on_near_and_far_planes() {
+    let frustum = frustum();
+    assert_eq!(frustum.corners.len(), 8);
+    for corner in frustum.corners[0..4].iter() {
+        assert!(Plane::dot3(&frustum.near, corner).abs() < 1e-3);
+    }
+    for corner in frustum.corners[4..8].iter() {
+        assert!(Plane::dot3(&frustum.far, corner).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn corners_lie_on_side_planes() {
+    let frustum = frustum();
+    let sides = [(&frustum.left, &frustum.top), (&frustum.right, &frustum.top), (&frustum.right, &frustum.bottom), (&frustum.left, &frustum.bottom)];
+    for (n, corner) in frustum.corners.iter().enumerate() {
+        let (side, edge) = sides[n % 4];
+        assert!(Plane::dot3(side, corner).abs() < 1e-2);
+        assert!(Plane::dot3(edge, corner).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,324 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Mat4, Vec3, Vec4};
+
+#[test]
+fn row_3_of_translation_is_translation() {
+    let m = Mat4::translation(&Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(m.row(3), Some(Vec4::new(1.0, 2.0, 3.0, 1.0)));
+    assert_eq!(m.col(3), Some(Vec4::new(0.0, 0.0, 0.0, 1.0)));
+    assert_eq!(m.row(4), None);
+    assert_eq!(m.col(4), None);
+}
+
+#[test]
+fn set_row_and_set_col_round_trip() {
+    let mut m = Mat4::identity();
+    let v = Vec4::new(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(m.set_row(1, &v), Some(()));
+    assert_eq!(m.row(1), Some(v));
+    assert_eq!(m.set_col(2, &v), Some(()));
+    assert_eq!(m.col(2), Some(v));
+    assert_eq!(m.set_row(4, &v), None);
+    assert_eq!(m.set_col(4, &v), None);
+}
+
+fn approx(v0: &Vec3, v1: &Vec3) -> bool {
+    (v0.x - v1.x).abs() < 1e-5 && (v0.y - v1.y).abs() < 1e-5 && (v0.z - v1.z).abs() < 1e-5
+}
+
+#[test]
+fn basis_of_inverted_look_at_matches_camera() {
+    let position = Vec3::new(1.0, 2.0, 3.0);
+    let target   = Vec3::new(-2.0, 0.5, -1.0);
+    let view     = Mat4::look_at(&position, &target, &Vec3::unit_y());
+    let world    = Mat4::invert(&view);
+    let forward  = Vec3::normalize(&(target - position));
+    assert!(approx(&world.forward(), &forward));
+    assert!(approx(&world.translation_part(), &position));
+    assert!(approx(&world.right(), &Vec3::normalize(&Vec3::cross(&forward, &Vec3::unit_y()))));
+    assert!(Vec3::dot(&world.up(), &Vec3::unit_y()) > 0.0);
+}
+
+#[test]
+fn basis_of_translation_is_identity_axes() {
+    let m = Mat4::translation(&Vec3::new(4.0, 5.0, 6.0));
+    assert_eq!(m.right(), Vec3::unit_x());
+    assert_eq!(m.up(), Vec3::unit_y());
+    assert_eq!(m.forward(), Vec3::new(0.0, 0.0, -1.0));
+    assert_eq!(m.translation_part(), Vec3::new(4.0, 5.0, 6.0));
+}
+
+fn depth(projection: &Mat4, z: f32) -> f32 {
+    let position = Vec4::new(0.0, 0.0, z, 1.0) * *projection;
+    position.z / position.w
+}
+
+#[test]
+fn reverse_z_maps_near_to_one_and_far_to_zero() {
+    let projection = Mat4::perspective_fov_reverse_z(1.0, 1.0, 0.1, 1000.0);
+    assert!((depth(&projection, -0.1) - 1.0).abs() < 1e-6);
+    assert!(depth(&projection, -1000.0).abs() < 1e-6);
+}
+
+#[test]
+fn reverse_z_has_more_depth_precision_for_far_objects() {
+    let standard = Mat4::perspective_fov(1.0, 1.0, 0.1, 1000.0);
+    let reversed = Mat4::perspective_fov_reverse_z(1.0, 1.0, 0.1, 1000.0);
+    let distinct = |projection: &Mat4| {
+        let mut values: Vec<u32> = (0..1000)
+            .map(|n| depth(projection, -900.0 - (n as f32) * 0.01).to_bits())
+            .collect();
+        values.sort();
+        values.dedup();
+        values.len()
+    };
+    assert!(distinct(&reversed) > distinct(&standard) * 10);
+}
+
+#[test]
+fn infinite_perspective_keeps_distant_points_inside_far_bound() {
+    let projection = Mat4::perspective_fov_infinite(1.0, 1.0, 0.1);
+    assert!(depth(&projection, -0.1).abs() < 1e-6);
+    let distant = depth(&projection, -1.0e6);
+    assert!(distant > 0.999 && distant < 1.0, "depth was {}", distant);
+}
+
+#[test]
+fn infinite_perspective_matches_finite_projection_in_the_limit() {
+    let infinite = Mat4::perspective_fov_infinite(1.0, 1.5, 0.1);
+    let finite   = Mat4::perspective_fov(1.0, 1.5, 0.1, 1.0e7);
+    for d in [0.5_f32, 10.0, 1000.0].iter() {
+        assert!((depth(&infinite, -*d) - depth(&finite, -*d)).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn from_basis_round_trips_through_accessors() {
+    let right       = Vec3::normalize(&Vec3::new(1.0, 0.0, 1.0));
+    let up          = Vec3::unit_y();
+    let forward     = Vec3::cross(&up, &right);
+    let translation = Vec3::new(3.0, -2.0, 5.0);
+    let matrix = Mat4::from_basis(&right, &up, &forward, &translation);
+    assert!(approx(&matrix.right(), &right));
+    assert!(approx(&matrix.up(), &up));
+    assert!(approx(&matrix.forward(), &forward));
+    assert!(approx(&matrix.translation_part(), &translation));
+}
+
+#[test]
+fn from_basis_of_identity_axes_is_translation() {
+    let translation = Vec3::new(1.0, 2.0, 3.0);
+    let matrix = Mat4::from_basis(&Vec3::unit_x(), &Vec3::unit_y(), &Vec3::new(0.0, 0.0, -1.0), &translation);
+    assert_eq!(matrix, Mat4::translation(&translation));
+}
+
+#[test]
+fn look_at_lh_negates_x_and_z_basis_of_look_at() {
+    let position = Vec3::new(1.0, 2.0, 5.0);
+    let target   = Vec3::new(-1.0, 0.5, 0.0);
+    let up       = Vec3::unit_y();
+    let rh = Mat4::look_at(&position, &target, &up);
+    let lh = Mat4::look_at_lh(&position, &target, &up);
+    for (column, sign) in [(0, -1.0), (1, 1.0), (2, -1.0)].iter() {
+        let a = rh.col(*column).unwrap();
+        let b = lh.col(*column).unwrap();
+        assert!((a.x - b.x * sign).abs() < 1e-6);
+        assert!((a.y - b.y * sign).abs() < 1e-6);
+        assert!((a.z - b.z * sign).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn lh_and_rh_pairs_agree_on_depth_and_mirror_x() {
+    let position = Vec3::new(0.0, 0.0, 5.0);
+    let target   = Vec3::new(0.0, 0.0, 0.0);
+    let up       = Vec3::unit_y();
+    let rh = Mat4::look_at(&position, &target, &up) * Mat4::perspective_fov(1.0, 1.0, 0.1, 100.0);
+    let lh = Mat4::look_at_lh(&position, &target, &up) * Mat4::perspective_fov_lh(1.0, 1.0, 0.1, 100.0);
+    let point = Vec4::new(1.0, 0.5, 0.0, 1.0);
+    let a = point * rh;
+    let b = point * lh;
+    assert!(a.w > 0.0 && b.w > 0.0);
+    assert!((a.z / a.w - b.z / b.w).abs() < 1e-6);
+    assert!((a.z / a.w) > 0.0 && (a.z / a.w) < 1.0);
+    assert!((a.x / a.w + b.x / b.w).abs() < 1e-6);
+    assert!((a.y / a.w - b.y / b.w).abs() < 1e-6);
+}
+
+#[test]
+fn orthographic_lh_maps_near_and_far_to_unit_depth() {
+    let projection = Mat4::orthographic_lh(2.0, 2.0, 1.0, 11.0);
+    assert!(depth(&projection, 1.0).abs() < 1e-6);
+    assert!((depth(&projection, 11.0) - 1.0).abs() < 1e-6);
+    let projection = Mat4::orthographic(2.0, 2.0, 1.0, 11.0);
+    assert!(depth(&projection, -1.0).abs() < 1e-6);
+    assert!((depth(&projection, -11.0) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn mul_matches_scalar_mul() {
+    let a = Mat4::rotation_y(0.7) * Mat4::translation(&Vec3::new(1.0, -2.0, 3.5));
+    let b = Mat4::perspective_fov(1.1, 1.5, 0.1, 100.0) * Mat4::scale(&Vec3::new(2.0, 0.5, -1.0));
+    let simd   = Mat4::mul(&a, &b);
+    let scalar = Mat4::mul_scalar(&a, &b);
+    for i in 0..4 {
+        let n0 = simd.row(i).unwrap();
+        let n1 = scalar.row(i).unwrap();
+        assert!((n0.x - n1.x).abs() < 1e-6 && (n0.y - n1.y).abs() < 1e-6);
+        assert!((n0.z - n1.z).abs() < 1e-6 && (n0.w - n1.w).abs() < 1e-6);
+    }
+    let v = Vec4::new(0.3, -1.2, 4.0, 1.0);
+    let n0 = Vec4::transform(&v, &simd);
+    let n1 = Vec4::transform_scalar(&v, &scalar);
+    assert!((n0.x - n1.x).abs() < 1e-5 && (n0.y - n1.y).abs() < 1e-5);
+    assert!((n0.z - n1.z).abs() < 1e-5 && (n0.w - n1.w).abs() < 1e-5);
+}
+
+#[test]
+fn transform_points_matches_per_element_transform() {
+    let m = Mat4::rotation_x(0.4) * Mat4::translation(&Vec3::new(1.0, 2.0, -3.0)) * Mat4::perspective_fov(1.0, 1.2, 0.1, 50.0);
+    let input: Vec<Vec4> = (0..37)
+        .map(|i| Vec4::new(i as f32 * 0.5, -(i as f32), 2.0 - i as f32 * 0.25, 1.0))
+        .collect();
+    let mut output = vec![Vec4::zero(); input.len()];
+    Mat4::transform_points(&m, &input, &mut output);
+    for (v0, v1) in input.iter().zip(output.iter()) {
+        let expect = Vec4::transform(v0, &m);
+        assert!((v1.x - expect.x).abs() < 1e-5 && (v1.y - expect.y).abs() < 1e-5);
+        assert!((v1.z - expect.z).abs() < 1e-5 && (v1.w - expect.w).abs() < 1e-5);
+    }
+
+    let input: Vec<Vec3> = input.iter().map(|v| Vec3::new(v.x, v.y, v.z)).collect();
+    let mut output = vec![Vec3::zero(); input.len()];
+    Mat4::transform_points3(&m, &input, &mut output);
+    for (v0, v1) in input.iter().zip(output.iter()) {
+        let expect = Vec3::transform(v0, &m);
+        assert!((v1.x - expect.x).abs() < 1e-5 && (v1.y - expect.y).abs() < 1e-5 && (v1.z - expect.z).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn transform_points_stops_at_shorter_slice() {
+    let m = Mat4::translation(&Vec3::new(1.0, 0.0, 0.0));
+    let input  = vec![Vec4::new(0.0, 0.0, 0.0, 1.0); 4];
+    let mut output = vec![Vec4::zero(); 2];
+    Mat4::transform_points(&m, &input, &mut output);
+    assert!(output.iter().all(|v| v.x == 1.0));
+}
+
+#[test]
+fn rotation_between_takes_from_to_to() {
+    let pairs = [
+        (Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 3.0, 0.0)),
+        (Vec3::new(1.0, 2.0, 3.0), Vec3::new(-2.0, 0.5, 1.0)),
+        (Vec3::new(0.0, 0.0, 2.0), Vec3::new(0.0, 0.0, 5.0)),
+        (Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0)),
+        (Vec3::new(1.0, -1.0, 0.5), Vec3::new(-2.0, 2.0, -1.0)),
+    ];
+    for (from, to) in pairs.iter() {
+        let matrix  = Mat4::rotation_between(from, to);
+        let rotated = Vec3::transform_normal(&Vec3::normalize(from), &matrix);
+        assert!(Vec3::distance(&rotated, &Vec3::normalize(to)) < 1e-5, "{:?} -> {:?}", from, to);
+        assert!((Mat4::determinant(&matrix) - 1.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn mirror_negates_one_axis() {
+    let point = Vec3::new(1.0, 2.0, 3.0);
+    assert_eq!(Vec3::transform(&point, &Mat4::mirror_x()), Vec3::new(-1.0, 2.0, 3.0));
+    assert_eq!(Vec3::transform(&point, &Mat4::mirror_y()), Vec3::new(1.0, -2.0, 3.0));
+    assert_eq!(Vec3::transform(&point, &Mat4::mirror_z()), Vec3::new(1.0, 2.0, -3.0));
+}
+
+#[test]
+fn shear_offsets_one_axis_by_another() {
+    let point = Vec3::new(1.0, 2.0, 3.0);
+    // x sheared by y, y and z are unchanged.
+    assert_eq!(Vec3::transform(&point, &Mat4::shear(0.5, 0.0, 0.0, 0.0, 0.0, 0.0)), Vec3::new(2.0, 2.0, 3.0));
+    // z sheared by x and y, x and y are unchanged.
+    assert_eq!(Vec3::transform(&point, &Mat4::shear(0.0, 0.0, 0.0, 0.0, 2.0, 1.0)), Vec3::new(1.0, 2.0, 7.0));
+    assert_eq!(Vec3::transform(&point, &Mat4::shear(0.0, 0.0, 1.0, 1.0, 0.0, 0.0)), Vec3::new(1.0, 6.0, 3.0));
+    assert_eq!(Mat4::determinant(&Mat4::shear(0.5, 0.0, 0.0, 0.0, 0.0, 0.0)), 1.0);
+}
+
+#[test]
+fn as_f32_slice_is_row_major() {
+    let m0 = Mat4::translation(&Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(std::mem::size_of::<Mat4>(), 64);
+    assert_eq!(m0.as_f32_slice(), &[
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        1.0, 2.0, 3.0, 1.0,
+    ]);
+}
+
+#[test]
+fn display_alternate_prints_aligned_rows() {
+    let m0 = Mat4::translation(&Vec3::new(1.5, -20.0, 3.0));
+    let pretty = format!("{:#}", m0);
+    let lines = pretty.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "[   1.0000   0.0000   0.0000   0.0000 ]");
+    assert_eq!(lines[3], "[   1.5000 -20.0000   3.0000   1.0000 ]");
+    assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    assert_eq!(format!("{:#.1}", m0).lines().next(), Some("[   1.0   0.0   0.0   0.0 ]"));
+}
+
+#[test]
+fn display_default_is_single_line() {
+    let text = format!("{}", Mat4::identity());
+    assert_eq!(text, "Mat4 { m11: 1, m12: 0, m13: 0, m14: 0, m21: 0, m22: 1, m23: 0, m24: 0, m31: 0, m32: 0, m33: 1, m34: 0, m41: 0, m42: 0, m43: 0, m44: 1, }");
+}
+
+#[test]
+fn is_identity_respects_epsilon() {
+    assert!(Mat4::is_identity(&Mat4::identity(), 0.0));
+    let mut m0 = Mat4::identity();
+    m0.m23 = 1e-3;
+    assert!(!Mat4::is_identity(&m0, 1e-5));
+    assert!(Mat4::is_identity(&m0, 1e-2));
+    assert!(!Mat4::is_identity(&Mat4::translation(&Vec3::new(0.0, 1.0, 0.0)), 1e-2));
+
+    // a rotation times its inverse drifts by rounding only.
+    let rotation = Mat4::rotation_y(0.3) * Mat4::rotation_x(1.1);
+    assert!(Mat4::is_identity(&(rotation * Mat4::invert(&rotation)), 1e-5));
+}
+
+#[test]
+fn approx_equals_compares_components() {
+    let m0 = Mat4::rotation_z(0.5);
+    let mut m1 = m0;
+    m1.m41 += 0.01;
+    assert!(Mat4::approx_equals(&m0, &m0, 0.0));
+    assert!(!Mat4::approx_equals(&m0, &m1, 1e-3));
+    assert!(Mat4::approx_equals(&m0, &m1, 0.1));
+}
@@ -0,0 +1,78 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::noise::{perlin_2d, value_noise_2d};
+
+#[test]
+fn noise_is_deterministic() {
+    for i in 0..100 {
+        let x = i as f32 * 0.37 - 20.0;
+        let y = i as f32 * -0.53 + 7.0;
+        assert_eq!(value_noise_2d(x, y, 1), value_noise_2d(x, y, 1));
+        assert_eq!(perlin_2d(x, y, 1), perlin_2d(x, y, 1));
+    }
+}
+
+#[test]
+fn noise_stays_within_bounds() {
+    let mut value_range  = (f32::MAX, f32::MIN);
+    let mut perlin_range = (f32::MAX, f32::MIN);
+    for j in -100..100 {
+        for i in -100..100 {
+            let x = i as f32 * 0.13;
+            let y = j as f32 * 0.17;
+            let value  = value_noise_2d(x, y, 42);
+            let perlin = perlin_2d(x, y, 42);
+            assert!((0.0..=1.0).contains(&value));
+            assert!((-1.0..=1.0).contains(&perlin));
+            value_range  = (value_range.0.min(value), value_range.1.max(value));
+            perlin_range = (perlin_range.0.min(perlin), perlin_range.1.max(perlin));
+        }
+    }
+    // the noise should make use of its range.
+    assert!(value_range.0 < 0.2 && value_range.1 > 0.8);
+    assert!(perlin_range.0 < -0.4 && perlin_range.1 > 0.4);
+}
+
+#[test]
+fn perlin_is_zero_at_lattice_points() {
+    for j in -5..5 {
+        for i in -5..5 {
+            assert_eq!(perlin_2d(i as f32, j as f32, 7), 0.0);
+        }
+    }
+}
+
+#[test]
+fn seed_changes_noise() {
+    let differs = (0..20).any(|i| {
+        let x = i as f32 * 0.7 + 0.3;
+        value_noise_2d(x, 0.5, 1) != value_noise_2d(x, 0.5, 2)
+    });
+    assert!(differs);
+}
@@ -0,0 +1,89 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Plane, Vec3};
+
+fn approx(v0: &Vec3, v1: &Vec3) -> bool {
+    Vec3::distance(v0, v1) < 1e-5
+}
+
+// planes are a x + b y + c z + d = 0.
+fn yz() -> Plane { Plane::new(1.0, 0.0, 0.0, 0.0) }
+fn xz() -> Plane { Plane::new(0.0, 1.0, 0.0, 0.0) }
+fn xy() -> Plane { Plane::new(0.0, 0.0, 1.0, 0.0) }
+
+#[test]
+fn intersect_planes_coordinate_planes_meet_at_origin() {
+    assert_eq!(Plane::intersect_planes(&yz(), &xz(), &xy()), Some(Vec3::zero()));
+    assert_eq!(Plane::intersect_planes(&xy(), &yz(), &xz()), Some(Vec3::zero()));
+}
+
+#[test]
+fn intersect_planes_offset_planes() {
+    let p0 = Plane::new(1.0, 0.0, 0.0, -2.0);
+    let p1 = Plane::new(0.0, 2.0, 0.0, 6.0);
+    let p2 = Plane::normalize(&Plane::new(1.0, 1.0, 1.0, -4.0));
+    let point = Plane::intersect_planes(&p0, &p1, &p2).unwrap();
+    assert!(approx(&point, &Vec3::new(2.0, -3.0, 5.0)));
+    for plane in [p0, p1, p2].iter() {
+        assert!(Plane::dot3(plane, &point).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn intersect_planes_returns_none_for_parallel_or_shared_line() {
+    let parallel = Plane::new(2.0, 0.0, 0.0, -1.0);
+    assert_eq!(Plane::intersect_planes(&yz(), &parallel, &xy()), None);
+    // three planes through the z axis share a line rather than a point.
+    let diagonal = Plane::normalize(&Plane::new(1.0, 1.0, 0.0, 0.0));
+    assert_eq!(Plane::intersect_planes(&yz(), &xz(), &diagonal), None);
+}
+
+#[test]
+fn intersect_line_coordinate_planes_meet_on_axis() {
+    let line = Plane::intersect_line(&yz(), &xz()).unwrap();
+    assert!(approx(&line.position, &Vec3::zero()));
+    assert!(approx(&line.direction, &Vec3::unit_z()));
+    let line = Plane::intersect_line(&xz(), &xy()).unwrap();
+    assert!(approx(&line.position, &Vec3::zero()));
+    assert!(approx(&line.direction, &Vec3::unit_x()));
+}
+
+#[test]
+fn intersect_line_lies_on_both_planes() {
+    let p0 = Plane::new(1.0, 0.0, 0.0, -2.0);
+    let p1 = Plane::normalize(&Plane::new(0.0, 1.0, 1.0, -4.0));
+    let line = Plane::intersect_line(&p0, &p1).unwrap();
+    assert!((Vec3::length(&line.direction) - 1.0).abs() < 1e-5);
+    for distance in [-3.0, 0.0, 5.0].iter() {
+        let point = line.position + line.direction * *distance;
+        assert!(Plane::dot3(&p0, &point).abs() < 1e-5);
+        assert!(Plane::dot3(&p1, &point).abs() < 1e-5);
+    }
+    assert_eq!(Plane::intersect_line(&p0, &Plane::new(-3.0, 0.0, 0.0, 1.0)), None);
+}
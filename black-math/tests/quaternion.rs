@@ -0,0 +1,157 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Mat4, Quaternion, Vec3, Vec4};
+
+fn approx(v0: &Vec3, v1: &Vec3) -> bool {
+    (v0.x - v1.x).abs() < 1e-5 && (v0.y - v1.y).abs() < 1e-5 && (v0.z - v1.z).abs() < 1e-5
+}
+
+// both the owned and the borrowed Mul impls are under test.
+#[allow(clippy::op_ref)]
+#[test]
+fn mul_vec3_matches_rotate_vector() {
+    let q = Quaternion::from_axis_angle(&Vec3::normalize(&Vec3::new(1.0, 2.0, 3.0)), 0.7);
+    let v = Vec3::new(0.5, -1.5, 2.0);
+    let expect = Quaternion::rotate_vector(&q, &v);
+    assert!(approx(&(q * v), &expect));
+    assert!(approx(&(&q * &v), &expect));
+}
+
+#[test]
+fn rotate_vector_matches_rotation_matrix() {
+    let q = Quaternion::from_axis_angle(&Vec3::normalize(&Vec3::new(-1.0, 0.5, 2.0)), 1.3);
+    let v = Vec3::new(1.0, 2.0, 3.0);
+    let m = Mat4::from_quaternion(&q);
+    assert!(approx(&Quaternion::rotate_vector(&q, &v), &Vec3::transform(&v, &m)));
+}
+
+#[test]
+fn rotate_vector_about_y_axis() {
+    let q = Quaternion::from_axis_angle(&Vec3::unit_y(), std::f32::consts::FRAC_PI_2);
+    let v = Quaternion::rotate_vector(&q, &Vec3::unit_x());
+    assert!(approx(&v, &Vec3::new(0.0, 0.0, -1.0)));
+}
+
+#[test]
+fn transform_quaternion_matches_axis_rotation_matrices() {
+    let v = Vec3::new(0.5, -1.5, 2.0);
+    let rotations = [
+        (Vec3::unit_x(), Mat4::rotation_x(0.9)),
+        (Vec3::unit_y(), Mat4::rotation_y(0.9)),
+        (Vec3::unit_z(), Mat4::rotation_z(0.9)),
+    ];
+    for (axis, m) in rotations.iter() {
+        let q = Quaternion::from_axis_angle(axis, 0.9);
+        let expect = Vec3::transform(&v, m);
+        assert!(approx(&Vec3::transform_quaternion(&v, &q), &expect));
+        assert!(approx(&Quaternion::rotate_vector(&q, &v), &expect));
+        assert!(approx(&(q * v), &expect));
+        let w = Vec4::transform_quaternion(&Vec4::new(v.x, v.y, v.z, 1.0), &q);
+        assert!(approx(&Vec3::new(w.x, w.y, w.z), &expect));
+        assert_eq!(w.w, 1.0);
+    }
+}
+
+#[test]
+fn as_f32_slice_matches_fields() {
+    assert_eq!(std::mem::size_of::<Quaternion>(), 16);
+    assert_eq!(Quaternion::new(1.0, 2.0, 3.0, 4.0).as_f32_slice(), &[1.0, 2.0, 3.0, 4.0]);
+}
+
+fn approx_quaternion(q0: &Quaternion, q1: &Quaternion, epsilon: f32) -> bool {
+    (Quaternion::dot(q0, q1).abs() - 1.0).abs() < epsilon
+}
+
+#[test]
+fn squad_reduces_to_slerp_when_controls_equal_endpoints() {
+    let q0 = Quaternion::from_axis_angle(&Vec3::unit_y(), 0.3);
+    let q1 = Quaternion::from_axis_angle(&Vec3::normalize(&Vec3::new(1.0, 1.0, 0.0)), 1.4);
+    for n in 0..=10 {
+        let t = n as f32 / 10.0;
+        let squad = Quaternion::squad(&q0, &q0, &q1, &q1, t);
+        let slerp = Quaternion::slerp(&q0, &q1, t);
+        assert!(approx_quaternion(&squad, &slerp, 1e-6), "t = {}", t);
+    }
+}
+
+#[test]
+fn squad_control_of_uniform_rotation_is_the_keyframe() {
+    let axis = Vec3::normalize(&Vec3::new(0.0, 1.0, 1.0));
+    let q0 = Quaternion::from_axis_angle(&axis, 0.2);
+    let q1 = Quaternion::from_axis_angle(&axis, 0.7);
+    let q2 = Quaternion::from_axis_angle(&axis, 1.2);
+    assert!(approx_quaternion(&Quaternion::squad_control(&q0, &q1, &q2), &q1, 1e-6));
+}
+
+// returns the body rotation vector from q0 to q1, scaled by 1 / h.
+fn angular_velocity(q0: &Quaternion, q1: &Quaternion, h: f32) -> Vec3 {
+    let n0 = Quaternion::mul(&Quaternion::conjugate(q0), q1);
+    Vec3::new(n0.x, n0.y, n0.z) * (f32::signum(n0.w) * 2.0 / h)
+}
+
+#[test]
+fn squad_spline_has_continuous_velocity_at_keyframes() {
+    let keys = [
+        Quaternion::from_axis_angle(&Vec3::unit_x(), 0.0),
+        Quaternion::from_axis_angle(&Vec3::unit_y(), 0.9),
+        Quaternion::from_axis_angle(&Vec3::unit_z(), 1.6),
+        Quaternion::from_axis_angle(&Vec3::unit_x(), 0.4),
+    ];
+    let a0 = Quaternion::squad_control(&keys[0], &keys[0], &keys[1]);
+    let a1 = Quaternion::squad_control(&keys[0], &keys[1], &keys[2]);
+    let a2 = Quaternion::squad_control(&keys[1], &keys[2], &keys[3]);
+    let h  = 1e-3;
+    let v0 = angular_velocity(&Quaternion::squad(&keys[0], &a0, &a1, &keys[1], 1.0 - h), &keys[1], h);
+    let v1 = angular_velocity(&keys[1], &Quaternion::squad(&keys[1], &a1, &a2, &keys[2], h), h);
+    assert!(Vec3::distance(&v0, &v1) < 0.02 * Vec3::length(&v0), "{} {}", v0, v1);
+
+    // chained slerp changes velocity abruptly at the keyframe.
+    let v0 = angular_velocity(&Quaternion::slerp(&keys[0], &keys[1], 1.0 - h), &keys[1], h);
+    let v1 = angular_velocity(&keys[1], &Quaternion::slerp(&keys[1], &keys[2], h), h);
+    assert!(Vec3::distance(&v0, &v1) > 0.2 * Vec3::length(&v0), "{} {}", v0, v1);
+}
+
+#[test]
+fn is_normalized_respects_epsilon() {
+    let q0 = Quaternion::from_axis_angle(&Vec3::normalize(&Vec3::new(1.0, 2.0, 3.0)), 0.8);
+    assert!(Quaternion::is_normalized(&q0, 1e-6));
+    let drifted = Quaternion::new(q0.x * 1.001, q0.y * 1.001, q0.z * 1.001, q0.w * 1.001);
+    assert!(!Quaternion::is_normalized(&drifted, 1e-5));
+    assert!(Quaternion::is_normalized(&drifted, 1e-2));
+    assert!(Quaternion::is_normalized(&Quaternion::normalize(&drifted), 1e-6));
+}
+
+#[test]
+fn approx_equals_compares_components() {
+    let q0 = Quaternion::from_axis_angle(&Vec3::unit_y(), 0.5);
+    let q1 = Quaternion::new(q0.x, q0.y + 1e-4, q0.z, q0.w);
+    assert!(Quaternion::approx_equals(&q0, &q1, 1e-3));
+    assert!(!Quaternion::approx_equals(&q0, &q1, 1e-5));
+    assert!(!Quaternion::approx_equals(&q0, &Quaternion::negate(&q0), 1e-3));
+}
@@ -0,0 +1,129 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{BoundingBox, Plane, Ray, Sphere, Triangle, Vec3};
+
+#[test]
+fn at_returns_point_along_ray() {
+    let ray = Ray::new(Vec3::new(1.0, 2.0, 3.0), Vec3::unit_x());
+    assert_eq!(Ray::at(&ray, 2.0), Vec3::new(3.0, 2.0, 3.0));
+}
+
+#[test]
+fn intersect_plane_returns_distance() {
+    let plane = Plane::new(0.0, 1.0, 0.0, 0.0);
+    let ray   = Ray::new(Vec3::new(0.0, 4.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+    assert_eq!(Ray::intersect_plane(&ray, &plane), Some(4.0));
+}
+
+#[test]
+fn plane_intersect_ray_returns_point_on_plane() {
+    let plane = Plane::new(0.0, 1.0, 0.0, 0.0);
+    let ray   = Ray::new(Vec3::new(1.0, 5.0, -2.0), Vec3::normalize(&Vec3::new(1.0, -1.0, 0.5)));
+    let point = Plane::intersect_ray(&plane, &ray).unwrap();
+    assert!(point.y.abs() < 1e-5);
+    assert!((point.x - 6.0).abs() < 1e-4);
+    assert!((point.z - 0.5).abs() < 1e-4);
+}
+
+#[test]
+fn plane_intersect_ray_parallel_or_behind_is_none() {
+    let plane = Plane::new(0.0, 1.0, 0.0, 0.0);
+    let parallel = Ray::new(Vec3::new(0.0, 1.0, 0.0), Vec3::unit_x());
+    let away     = Ray::new(Vec3::new(0.0, 1.0, 0.0), Vec3::unit_y());
+    assert_eq!(Plane::intersect_ray(&plane, &parallel), None);
+    assert_eq!(Plane::intersect_ray(&plane, &away), None);
+}
+
+#[test]
+fn intersect_triangle_by_reference() {
+    let triangle = Triangle::new(
+        Vec3::new(-1.0, -1.0, 0.0),
+        Vec3::new( 1.0, -1.0, 0.0),
+        Vec3::new( 0.0,  1.0, 0.0),
+    );
+    let ray = Ray::new(Vec3::new(0.0, 0.0, -5.0), Vec3::unit_z());
+    let distance = Ray::intersect_triangle(&ray, &triangle).unwrap();
+    assert!((distance - 5.0).abs() < 1e-5);
+    let miss = Ray::new(Vec3::new(4.0, 0.0, -5.0), Vec3::unit_z());
+    assert_eq!(Ray::intersect_triangle(&miss, &triangle), None);
+}
+
+#[test]
+fn intersect_box_by_reference() {
+    let bounds = BoundingBox::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+    let ray = Ray::new(Vec3::new(0.0, 0.0, -5.0), Vec3::unit_z());
+    assert_eq!(Ray::intersect_box(&ray, &bounds), Some(4.0));
+    let miss = Ray::new(Vec3::new(0.0, 3.0, -5.0), Vec3::unit_z());
+    assert_eq!(Ray::intersect_box(&miss, &bounds), None);
+}
+
+#[test]
+fn intersect_sphere_by_reference() {
+    let sphere = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, -5.0), Vec3::unit_z());
+    assert_eq!(Ray::intersect_sphere(&ray, &sphere), Some(4.0));
+    let inside = Ray::new(Vec3::zero(), Vec3::unit_z());
+    assert_eq!(Ray::intersect_sphere(&inside, &sphere), Some(0.0));
+    let miss = Ray::new(Vec3::new(0.0, 3.0, -5.0), Vec3::unit_z());
+    assert_eq!(Ray::intersect_sphere(&miss, &sphere), None);
+}
+
+#[test]
+fn intersect_sphere_interval_is_symmetric_about_center() {
+    let sphere = Sphere::new(Vec3::new(0.0, 0.0, 10.0), 2.0);
+    let ray = Ray::new(Vec3::zero(), Vec3::unit_z());
+    let (near, far) = Ray::intersect_sphere_interval(&ray, &sphere).unwrap();
+    assert!((near - 8.0).abs() < 1e-5);
+    assert!((far - 12.0).abs() < 1e-5);
+    assert!(((near + far) * 0.5 - 10.0).abs() < 1e-5);
+}
+
+#[test]
+fn intersect_sphere_interval_from_inside_has_negative_near() {
+    let sphere = Sphere::new(Vec3::zero(), 1.0);
+    let ray = Ray::new(Vec3::zero(), Vec3::unit_x());
+    assert_eq!(Ray::intersect_sphere_interval(&ray, &sphere), Some((-1.0, 1.0)));
+}
+
+#[test]
+fn intersect_sphere_interval_miss_or_behind_is_none() {
+    let sphere = Sphere::new(Vec3::new(0.0, 0.0, 10.0), 2.0);
+    let miss   = Ray::new(Vec3::new(0.0, 3.0, 0.0), Vec3::unit_z());
+    let behind = Ray::new(Vec3::zero(), Vec3::new(0.0, 0.0, -1.0));
+    assert_eq!(Ray::intersect_sphere_interval(&miss, &sphere), None);
+    assert_eq!(Ray::intersect_sphere_interval(&behind, &sphere), None);
+}
+
+#[test]
+fn intersect_box_diagonal_ray_entering_through_y_slab() {
+    let bounds = BoundingBox::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+    let ray = Ray::new(Vec3::new(-5.0, -5.0, 0.0), Vec3::new(1.0, 0.9, 0.0));
+    let distance = Ray::intersect_box(&ray, &bounds).unwrap();
+    assert!((distance - 4.0 / 0.9).abs() < 1e-4);
+}
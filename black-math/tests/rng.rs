@@ -0,0 +1,67 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Rng, Vec2, Vec3};
+
+#[test]
+fn rng_reproduces_pcg32_reference_sequence() {
+    let mut rng = Rng::new(42);
+    let expect = [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e];
+    for value in expect.iter() {
+        assert_eq!(rng.next_u32(), *value);
+    }
+}
+
+#[test]
+fn rng_same_seed_same_sequence() {
+    let mut rng0 = Rng::new(7);
+    let mut rng1 = Rng::new(7);
+    for _ in 0..100 {
+        assert_eq!(rng0.next_f32(), rng1.next_f32());
+    }
+}
+
+#[test]
+fn rng_next_f32_is_in_unit_interval() {
+    let mut rng = Rng::new(1);
+    for _ in 0..10000 {
+        let n = rng.next_f32();
+        assert!((0.0..1.0).contains(&n));
+    }
+}
+
+#[test]
+fn rng_unit_disk_and_sphere_samples_are_inside() {
+    let mut rng = Rng::new(3);
+    for _ in 0..10000 {
+        let d = rng.next_in_unit_disk();
+        let s = rng.next_in_unit_sphere();
+        assert!(Vec2::dot(&d, &d) <= 1.0);
+        assert!(Vec3::dot(&s, &s) <= 1.0);
+    }
+}
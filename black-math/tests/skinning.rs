@@ -0,0 +1,66 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::skinning::{skin_normal, skin_position};
+use black_math::{Mat4, Vec3, Vec4};
+
+#[test]
+fn two_bones_with_even_weights_average_positions() {
+    let bones = [
+        Mat4::translation(&Vec3::new(2.0, 0.0, 0.0)),
+        Mat4::rotation_z(1.0) * Mat4::translation(&Vec3::new(0.0, 4.0, 0.0)),
+    ];
+    let bind = Vec4::new(1.0, 2.0, 3.0, 1.0);
+    let skinned = skin_position(&bind, &bones, [0, 1, 0, 0], [0.5, 0.5, 0.0, 0.0]);
+    let expect  = (Vec4::transform(&bind, &bones[0]) + Vec4::transform(&bind, &bones[1])) * 0.5;
+    assert!((skinned.x - expect.x).abs() < 1e-5);
+    assert!((skinned.y - expect.y).abs() < 1e-5);
+    assert!((skinned.z - expect.z).abs() < 1e-5);
+    assert!((skinned.w - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn single_bone_matches_bone_transform() {
+    let bones = [Mat4::identity(), Mat4::rotation_y(0.5)];
+    let bind = Vec4::new(1.0, 0.0, 0.0, 1.0);
+    let skinned = skin_position(&bind, &bones, [1, 7, 7, 7], [1.0, 0.0, 0.0, 0.0]);
+    let expect  = Vec4::transform(&bind, &bones[1]);
+    assert!((skinned.x - expect.x).abs() < 1e-6 && (skinned.z - expect.z).abs() < 1e-6);
+}
+
+#[test]
+fn skinned_normals_use_inverse_transpose() {
+    // the plane x + y = 1 scaled by 2 in x becomes x / 2 + y = 1.
+    let bones  = [Mat4::scale(&Vec3::new(2.0, 1.0, 1.0))];
+    let normal = Vec3::normalize(&Vec3::new(1.0, 1.0, 0.0));
+    let skinned = skin_normal(&normal, &bones, [0, 0, 0, 0], [1.0, 0.0, 0.0, 0.0]);
+    let expect  = Vec3::normalize(&Vec3::new(0.5, 1.0, 0.0));
+    assert!((skinned.x - expect.x).abs() < 1e-5);
+    assert!((skinned.y - expect.y).abs() < 1e-5);
+    assert!(skinned.z.abs() < 1e-5);
+}
@@ -0,0 +1,74 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Triangle, Vec2, Vec3, Winding};
+
+#[test]
+fn signed_area_2d_is_positive_for_counter_clockwise() {
+    let a = Vec2::new(0.0, 0.0);
+    let b = Vec2::new(2.0, 0.0);
+    let c = Vec2::new(0.0, 2.0);
+    assert_eq!(Triangle::signed_area_2d(&a, &b, &c), 2.0);
+    assert_eq!(Triangle::signed_area_2d(&a, &c, &b), -2.0);
+}
+
+#[test]
+fn is_front_facing_counter_clockwise() {
+    let a = Vec2::new(0.0, 0.0);
+    let b = Vec2::new(2.0, 0.0);
+    let c = Vec2::new(0.0, 2.0);
+    assert!(Triangle::is_front_facing(&a, &b, &c, Winding::CounterClockwise));
+    assert!(!Triangle::is_front_facing(&a, &c, &b, Winding::CounterClockwise));
+}
+
+#[test]
+fn is_front_facing_clockwise() {
+    let a = Vec2::new(0.0, 0.0);
+    let b = Vec2::new(2.0, 0.0);
+    let c = Vec2::new(0.0, 2.0);
+    assert!(Triangle::is_front_facing(&a, &c, &b, Winding::Clockwise));
+    assert!(!Triangle::is_front_facing(&a, &b, &c, Winding::Clockwise));
+}
+
+#[test]
+fn degenerate_triangle_is_never_front_facing() {
+    let a = Vec2::new(0.0, 0.0);
+    let b = Vec2::new(1.0, 1.0);
+    let c = Vec2::new(2.0, 2.0);
+    assert!(!Triangle::is_front_facing(&a, &b, &c, Winding::CounterClockwise));
+    assert!(!Triangle::is_front_facing(&a, &b, &c, Winding::Clockwise));
+}
+
+#[test]
+fn face_normal_follows_counter_clockwise_winding() {
+    let a = Vec3::new(0.0, 0.0, 0.0);
+    let b = Vec3::new(2.0, 0.0, 0.0);
+    let c = Vec3::new(0.0, 3.0, 0.0);
+    assert_eq!(Triangle::face_normal(&Triangle::new(a, b, c)), Vec3::unit_z());
+    assert_eq!(Triangle::face_normal(&Triangle::new(a, c, b)), Vec3::new(0.0, 0.0, -1.0));
+}
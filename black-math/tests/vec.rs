@@ -0,0 +1,341 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Vec2, Vec3, Vec4};
+
+#[test]
+fn scalar_mul_is_commutative() {
+    let v2 = Vec2::new(1.0, -2.0);
+    let v3 = Vec3::new(1.0, -2.0, 3.0);
+    let v4 = Vec4::new(1.0, -2.0, 3.0, -4.0);
+    assert_eq!(2.0 * v2, v2 * 2.0);
+    assert_eq!(2.0 * v3, v3 * 2.0);
+    assert_eq!(2.0 * v4, v4 * 2.0);
+    assert_eq!(2.0 * &v2, &v2 * 2.0);
+    assert_eq!(2.0 * &v3, &v3 * 2.0);
+    assert_eq!(2.0 * &v4, &v4 * 2.0);
+}
+
+#[test]
+fn mul_assign_scales_in_place() {
+    let mut v2 = Vec2::new(1.0, -2.0);
+    let mut v3 = Vec3::new(1.0, -2.0, 3.0);
+    let mut v4 = Vec4::new(1.0, -2.0, 3.0, -4.0);
+    v2 *= 2.0;
+    v3 *= 2.0;
+    v4 *= 2.0;
+    assert_eq!(v2, Vec2::new(2.0, -4.0));
+    assert_eq!(v3, Vec3::new(2.0, -4.0, 6.0));
+    assert_eq!(v4, Vec4::new(2.0, -4.0, 6.0, -8.0));
+}
+
+#[test]
+fn slerp_halfway_between_perpendicular_vectors() {
+    let v = Vec3::slerp(&Vec3::unit_x(), &Vec3::unit_y(), 0.5);
+    let n = std::f32::consts::FRAC_1_SQRT_2;
+    assert!((Vec3::length(&v) - 1.0).abs() < 1e-5);
+    assert!((v.x - n).abs() < 1e-5);
+    assert!((v.y - n).abs() < 1e-5);
+    assert!(v.z.abs() < 1e-5);
+}
+
+#[test]
+fn slerp_nearly_parallel_vectors_falls_back_to_lerp() {
+    let v0 = Vec3::unit_x();
+    let v1 = Vec3::normalize(&Vec3::new(1.0, 0.0001, 0.0));
+    assert_eq!(Vec3::slerp(&v0, &v1, 0.5), Vec3::lerp(&v0, &v1, 0.5));
+}
+
+#[test]
+fn slerp_opposite_vectors_rotates_through_perpendicular_axis() {
+    let vectors = [Vec3::unit_x(), Vec3::unit_z(), Vec3::negate(&Vec3::unit_z()), Vec3::normalize(&Vec3::new(1.0, -2.0, 3.0))];
+    for v in vectors.iter() {
+        let opposite = Vec3::negate(v);
+        assert!(Vec3::distance(&Vec3::slerp(v, &opposite, 0.0), v) < 1e-5);
+        assert!(Vec3::distance(&Vec3::slerp(v, &opposite, 1.0), &opposite) < 1e-5);
+        let halfway = Vec3::slerp(v, &opposite, 0.5);
+        assert!((Vec3::length(&halfway) - 1.0).abs() < 1e-5, "{}", halfway);
+        assert!(Vec3::dot(&halfway, v).abs() < 1e-5, "{}", halfway);
+        for n in 0..=8 {
+            let step = Vec3::slerp(v, &opposite, n as f32 / 8.0);
+            assert!((Vec3::length(&step) - 1.0).abs() < 1e-5, "{}", step);
+        }
+    }
+}
+
+#[test]
+fn cross_of_perpendicular_unit_vectors() {
+    assert_eq!(Vec2::cross(&Vec2::unit_x(), &Vec2::unit_y()), 1.0);
+    assert_eq!(Vec2::cross(&Vec2::unit_y(), &Vec2::unit_x()), -1.0);
+    assert_eq!(Vec2::cross(&Vec2::unit_x(), &Vec2::unit_x()), 0.0);
+}
+
+#[test]
+fn perp_rotates_counter_clockwise() {
+    let v = Vec2::new(3.0, 4.0);
+    assert_eq!(Vec2::perp(&v), Vec2::new(-4.0, 3.0));
+    assert_eq!(Vec2::dot(&v, &Vec2::perp(&v)), 0.0);
+    assert_eq!(Vec2::cross(&v, &Vec2::perp(&v)), Vec2::length_sq(&v));
+}
+
+#[test]
+fn rotate_unit_x_by_half_pi() {
+    let v = Vec2::rotate(&Vec2::unit_x(), std::f32::consts::FRAC_PI_2);
+    assert!((v.x - 0.0).abs() < 1e-6);
+    assert!((v.y - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn angle_of_unit_vectors() {
+    assert_eq!(Vec2::angle(&Vec2::unit_x()), 0.0);
+    assert!((Vec2::angle(&Vec2::unit_y()) - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    let v = Vec2::rotate(&Vec2::new(2.0, 0.0), 1.0);
+    assert!((Vec2::angle(&v) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn swizzles() {
+    let v3 = Vec3::new(1.0, 2.0, 3.0);
+    let v4 = Vec4::new(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(v4.xy(), Vec2::new(1.0, 2.0));
+    assert_eq!(v4.xyz(), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(v4.xyz0(), Vec4::new(1.0, 2.0, 3.0, 0.0));
+    assert_eq!(v4.xyz1(), Vec4::new(1.0, 2.0, 3.0, 1.0));
+    assert_eq!(v3.xy(), Vec2::new(1.0, 2.0));
+    assert_eq!(v3.xyz0(), Vec4::new(1.0, 2.0, 3.0, 0.0));
+    assert_eq!(v3.xyz1(), v3.xyzw());
+}
+
+#[test]
+fn with_replaces_a_single_component() {
+    assert_eq!(Vec2::zero().with_y(5.0), Vec2::new(0.0, 5.0));
+    assert_eq!(Vec3::one().with_z(0.0), Vec3::new(1.0, 1.0, 0.0));
+    assert_eq!(Vec4::one().with_x(2.0).with_w(0.0), Vec4::new(2.0, 1.0, 1.0, 0.0));
+}
+
+#[test]
+fn component_reductions() {
+    let v3 = Vec3::new(1.0, 5.0, 2.0);
+    assert_eq!(v3.max_component(), 5.0);
+    assert_eq!(v3.min_component(), 1.0);
+    assert_eq!(v3.sum(), 8.0);
+    assert_eq!(v3.max_axis(), 1);
+    assert_eq!(Vec2::new(3.0, -1.0).max_axis(), 0);
+    assert_eq!(Vec4::new(1.0, 2.0, 3.0, 4.0).max_axis(), 3);
+    assert_eq!(Vec4::new(1.0, 2.0, 3.0, 4.0).sum(), 10.0);
+    assert_eq!(Vec3::all(2.0).max_axis(), 0);
+}
+
+#[test]
+fn bezier_end_points_and_tangent() {
+    let p0 = Vec3::new(0.0, 0.0, 0.0);
+    let p1 = Vec3::new(1.0, 2.0, 0.0);
+    let p2 = Vec3::new(3.0, 2.0, 1.0);
+    let p3 = Vec3::new(4.0, 0.0, 1.0);
+    assert_eq!(Vec3::bezier(&p0, &p1, &p2, &p3, 0.0), p0);
+    assert_eq!(Vec3::bezier(&p0, &p1, &p2, &p3, 1.0), p3);
+    let tangent = Vec3::bezier_tangent(&p0, &p1, &p2, &p3, 0.0);
+    assert_eq!(tangent, Vec3::scale(&Vec3::sub(&p1, &p0), 3.0));
+    let tangent = Vec3::bezier_tangent(&p0, &p1, &p2, &p3, 1.0);
+    assert_eq!(tangent, Vec3::scale(&Vec3::sub(&p3, &p2), 3.0));
+    // a straight, evenly spaced curve is linear in amount.
+    let line = Vec3::bezier(&Vec3::zero(), &Vec3::all(1.0), &Vec3::all(2.0), &Vec3::all(3.0), 0.5);
+    assert_eq!(line, Vec3::all(1.5));
+}
+
+#[test]
+fn bezier_vec2() {
+    let p0 = Vec2::new(0.0, 0.0);
+    let p1 = Vec2::new(0.0, 1.0);
+    let p2 = Vec2::new(1.0, 1.0);
+    let p3 = Vec2::new(1.0, 0.0);
+    assert_eq!(Vec2::bezier(&p0, &p1, &p2, &p3, 0.0), p0);
+    assert_eq!(Vec2::bezier(&p0, &p1, &p2, &p3, 1.0), p3);
+    assert_eq!(Vec2::bezier(&p0, &p1, &p2, &p3, 0.5), Vec2::new(0.5, 0.75));
+    let tangent = Vec2::bezier_tangent(&p0, &p1, &p2, &p3, 0.0);
+    assert_eq!(Vec2::normalize(&tangent), Vec2::new(0.0, 1.0));
+}
+
+#[test]
+fn orthonormal_basis_is_perpendicular_and_unit_length() {
+    let normals = [
+        Vec3::unit_x(),
+        Vec3::unit_y(),
+        Vec3::unit_z(),
+        Vec3::negate(&Vec3::unit_z()),
+        Vec3::normalize(&Vec3::new(0.0, 1e-6, -1.0)),
+        Vec3::normalize(&Vec3::new(1e-4, -1e-4, 1.0)),
+        Vec3::normalize(&Vec3::new(1.0, 2.0, 3.0)),
+        Vec3::normalize(&Vec3::new(-0.3, 0.9, -0.1)),
+    ];
+    for n in normals.iter() {
+        let (t, b) = Vec3::orthonormal_basis(n);
+        assert!(Vec3::dot(&t, n).abs() < 1e-5);
+        assert!(Vec3::dot(&b, n).abs() < 1e-5);
+        assert!(Vec3::dot(&t, &b).abs() < 1e-5);
+        assert!((Vec3::length(&t) - 1.0).abs() < 1e-5);
+        assert!((Vec3::length(&b) - 1.0).abs() < 1e-5);
+        assert!(Vec3::distance(&Vec3::cross(&t, &b), n) < 1e-5);
+    }
+}
+
+#[test]
+fn project_onto_and_reject_from_decompose_vector() {
+    let pairs = [
+        (Vec3::new(1.0, 2.0, 3.0),   Vec3::new(0.0, 0.0, 5.0)),
+        (Vec3::new(-4.0, 0.5, 2.0),  Vec3::new(1.0, 1.0, 0.0)),
+        (Vec3::new(0.3, -0.7, 0.1),  Vec3::new(-2.0, 3.0, 0.5)),
+        (Vec3::new(2.0, 2.0, 2.0),   Vec3::new(1.0, 1.0, 1.0)),
+    ];
+    for (v, onto) in pairs.iter() {
+        let parallel      = Vec3::project_onto(v, onto);
+        let perpendicular = Vec3::reject_from(v, onto);
+        assert!(Vec3::distance(&(parallel + perpendicular), v) < 1e-5);
+        assert!(Vec3::length(&Vec3::cross(&parallel, onto)) < 1e-4);
+        assert!(Vec3::dot(&perpendicular, onto).abs() < 1e-4);
+    }
+    assert_eq!(Vec3::project_onto(&Vec3::new(1.0, 2.0, 3.0), &Vec3::new(0.0, 4.0, 0.0)), Vec3::new(0.0, 2.0, 0.0));
+}
+
+#[test]
+fn project_onto_zero_vector_returns_zero() {
+    let v = Vec3::new(1.0, 2.0, 3.0);
+    assert_eq!(Vec3::project_onto(&v, &Vec3::zero()), Vec3::zero());
+    assert_eq!(Vec3::reject_from(&v, &Vec3::zero()), v);
+}
+
+#[test]
+fn rotate_around_y_axis_through_origin() {
+    let point = Vec3::new(1.0, 2.0, 0.0);
+    let rotated = Vec3::rotate_around(&point, &Vec3::zero(), &Vec3::unit_y(), std::f32::consts::FRAC_PI_2);
+    assert!(Vec3::distance(&rotated, &Vec3::new(0.0, 2.0, -1.0)) < 1e-6);
+}
+
+#[test]
+fn rotate_around_offset_pivot_preserves_distance() {
+    let pivot = Vec3::new(3.0, -1.0, 2.0);
+    let point = Vec3::new(4.0, 0.5, 2.0);
+    let axis  = Vec3::new(0.0, 0.0, 2.0);
+    let rotated = Vec3::rotate_around(&point, &pivot, &axis, std::f32::consts::PI);
+    assert!(Vec3::distance(&rotated, &Vec3::new(2.0, -2.5, 2.0)) < 1e-5);
+    assert!((Vec3::distance(&rotated, &pivot) - Vec3::distance(&point, &pivot)).abs() < 1e-5);
+}
+
+#[test]
+fn try_normalize_rejects_degenerate_vectors() {
+    assert_eq!(Vec3::try_normalize(&Vec3::zero(), 1e-6), None);
+    assert_eq!(Vec3::try_normalize(&Vec3::all(1e-8), 1e-6), None);
+    assert_eq!(Vec3::try_normalize(&Vec3::new(f32::NAN, 0.0, 0.0), 1e-6), None);
+    assert_eq!(Vec3::try_normalize(&Vec3::new(f32::INFINITY, 0.0, 0.0), 1e-6), None);
+    assert_eq!(Vec3::try_normalize(&Vec3::new(0.0, 3.0, 0.0), 1e-6), Some(Vec3::unit_y()));
+    assert_eq!(Vec2::try_normalize(&Vec2::zero(), 1e-6), None);
+    assert_eq!(Vec4::try_normalize(&Vec4::zero(), 1e-6), None);
+}
+
+#[test]
+fn normalize_or_zero_returns_zero_for_zero_vector() {
+    assert_eq!(Vec3::normalize_or_zero(&Vec3::zero()), Vec3::zero());
+    assert_eq!(Vec2::normalize_or_zero(&Vec2::zero()), Vec2::zero());
+    assert_eq!(Vec4::normalize_or_zero(&Vec4::zero()), Vec4::zero());
+    assert_eq!(Vec3::normalize_or_zero(&Vec3::new(0.0, 0.0, -2.0)), Vec3::new(0.0, 0.0, -1.0));
+}
+
+#[test]
+fn sum_matches_manual_fold() {
+    let points = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(-4.0, 0.5, 2.0), Vec3::new(0.25, -1.0, 8.0)];
+    let fold = points.iter().fold(Vec3::zero(), |acc, n| acc + *n);
+    assert_eq!(points.iter().sum::<Vec3>(), fold);
+    assert_eq!(points.iter().copied().sum::<Vec3>(), fold);
+    assert_eq!(Vec::<Vec3>::new().into_iter().sum::<Vec3>(), Vec3::zero());
+
+    let points = [Vec2::new(1.0, 2.0), Vec2::new(-3.0, 4.0)];
+    assert_eq!(points.iter().sum::<Vec2>(), Vec2::new(-2.0, 6.0));
+    let points = [Vec4::new(1.0, 2.0, 3.0, 4.0), Vec4::one()];
+    assert_eq!(points.iter().copied().sum::<Vec4>(), Vec4::new(2.0, 3.0, 4.0, 5.0));
+}
+
+#[test]
+fn product_matches_manual_fold() {
+    let scales = [Vec3::new(2.0, 3.0, 4.0), Vec3::new(0.5, -1.0, 2.0)];
+    let fold = scales.iter().fold(Vec3::one(), |acc, n| acc * *n);
+    assert_eq!(scales.iter().product::<Vec3>(), fold);
+    assert_eq!(scales.iter().copied().product::<Vec3>(), Vec3::new(1.0, -3.0, 8.0));
+    assert_eq!(Vec::<Vec3>::new().into_iter().product::<Vec3>(), Vec3::one());
+    assert_eq!([Vec2::new(2.0, 3.0), Vec2::new(2.0, 2.0)].iter().product::<Vec2>(), Vec2::new(4.0, 6.0));
+    assert_eq!([Vec4::all(2.0), Vec4::all(3.0)].iter().product::<Vec4>(), Vec4::all(6.0));
+}
+
+#[test]
+fn vec3_is_twelve_bytes_and_slice_view_matches_fields() {
+    assert_eq!(std::mem::size_of::<Vec3>(), 12);
+    assert_eq!(std::mem::size_of::<Vec2>(), 8);
+    assert_eq!(std::mem::size_of::<Vec4>(), 16);
+    let v0 = Vec3::new(1.0, -2.0, 3.5);
+    assert_eq!(v0.as_f32_slice(), &[1.0, -2.0, 3.5]);
+    assert_eq!(Vec2::new(4.0, 5.0).as_f32_slice(), &[4.0, 5.0]);
+    assert_eq!(Vec4::new(1.0, 2.0, 3.0, 4.0).as_f32_slice(), &[1.0, 2.0, 3.0, 4.0]);
+    let points = [v0, Vec3::new(7.0, 8.0, 9.0)];
+    assert_eq!(Vec3::slice_as_f32(&points), &[1.0, -2.0, 3.5, 7.0, 8.0, 9.0]);
+}
+
+#[test]
+fn tone_mapping_maps_zero_to_zero() {
+    assert_eq!(Vec3::reinhard(&Vec3::zero()), Vec3::zero());
+    assert_eq!(Vec3::aces_approx(&Vec3::zero()), Vec3::zero());
+    assert_eq!(Vec3::reinhard(&Vec3::all(-2.0)), Vec3::zero());
+    assert_eq!(Vec3::aces_approx(&Vec3::all(-2.0)), Vec3::zero());
+}
+
+#[test]
+fn tone_mapping_is_monotonic() {
+    let mut reinhard = 0.0;
+    let mut aces     = 0.0;
+    for n in 1..=1000 {
+        let value = Vec3::all(n as f32 * 0.02);
+        let next_reinhard = Vec3::reinhard(&value).x;
+        let next_aces     = Vec3::aces_approx(&value).x;
+        assert!(next_reinhard > reinhard, "{}", value);
+        assert!(next_aces >= aces, "{}", value);
+        reinhard = next_reinhard;
+        aces     = next_aces;
+    }
+}
+
+#[test]
+fn tone_mapping_stays_within_unit_range() {
+    let bright = Vec3::new(10.0, 100.0, 1000.0);
+    let reinhard = Vec3::reinhard(&bright);
+    assert!(reinhard.x < 1.0 && reinhard.y < 1.0 && reinhard.z < 1.0);
+    assert!(reinhard.z > 0.99);
+    let aces = Vec3::aces_approx(&bright);
+    assert!(aces.x <= 1.0 && aces.y <= 1.0 && aces.z <= 1.0);
+    assert!(aces.x > 0.99);
+    // the components stay ordered.
+    let ordered = Vec3::aces_approx(&Vec3::new(0.25, 0.5, 1.0));
+    assert!(ordered.x < ordered.y && ordered.y < ordered.z && ordered.z < 1.0);
+}
@@ -0,0 +1,137 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use super::DepthBuffer;
+use super::DepthConfig;
+
+/// AtomicDepthBuffer
+///
+/// A row major depth buffer that may be tested and written from many
+/// threads at once. Each cell stores the bits of an f32 in an AtomicU32
+/// and test_and_set() updates it with a compare and swap loop, so
+/// fragments racing on the same pixel resolve to the same depth as a
+/// serial draw regardless of order. The loop compares floats with the
+/// config's depth func rather than the raw bits, so negative depths and
+/// reverse-Z configs behave as with DepthBuffer.
+pub struct AtomicDepthBuffer {
+    /// The clear value and depth test used with this buffer.
+    pub config: DepthConfig,
+    data:       Vec<AtomicU32>,
+    width:      usize,
+    height:     usize,
+}
+impl AtomicDepthBuffer {
+    /// Creates a depth buffer cleared to the default config's clear value.
+    pub fn new(width: usize, height: usize) -> AtomicDepthBuffer {
+        let config = DepthConfig::default();
+        let data   = (0..width * height).map(|_| AtomicU32::new(config.clear_value.to_bits())).collect();
+        AtomicDepthBuffer { config, data, width, height }
+    }
+
+    /// Returns this buffer using the given depth config. The buffer is
+    /// cleared to the config's clear value.
+    pub fn with_config(mut self, config: DepthConfig) -> AtomicDepthBuffer {
+        self.config = config;
+        self.clear();
+        self
+    }
+
+    #[inline(always)]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline(always)]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline(always)]
+    pub fn get(&self, x: usize, y: usize) -> f32 {
+        f32::from_bits(self.data[x + y * self.width].load(Ordering::Relaxed))
+    }
+
+    /// Stores value at x, y without testing against the stored depth.
+    #[inline(always)]
+    pub fn set(&self, x: usize, y: usize, value: f32) {
+        self.data[x + y * self.width].store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Returns true if a fragment at depth passes the depth test at x, y.
+    /// The result may be stale by the time it is acted on, use
+    /// test_and_set() to write.
+    #[inline(always)]
+    pub fn test(&self, x: usize, y: usize, depth: f32) -> bool {
+        self.config.func.test(depth, self.get(x, y))
+    }
+
+    /// Writes depth at x, y if it passes the depth test against the stored
+    /// depth, retrying if another thread wrote the pixel in between.
+    /// Returns true if depth was written.
+    #[inline(always)]
+    pub fn test_and_set(&self, x: usize, y: usize, depth: f32) -> bool {
+        let cell   = &self.data[x + y * self.width];
+        let mut n0 = cell.load(Ordering::Relaxed);
+        loop {
+            if !self.config.func.test(depth, f32::from_bits(n0)) {
+                return false;
+            }
+            match cell.compare_exchange_weak(n0, depth.to_bits(), Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_)       => return true,
+                Err(stored) => n0 = stored,
+            }
+        }
+    }
+
+    /// Clears the buffer to the clear value of its config.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.clear_to(self.config.clear_value)
+    }
+
+    #[inline(always)]
+    pub fn clear_to(&mut self, value: f32) {
+        use rayon::prelude::*;
+        let bits = value.to_bits();
+        self.data.par_iter_mut().for_each(|n| *n.get_mut() = bits);
+    }
+
+    /// Copies the stored depths into a new row major DepthBuffer with the
+    /// same config.
+    pub fn to_depth_buffer(&self) -> DepthBuffer {
+        let mut depth = DepthBuffer::new(self.width, self.height).with_config(self.config);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                depth.set(x, y, self.get(x, y));
+            }
+        }
+        depth
+    }
+}
@@ -0,0 +1,115 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+/// The number of elements filled by each rayon task when clearing.
+#[cfg(feature = "rayon")]
+const CLEAR_CHUNK: usize = 64 * 1024;
+
+/// Fills data with value. With the rayon feature the slice is split into
+/// chunks of CLEAR_CHUNK elements which are filled in parallel.
+#[inline(always)]
+pub(crate) fn clear_slice<T: Copy + Send + Sync>(data: &mut [T], value: T) {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        data.par_chunks_mut(CLEAR_CHUNK).for_each(|chunk| chunk.fill(value));
+    }
+    #[cfg(not(feature = "rayon"))]
+    data.fill(value);
+}
+
+/// Buffer2D
+/// 
+/// A generic 2D grid of values stored in row major order. Used as the
+/// storage for depth buffers, and may be used for color or stencil
+/// buffers.
+#[derive(Debug, Clone)]
+pub struct Buffer2D<T> {
+    pub data:   Vec<T>,
+    pub width:  usize,
+    pub height: usize
+}
+impl<T: Copy + Default> Buffer2D<T> {
+    pub fn new(width: usize, height: usize) -> Buffer2D<T> {
+        let data = vec![T::default(); width * height];
+        Buffer2D { width, height, data }
+    }
+}
+impl<T: Copy + Send + Sync> Buffer2D<T> {
+    /// Fills the buffer with value. With the rayon feature the buffer is
+    /// filled in parallel, see clear_slice.
+    #[inline(always)]
+    pub fn clear(&mut self, value: T) {
+        clear_slice(&mut self.data, value);
+    }
+}
+impl<T: Copy> Buffer2D<T> {
+
+    /// Fills the given region with value. The region is clipped to the buffer.
+    #[inline(always)]
+    pub fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, value: T) {
+        let max_x = usize::min(x.saturating_add(width),  self.width);
+        let max_y = usize::min(y.saturating_add(height), self.height);
+        if x >= max_x {
+            return;
+        }
+        for row in y..max_y {
+            let offset = row * self.width;
+            self.data[offset + x..offset + max_x].fill(value);
+        }
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        self.data[x + y * self.width] = value;
+    }
+
+    #[inline(always)]
+    pub fn get(&self, x: usize, y: usize) -> T {
+        self.data[x + y * self.width]
+    }
+
+    /// Sets the value at x, y, returning None if out of bounds.
+    #[inline(always)]
+    pub fn try_set(&mut self, x: usize, y: usize, value: T) -> Option<()> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.set(x, y, value);
+        Some(())
+    }
+
+    /// Gets the value at x, y, returning None if out of bounds.
+    #[inline(always)]
+    pub fn try_get(&self, x: usize, y: usize) -> Option<T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.get(x, y))
+    }
+}
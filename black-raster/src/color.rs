@@ -0,0 +1,97 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::Vec4;
+
+/// Color
+///
+/// An RGBA color with components in the range 0.0 to 1.0. Converts to and
+/// from the Vec4 colors used by fragment programs and target buffers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+impl Color {
+    #[inline(always)]
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Color {
+        Color { r, g, b, a }
+    }
+    #[inline(always)]
+    pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color::rgba(r, g, b, 255)
+    }
+    #[inline(always)]
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color::new(
+            r as f32 / 255.0,
+            g as f32 / 255.0,
+            b as f32 / 255.0,
+            a as f32 / 255.0,
+        )
+    }
+    /// Creates an opaque color from a 0xRRGGBB value.
+    #[inline(always)]
+    pub fn from_hex(hex: u32) -> Color {
+        Color::rgb(
+            ((hex >> 16) & 0xFF) as u8,
+            ((hex >> 8)  & 0xFF) as u8,
+            (hex         & 0xFF) as u8,
+        )
+    }
+    #[inline(always)]
+    pub fn from_vec4(v: &Vec4) -> Color {
+        Color::new(v.x, v.y, v.z, v.w)
+    }
+    #[inline(always)]
+    pub fn to_vec4(&self) -> Vec4 {
+        Vec4::new(self.r, self.g, self.b, self.a)
+    }
+    /// Packs this color as 0xAARRGGBB, clamping each component to the
+    /// range 0.0 to 1.0.
+    #[inline(always)]
+    pub fn to_u32(&self) -> u32 {
+        let r = (self.r.clamp(0.0, 1.0) * 255.0) as u32;
+        let g = (self.g.clamp(0.0, 1.0) * 255.0) as u32;
+        let b = (self.b.clamp(0.0, 1.0) * 255.0) as u32;
+        let a = (self.a.clamp(0.0, 1.0) * 255.0) as u32;
+        (a << 24) | (r << 16) | (g << 8) | b
+    }
+}
+impl From<Vec4> for Color {
+    fn from(v: Vec4) -> Color {
+        Color::from_vec4(&v)
+    }
+}
+impl From<Color> for Vec4 {
+    fn from(c: Color) -> Vec4 {
+        c.to_vec4()
+    }
+}
@@ -0,0 +1,88 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Vec2, Vec3, Vec4};
+
+use super::Sampler;
+use super::Texture2D;
+use super::WrapMode;
+
+/// Cubemap
+///
+/// Six textures forming the faces of a cube, sampled by direction. Faces
+/// are ordered +X, -X, +Y, -Y, +Z, -Z and oriented as OpenGL cubemaps, with
+/// v increasing downwards on each face. Faces are clamped at their edges
+/// and filtering does not cross between faces.
+#[derive(Debug, Clone)]
+pub struct Cubemap {
+    pub faces: [Texture2D; 6],
+}
+impl Cubemap {
+    /// Creates a cubemap from faces ordered +X, -X, +Y, -Y, +Z, -Z. The wrap
+    /// mode of each face is set to clamp.
+    pub fn new(mut faces: [Texture2D; 6]) -> Cubemap {
+        for face in faces.iter_mut() {
+            face.wrap = WrapMode::Clamp;
+        }
+        Cubemap { faces }
+    }
+
+    /// Returns the face index and face local uv for the given direction. The
+    /// face is chosen by the direction's dominant axis. The direction need
+    /// not be unit length but must not be zero.
+    #[inline(always)]
+    pub fn face_uv(direction: &Vec3) -> (usize, Vec2) {
+        let abs = Vec3::abs(direction);
+        let (face, major, s, t) = if abs.x >= abs.y && abs.x >= abs.z {
+            if direction.x >= 0.0 {
+                (0, abs.x, -direction.z, -direction.y)
+            } else {
+                (1, abs.x, direction.z, -direction.y)
+            }
+        } else if abs.y >= abs.z {
+            if direction.y >= 0.0 {
+                (2, abs.y, direction.x, direction.z)
+            } else {
+                (3, abs.y, direction.x, -direction.z)
+            }
+        } else if direction.z >= 0.0 {
+            (4, abs.z, direction.x, -direction.y)
+        } else {
+            (5, abs.z, -direction.x, -direction.y)
+        };
+        (face, Vec2::new(((s / major) + 1.0) * 0.5, ((t / major) + 1.0) * 0.5))
+    }
+
+    /// Samples the cubemap along the given direction with bilinear filtering.
+    /// For reflections pass Vec3::reflect(&view, &normal).
+    #[inline(always)]
+    pub fn sample(&self, direction: &Vec3) -> Vec4 {
+        let (face, uv) = Self::face_uv(direction);
+        self.faces[face].sample(&uv)
+    }
+}
@@ -26,30 +26,240 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
+use super::Buffer2D;
+use super::Rect;
+
+/// The width and height in pixels of the tiles used by DepthBuffer::new_tiled.
+const TILE_SIZE: usize = 8;
+
+/// DepthFunc
+///
+/// The comparison used by the depth test. A fragment passes if
+/// test(fragment_depth, stored_depth) returns true.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthFunc {
+    Never,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Always,
+}
+impl DepthFunc {
+    /// Returns true if a fragment at depth passes against the stored depth.
+    #[inline(always)]
+    pub fn test(&self, depth: f32, stored: f32) -> bool {
+        match self {
+            DepthFunc::Never        => false,
+            DepthFunc::Less         => depth <  stored,
+            DepthFunc::LessEqual    => depth <= stored,
+            DepthFunc::Greater      => depth >  stored,
+            DepthFunc::GreaterEqual => depth >= stored,
+            DepthFunc::Always       => true,
+        }
+    }
+}
+
+/// DepthConfig
+///
+/// The depth convention of a DepthBuffer. The clear value is written by
+/// DepthBuffer::clear() and func is used by the rasterizer to test
+/// fragments against the buffer, so both always agree. The default
+/// clears to f32::MAX and passes nearer (smaller) depths. The depth
+/// written is the normalized device z (z / w).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthConfig {
+    pub clear_value: f32,
+    pub func:        DepthFunc,
+}
+impl DepthConfig {
+    /// Returns a reverse-Z config for projections mapping the near plane
+    /// to 1.0 and the far plane to 0.0. Clears to 0.0 and passes greater
+    /// depths.
+    pub fn reverse_z() -> DepthConfig {
+        DepthConfig { clear_value: 0.0, func: DepthFunc::Greater }
+    }
+}
+impl Default for DepthConfig {
+    fn default() -> DepthConfig {
+        DepthConfig { clear_value: f32::MAX, func: DepthFunc::Less }
+    }
+}
+
+/// DepthBuffer
+///
+/// Stores one depth value per pixel. Buffers created with new() are row
+/// major. Buffers created with new_tiled() store 8x8 pixel tiles
+/// contiguously, so the rows of a triangle touch fewer cache lines. The
+/// storage is private as its layout depends on the buffer, use get() and
+/// set() to address pixels in either layout, and width() and height()
+/// in place of the former public fields.
 pub struct DepthBuffer {
-    pub data:   Vec<f32>,
-    pub width:  usize,
-    pub height: usize
+    /// The clear value and depth test used with this buffer.
+    pub config: DepthConfig,
+    buffer:     Buffer2D<f32>,
+    tiles_x:    usize,
+    tiled:      bool,
 }
 impl DepthBuffer {
+    /// Creates a row major depth buffer filled with the default clear value.
     pub fn new(width: usize, height: usize) -> DepthBuffer {
-        let data = vec![0.0; width * height];
-        DepthBuffer { width, height, data }
+        let config = DepthConfig::default();
+        let data   = vec![config.clear_value; width * height];
+        DepthBuffer { config, buffer: Buffer2D { data, width, height }, tiles_x: 0, tiled: false }
     }
-    
+
+    /// Creates a depth buffer stored as 8x8 pixel tiles, filled with the
+    /// default clear value.
+    pub fn new_tiled(width: usize, height: usize) -> DepthBuffer {
+        let config  = DepthConfig::default();
+        let tiles_x = width.div_ceil(TILE_SIZE);
+        let tiles_y = height.div_ceil(TILE_SIZE);
+        let data    = vec![config.clear_value; tiles_x * tiles_y * TILE_SIZE * TILE_SIZE];
+        DepthBuffer { config, buffer: Buffer2D { data, width, height }, tiles_x, tiled: true }
+    }
+
+    /// Returns this buffer using the given depth config. The buffer is
+    /// cleared to the config's clear value.
+    pub fn with_config(mut self, config: DepthConfig) -> DepthBuffer {
+        self.config = config;
+        self.clear();
+        self
+    }
+
+    /// Returns true if a fragment at depth passes the depth test at x, y.
     #[inline(always)]
-    pub fn clear(&mut self) {
-        for n in 0..self.data.len() {
-            self.data[n] = std::f32::MAX;
+    pub fn test(&self, x: usize, y: usize, depth: f32) -> bool {
+        self.config.func.test(depth, self.get(x, y))
+    }
+
+    /// Returns true if this buffer is stored as tiles.
+    #[inline(always)]
+    pub fn is_tiled(&self) -> bool {
+        self.tiled
+    }
+
+    /// Returns the width of this buffer in pixels.
+    #[inline(always)]
+    pub fn width(&self) -> usize {
+        self.buffer.width
+    }
+
+    /// Returns the height of this buffer in pixels.
+    #[inline(always)]
+    pub fn height(&self) -> usize {
+        self.buffer.height
+    }
+
+    /// Returns the offset into data for the pixel at x, y.
+    #[inline(always)]
+    fn offset(&self, x: usize, y: usize) -> usize {
+        if self.tiled {
+            let tile = (y / TILE_SIZE) * self.tiles_x + (x / TILE_SIZE);
+            (tile * TILE_SIZE * TILE_SIZE) + ((y % TILE_SIZE) * TILE_SIZE) + (x % TILE_SIZE)
+        } else {
+            x + y * self.buffer.width
+        }
+    }
+
+    #[inline(always)]
+    pub fn get(&self, x: usize, y: usize) -> f32 {
+        self.buffer.data[self.offset(x, y)]
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, x: usize, y: usize, value: f32) {
+        let offset = self.offset(x, y);
+        self.buffer.data[offset] = value;
+    }
+
+    /// Gets the value at x, y, returning None if out of bounds.
+    #[inline(always)]
+    pub fn try_get(&self, x: usize, y: usize) -> Option<f32> {
+        if x >= self.buffer.width || y >= self.buffer.height {
+            return None;
         }
+        Some(self.get(x, y))
     }
+
+    /// Sets the value at x, y, returning None if out of bounds.
     #[inline(always)]
-    pub fn set(&mut self, x: usize, y: usize, z: f32) {
-        self.data[x + y * self.width] = z;
+    pub fn try_set(&mut self, x: usize, y: usize, value: f32) -> Option<()> {
+        if x >= self.buffer.width || y >= self.buffer.height {
+            return None;
+        }
+        self.set(x, y, value);
+        Some(())
+    }
+
+    /// Fills the given region with value. The region is clipped to the buffer.
+    #[inline(always)]
+    pub fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, value: f32) {
+        if !self.tiled {
+            return self.buffer.fill(x, y, width, height, value);
+        }
+        let max_x = usize::min(x.saturating_add(width),  self.buffer.width);
+        let max_y = usize::min(y.saturating_add(height), self.buffer.height);
+        for row in y..max_y {
+            for column in x..max_x {
+                self.set(column, row, value);
+            }
+        }
     }
     
+    /// Copies the depth values of other into this buffer. The config of
+    /// this buffer is unchanged. Buffers may differ in layout, tiled or
+    /// untiled, but must have the same dimensions.
+    ///
+    /// Panics if the dimensions differ.
+    pub fn copy_from(&mut self, other: &DepthBuffer) {
+        assert!(
+            self.width() == other.width() && self.height() == other.height(),
+            "cannot copy a {}x{} depth buffer into a {}x{} depth buffer",
+            other.width(),
+            other.height(),
+            self.width(),
+            self.height()
+        );
+        if self.tiled == other.tiled {
+            self.buffer.data.copy_from_slice(&other.buffer.data);
+            return;
+        }
+        self.blit_region(other, &Rect::from_size(other.width() as i32, other.height() as i32), 0, 0);
+    }
+
+    /// Copies the src_rect region of src into this buffer with its top left
+    /// corner at dst_x, dst_y. The region is clipped to both buffers.
+    pub fn blit_region(&mut self, src: &DepthBuffer, src_rect: &Rect, dst_x: i32, dst_y: i32) {
+        // clip the source rect to src, then shift into the destination and clip to self.
+        let clipped = src_rect.intersect(&Rect::from_size(src.width() as i32, src.height() as i32));
+        let shifted = Rect::new(
+            clipped.x + (dst_x - src_rect.x),
+            clipped.y + (dst_y - src_rect.y),
+            clipped.width,
+            clipped.height,
+        );
+        let target = shifted.intersect(&Rect::from_size(self.width() as i32, self.height() as i32));
+        let offset_x = src_rect.x - dst_x;
+        let offset_y = src_rect.y - dst_y;
+        for y in target.y..target.bottom() {
+            for x in target.x..target.right() {
+                let value = src.get((x + offset_x) as usize, (y + offset_y) as usize);
+                self.set(x as usize, y as usize, value);
+            }
+        }
+    }
+
+    /// Clears the buffer to the clear value of its config.
     #[inline(always)]
-    pub fn get(&self, x: usize, y: usize) -> f32 {
-        self.data[x + y * self.width]
+    pub fn clear(&mut self) {
+        self.clear_to(self.config.clear_value)
     }
-}
\ No newline at end of file
+
+    /// Clears every pixel, and the padding of tiled buffers, to value. With
+    /// the rayon feature the buffer is cleared in parallel chunks.
+    #[inline(always)]
+    pub fn clear_to(&mut self, value: f32) {
+        self.buffer.clear(value);
+    }
+}
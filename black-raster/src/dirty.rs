@@ -0,0 +1,101 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::Rect;
+
+/// DirtyRegion
+/// 
+/// Tracks the bounding rectangle of pixels written to a target. Targets
+/// may embed this type and call add() from TargetBuffer::set() to allow
+/// presenting only the region that has changed.
+#[derive(Debug, Clone, Copy)]
+pub struct DirtyRegion {
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+}
+impl DirtyRegion {
+    pub fn new() -> DirtyRegion {
+        DirtyRegion {
+            min_x: i32::MAX,
+            min_y: i32::MAX,
+            max_x: i32::MIN,
+            max_y: i32::MIN,
+        }
+    }
+
+    /// Marks the given pixel as dirty.
+    #[inline(always)]
+    pub fn add(&mut self, x: i32, y: i32) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+
+    /// Marks the given rectangle as dirty.
+    #[inline(always)]
+    pub fn add_rect(&mut self, rect: &Rect) {
+        if !rect.is_empty() {
+            self.add(rect.x, rect.y);
+            self.add(rect.right() - 1, rect.bottom() - 1);
+        }
+    }
+
+    /// Returns true if no pixels have been marked dirty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.min_x > self.max_x || self.min_y > self.max_y
+    }
+
+    /// Returns the dirty rectangle, or None if empty.
+    #[inline(always)]
+    pub fn bounds(&self) -> Option<Rect> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(Rect::new(
+            self.min_x,
+            self.min_y,
+            (self.max_x - self.min_x) + 1,
+            (self.max_y - self.min_y) + 1,
+        ))
+    }
+
+    /// Resets this region to empty.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        *self = DirtyRegion::new();
+    }
+}
+impl Default for DirtyRegion {
+    fn default() -> Self {
+        DirtyRegion::new()
+    }
+}
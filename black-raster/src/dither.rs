@@ -0,0 +1,61 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+/// The 4x4 Bayer matrix, each value is the order in which the pixel is
+/// drawn as alpha increases.
+const BAYER_4X4: [[u8; 4]; 4] = [
+    [ 0,  8,  2, 10],
+    [12,  4, 14,  6],
+    [ 3, 11,  1,  9],
+    [15,  7, 13,  5],
+];
+
+/// Dither
+///
+/// Ordered dithering helpers for fake transparency on opaque targets,
+/// known as screen-door transparency. A fragment is either drawn or
+/// discarded based on its alpha and a 4x4 Bayer threshold at its pixel,
+/// so over any 4x4 block the fraction of pixels drawn matches alpha to
+/// the nearest 1/16. The pattern is fixed to the screen, so results are
+/// deterministic.
+pub struct Dither;
+impl Dither {
+    /// Returns the Bayer threshold at pixel x, y in the range (0.0, 1.0).
+    #[inline(always)]
+    pub fn threshold(x: i32, y: i32) -> f32 {
+        let value = BAYER_4X4[(y & 3) as usize][(x & 3) as usize];
+        (value as f32 + 0.5) / 16.0
+    }
+
+    /// Returns true if a fragment with the given alpha should be drawn at
+    /// pixel x, y. Alpha of 0.0 is never drawn and 1.0 is always drawn.
+    #[inline(always)]
+    pub fn screen_door(alpha: f32, x: i32, y: i32) -> bool {
+        alpha > Dither::threshold(x, y)
+    }
+}
@@ -0,0 +1,142 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Vec2, Vec4};
+use std::cmp::{max, min};
+
+use super::font::{glyph, GLYPH_SIZE};
+use super::TargetBuffer;
+
+/// Draw2D
+///
+/// Immediate mode 2D drawing in screen space for overlays and debugging.
+/// Shapes are written directly to the target with a solid color, without
+/// depth testing or shaders, and are clipped to the target.
+pub struct Draw2D;
+impl Draw2D {
+    /// Fills the rectangle with its top left corner at x, y.
+    pub fn rect<TTargetBuffer>(target: &mut TTargetBuffer, x: i32, y: i32, width: i32, height: i32, color: Vec4) where
+        TTargetBuffer: TargetBuffer,
+    {
+        let min_x = max(x, 0);
+        let min_y = max(y, 0);
+        let max_x = min(x.saturating_add(width),  target.width());
+        let max_y = min(y.saturating_add(height), target.height());
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                target.set(x, y, color);
+            }
+        }
+    }
+
+    /// Draws a one pixel wide line between the pixels containing p0 and p1,
+    /// inclusive of both end points.
+    pub fn line<TTargetBuffer>(target: &mut TTargetBuffer, p0: &Vec2, p1: &Vec2, color: Vec4) where
+        TTargetBuffer: TargetBuffer,
+    {
+        let (width, height) = (target.width(), target.height());
+        let mut x = p0.x.floor() as i32;
+        let mut y = p0.y.floor() as i32;
+        let x1 = p1.x.floor() as i32;
+        let y1 = p1.y.floor() as i32;
+        let dx =  (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        loop {
+            if x >= 0 && y >= 0 && x < width && y < height {
+                target.set(x, y, color);
+            }
+            if x == x1 && y == y1 {
+                break;
+            }
+            let n0 = error * 2;
+            if n0 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if n0 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Fills the circle, covering each pixel whose center lies within
+    /// radius of center.
+    pub fn circle<TTargetBuffer>(target: &mut TTargetBuffer, center: &Vec2, radius: f32, color: Vec4) where
+        TTargetBuffer: TargetBuffer,
+    {
+        let min_x = max((center.x - radius).floor() as i32, 0);
+        let min_y = max((center.y - radius).floor() as i32, 0);
+        let max_x = min((center.x + radius).ceil() as i32, target.width());
+        let max_y = min((center.y + radius).ceil() as i32, target.height());
+        let radius_sq = radius * radius;
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let n0 = (x as f32 + 0.5) - center.x;
+                let n1 = (y as f32 + 0.5) - center.y;
+                if (n0 * n0) + (n1 * n1) <= radius_sq {
+                    target.set(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Draws text with its top left corner at x, y using the built in 8x8
+    /// bitmap font. Each character advances 8 pixels and newlines return to
+    /// x on the next line. Characters outside printable ASCII draw as '?'.
+    pub fn text<TTargetBuffer>(target: &mut TTargetBuffer, x: i32, y: i32, text: &str, color: Vec4) where
+        TTargetBuffer: TargetBuffer,
+    {
+        let (width, height) = (target.width(), target.height());
+        let mut cursor_x = x;
+        let mut cursor_y = y;
+        for c in text.chars() {
+            if c == '\n' {
+                cursor_x = x;
+                cursor_y += GLYPH_SIZE;
+                continue;
+            }
+            for (row, bits) in glyph(c).iter().enumerate() {
+                let py = cursor_y + row as i32;
+                if py < 0 || py >= height {
+                    continue;
+                }
+                for column in 0..GLYPH_SIZE {
+                    let px = cursor_x + column;
+                    if bits & (1 << column) != 0 && px >= 0 && px < width {
+                        target.set(px, py, color);
+                    }
+                }
+            }
+            cursor_x += GLYPH_SIZE;
+        }
+    }
+}
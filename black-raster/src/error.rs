@@ -0,0 +1,78 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// RasterError
+///
+/// Errors returned by the fallible Raster::try_* entry points, which check
+/// a draw before rasterizing it rather than panicking on an out of bounds
+/// index or silently producing wrong output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RasterError {
+    /// The depth buffer and target have different dimensions.
+    SizeMismatch {
+        depth_width:   usize,
+        depth_height:  usize,
+        target_width:  i32,
+        target_height: i32,
+    },
+    /// The target has zero (or negative) width or height.
+    EmptyViewport {
+        width:  i32,
+        height: i32,
+    },
+    /// The index count is not a multiple of three.
+    IndexCount(usize),
+    /// An index refers past the end of the vertex array.
+    IndexOutOfRange {
+        offset: usize,
+        index:  u32,
+        vertex_count: usize,
+    },
+}
+impl Display for RasterError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            RasterError::SizeMismatch { depth_width, depth_height, target_width, target_height } => write!(
+                f,
+                "depth buffer is {}x{} but target is {}x{}",
+                depth_width, depth_height, target_width, target_height
+            ),
+            RasterError::EmptyViewport { width, height } => write!(f, "viewport {}x{} is empty", width, height),
+            RasterError::IndexCount(count) => write!(f, "index count {} is not a multiple of three", count),
+            RasterError::IndexOutOfRange { offset, index, vertex_count } => write!(
+                f,
+                "index {} at offset {} is out of range for {} vertices",
+                index, offset, vertex_count
+            ),
+        }
+    }
+}
+impl Error for RasterError {}
@@ -28,8 +28,95 @@ THE SOFTWARE.
 
 use black_math::{ Vec4 };
 
+use super::Interpolate;
+
+/// FragmentProgram
+/// 
+/// Computes the color for a fragment. Returning None discards the
+/// fragment, in which case neither the target nor depth are written.
 pub trait FragmentProgram {
     type Uniform;
     type Varying;
-    fn main(&self, uniform: &Self::Uniform, varying: &Self::Varying) -> Vec4;
+    fn main(&self, uniform: &Self::Uniform, varying: &Self::Varying) -> Option<Vec4>;
+}
+
+/// FragmentProgramMRT
+/// 
+/// A fragment program that writes N outputs per fragment, with each
+/// output written to the corresponding target in Raster::triangle_mrt.
+pub trait FragmentProgramMRT<const N: usize> {
+    type Uniform;
+    type Varying;
+    fn main(&self, uniform: &Self::Uniform, varying: &Self::Varying) -> [Vec4; N];
+}
+
+/// QuadFragmentProgram
+/// 
+/// A fragment program that may query screen space derivatives of its
+/// varyings through the given Quad. Used with Raster::triangle_quad.
+pub trait QuadFragmentProgram {
+    type Uniform;
+    type Varying;
+    fn main(&self, uniform: &Self::Uniform, varying: &Self::Varying, quad: &Quad<Self::Varying>) -> Option<Vec4>;
+}
+
+/// Quad
+/// 
+/// The 2x2 pixel quad a fragment is shaded in. Derivatives are the
+/// difference between the varyings of the fragment's row or column
+/// within the quad. Neighbours are evaluated from the triangle's
+/// barycentric plane equations, so neighbours that fall outside the
+/// triangle are extrapolated in the same way as helper pixels on a GPU.
+pub struct Quad<'a, TVarying> {
+    pub(crate) varyings:    [&'a TVarying; 3],
+    pub(crate) reciprocal_w: [f32; 3],
+    pub(crate) weights:      [f32; 3],
+    pub(crate) step_x:       [f32; 3],
+    pub(crate) step_y:       [f32; 3],
+    pub(crate) x:            i32,
+    pub(crate) y:            i32,
+}
+impl<'a, TVarying: Interpolate> Quad<'a, TVarying> {
+    /// Returns the rate of change of the varying between the left and right
+    /// pixels of this fragment's quad row.
+    pub fn ddx(&self) -> TVarying {
+        let offset = (self.x & !1) - self.x;
+        self.difference((offset as f32, 0.0), (offset as f32 + 1.0, 0.0))
+    }
+
+    /// Returns the rate of change of the varying between the top and bottom
+    /// pixels of this fragment's quad column. Screen y increases downwards.
+    pub fn ddy(&self) -> TVarying {
+        let offset = (self.y & !1) - self.y;
+        self.difference((0.0, offset as f32), (0.0, offset as f32 + 1.0))
+    }
+
+    /// Returns the varying at pixel offset p1 minus the varying at offset p0.
+    /// Interpolation is linear in the varyings, so the difference is itself
+    /// an interpolation with differenced perspective corrected weights.
+    #[inline(always)]
+    fn difference(&self, p0: (f32, f32), p1: (f32, f32)) -> TVarying {
+        let n0 = self.weights_at(p0);
+        let n1 = self.weights_at(p1);
+        TVarying::interpolate(
+            self.varyings[0],
+            self.varyings[1],
+            self.varyings[2],
+            &(n1[0] - n0[0]),
+            &(n1[1] - n0[1]),
+            &(n1[2] - n0[2]),
+            &1.0,
+        )
+    }
+
+    /// Returns the perspective corrected weights at the given pixel offset.
+    #[inline(always)]
+    fn weights_at(&self, offset: (f32, f32)) -> [f32; 3] {
+        let (dx, dy) = offset;
+        let w0 = self.weights[0] + self.step_x[0] * dx + self.step_y[0] * dy;
+        let w1 = self.weights[1] + self.step_x[1] * dx + self.step_y[1] * dy;
+        let w2 = self.weights[2] + self.step_x[2] * dx + self.step_y[2] * dy;
+        let w  = (w0 * self.reciprocal_w[0]) + (w1 * self.reciprocal_w[1]) + (w2 * self.reciprocal_w[2]);
+        [w0 / w, w1 / w, w2 / w]
+    }
 }
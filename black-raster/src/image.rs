@@ -0,0 +1,101 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::Vec4;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use super::buffer::clear_slice;
+use super::Color;
+use super::TargetBuffer;
+
+/// ImageBuffer
+///
+/// An in memory color target. Useful for rendering without a window, such
+/// as tests and offline renders, and can be written out as a PPM image.
+#[derive(Debug, Clone)]
+pub struct ImageBuffer {
+    pub data:   Vec<Vec4>,
+    pub width:  usize,
+    pub height: usize,
+}
+impl ImageBuffer {
+    /// Creates an image of the given size cleared to zero.
+    pub fn new(width: usize, height: usize) -> ImageBuffer {
+        ImageBuffer { data: vec![Vec4::zero(); width * height], width, height }
+    }
+
+    /// Fills the image with color, in parallel with the rayon feature.
+    #[inline(always)]
+    pub fn clear(&mut self, color: Vec4) {
+        clear_slice(&mut self.data, color);
+    }
+
+    #[inline(always)]
+    pub fn get(&self, x: usize, y: usize) -> Vec4 {
+        self.data[x + y * self.width]
+    }
+
+    /// Writes the image as a binary (P6) PPM. Colors are clamped to the
+    /// range 0.0 to 1.0 and alpha is discarded.
+    pub fn write_ppm<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        let mut bytes = Vec::with_capacity(self.data.len() * 3);
+        for color in self.data.iter() {
+            let packed = Color::from_vec4(color).to_u32();
+            bytes.push(((packed >> 16) & 0xFF) as u8);
+            bytes.push(((packed >> 8)  & 0xFF) as u8);
+            bytes.push((packed         & 0xFF) as u8);
+        }
+        writer.write_all(&bytes)
+    }
+
+    /// Saves the image as a binary PPM to the given path.
+    pub fn save_ppm<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_ppm(&mut writer)?;
+        writer.flush()
+    }
+}
+impl TargetBuffer for ImageBuffer {
+    #[inline(always)]
+    fn width(&self) -> i32 {
+        self.width as i32
+    }
+
+    #[inline(always)]
+    fn height(&self) -> i32 {
+        self.height as i32
+    }
+
+    #[inline(always)]
+    fn set(&mut self, x: i32, y: i32, color: Vec4) {
+        self.data[x as usize + y as usize * self.width] = color;
+    }
+}
@@ -30,12 +30,61 @@ mod interpolate;
 mod fragment;
 mod vertex;
 mod raster;
+mod buffer;
 mod depth;
+#[cfg(feature = "rayon")]
+mod atomic_depth;
 mod target;
+mod options;
+mod stats;
+mod error;
+mod mesh;
+mod shadow;
+mod dirty;
+mod sampler;
+mod texture;
+mod cubemap;
+mod draw2d;
+mod font;
+mod image;
+mod color;
+mod light;
+mod rect;
+mod dither;
 
 pub use fragment::FragmentProgram;
+pub use fragment::FragmentProgramMRT;
+pub use fragment::QuadFragmentProgram;
+pub use fragment::Quad;
 pub use vertex::VertexProgram;
 pub use interpolate::Interpolate;
+pub use buffer::Buffer2D;
 pub use depth::DepthBuffer;
+pub use depth::DepthConfig;
+pub use depth::DepthFunc;
+#[cfg(feature = "rayon")]
+pub use atomic_depth::AtomicDepthBuffer;
 pub use target::TargetBuffer;
 pub use raster::Raster;
+pub use options::RasterOptions;
+pub use options::CullMode;
+pub use options::Traversal;
+pub use stats::RasterStats;
+pub use error::RasterError;
+pub use black_math::Winding;
+pub use mesh::Mesh;
+pub use shadow::ShadowMap;
+pub use dirty::DirtyRegion;
+pub use sampler::Sampler;
+pub use sampler::WrapMode;
+pub use sampler::SolidSampler;
+pub use sampler::GradientSampler;
+pub use sampler::CheckerSampler;
+pub use texture::Texture2D;
+pub use cubemap::Cubemap;
+pub use draw2d::Draw2D;
+pub use image::ImageBuffer;
+pub use color::Color;
+pub use light::Light;
+pub use rect::Rect;
+pub use dither::Dither;
@@ -0,0 +1,94 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::Vec3;
+
+/// Light
+///
+/// A point light with a color and intensity, for use in fragment programs.
+/// Directions passed to the lighting helpers point away from the surface
+/// and are expected to be unit length, as is the surface normal. Loop
+/// over a slice of lights and sum their contributions, or use
+/// Light::illuminate to do so.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Light {
+    pub position:  Vec3,
+    pub color:     Vec3,
+    pub intensity: f32,
+}
+impl Light {
+    pub fn new(position: Vec3, color: Vec3, intensity: f32) -> Light {
+        Light { position, color, intensity }
+    }
+
+    /// Returns the unit direction from point towards this light.
+    #[inline(always)]
+    pub fn direction(&self, point: &Vec3) -> Vec3 {
+        Vec3::normalize_or_zero(&(self.position - *point))
+    }
+
+    /// Returns the color of this light scaled by its intensity.
+    #[inline(always)]
+    pub fn radiance(&self) -> Vec3 {
+        self.color * self.intensity
+    }
+
+    /// Returns the lambertian diffuse term, the cosine of the angle between
+    /// the normal and light direction clamped to zero.
+    #[inline(always)]
+    pub fn lambert(normal: &Vec3, light_dir: &Vec3) -> f32 {
+        f32::max(Vec3::dot(normal, light_dir), 0.0)
+    }
+
+    /// Returns the Blinn-Phong specular term for the given view and light
+    /// directions. Larger shininess gives a smaller highlight. Surfaces
+    /// facing away from the light receive no specular.
+    #[inline(always)]
+    pub fn blinn_phong(normal: &Vec3, view_dir: &Vec3, light_dir: &Vec3, shininess: f32) -> f32 {
+        if Vec3::dot(normal, light_dir) <= 0.0 {
+            return 0.0;
+        }
+        let half = Vec3::normalize_or_zero(&(*view_dir + *light_dir));
+        f32::powf(f32::max(Vec3::dot(normal, &half), 0.0), shininess)
+    }
+
+    /// Returns the summed diffuse and specular light arriving at a surface
+    /// point from each of the given lights. Multiply the diffuse term by
+    /// the surface albedo and add the specular term to get the lit color.
+    pub fn illuminate(lights: &[Light], point: &Vec3, normal: &Vec3, view_dir: &Vec3, shininess: f32) -> (Vec3, Vec3) {
+        let mut diffuse  = Vec3::zero();
+        let mut specular = Vec3::zero();
+        for light in lights {
+            let light_dir = light.direction(point);
+            let radiance  = light.radiance();
+            diffuse  = diffuse  + radiance * Light::lambert(normal, &light_dir);
+            specular = specular + radiance * Light::blinn_phong(normal, view_dir, &light_dir, shininess);
+        }
+        (diffuse, specular)
+    }
+}
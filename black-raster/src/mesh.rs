@@ -0,0 +1,104 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use super::DepthBuffer;
+use super::FragmentProgram;
+use super::Interpolate;
+use super::Raster;
+use super::RasterOptions;
+use super::TargetBuffer;
+use super::VertexProgram;
+
+/// Mesh
+/// 
+/// A container for indexed triangle geometry. Vertices may be of any
+/// type accepted by a VertexProgram, with every three indices forming
+/// a single triangle.
+#[derive(Debug, Clone)]
+pub struct Mesh<TVertex> {
+    pub vertices: Vec<TVertex>,
+    pub indices:  Vec<u32>,
+}
+impl<TVertex> Mesh<TVertex> {
+    pub fn new(vertices: Vec<TVertex>, indices: Vec<u32>) -> Mesh<TVertex> {
+        Mesh { vertices, indices }
+    }
+
+    #[inline(always)]
+    pub fn draw<TVertexProgram, TFragmentProgram, TUniform, TVarying, TTargetBuffer>(
+        &self,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Raster::mesh(
+            vertex,
+            fragment,
+            depth,
+            target,
+            uniform,
+            &self.vertices,
+            &self.indices,
+        )
+    }
+
+    /// Draws this mesh as Mesh::draw with the given options.
+    #[inline(always)]
+    pub fn draw_with_options<TVertexProgram, TFragmentProgram, TUniform, TVarying, TTargetBuffer>(
+        &self,
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Raster::mesh_with_options(
+            options,
+            vertex,
+            fragment,
+            depth,
+            target,
+            uniform,
+            &self.vertices,
+            &self.indices,
+        )
+    }
+}
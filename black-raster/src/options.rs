@@ -0,0 +1,166 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::Winding;
+
+/// CullMode
+///
+/// Which faces the rasterizer discards, where the front face is given
+/// by RasterOptions::winding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CullMode {
+    /// Draw both front and back faces.
+    None,
+    /// Discard front faces.
+    Front,
+    /// Discard back faces.
+    Back,
+}
+
+/// Traversal
+///
+/// The pixel traversal used to rasterize triangles. Both sample pixel
+/// centers with the top-left rule and produce identical output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Traversal {
+    /// Walks the spans between the sorted vertices of each row.
+    Scanline,
+    /// Tests the edge functions of every pixel in the bounding box.
+    /// Offered for comparison and benchmarking.
+    HalfSpace,
+}
+
+/// RasterOptions
+///
+/// Options passed to the rasterizer for a single draw. Use
+/// RasterOptions::default() to obtain the options used by
+/// Raster::triangle. Options may be set by field or by chaining the
+/// setters from default(), for example
+/// RasterOptions::default().cull(CullMode::None).depth_write(false).build().
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RasterOptions {
+    /// A constant offset added to each fragment's depth before the
+    /// depth test and store. With the default DepthConfig fragments with
+    /// lower depth values win the depth test, so a negative bias moves
+    /// geometry toward the camera.
+    pub depth_bias: f32,
+    /// Scales the maximum screen space depth slope of the triangle and
+    /// is added to depth_bias. Useful for geometry viewed at glancing
+    /// angles where a constant bias is not enough.
+    pub slope_scaled_depth_bias: f32,
+    /// The winding of front facing triangles. Defaults to
+    /// CounterClockwise.
+    pub winding: Winding,
+    /// The faces to discard. Defaults to Back.
+    pub cull: CullMode,
+    /// If false, fragments are not tested against the depth buffer.
+    /// Defaults to true.
+    pub depth_test: bool,
+    /// If false, fragments do not write to the depth buffer. Useful for
+    /// transparent geometry that should be tested against but not occlude
+    /// later fragments. Defaults to true.
+    pub depth_write: bool,
+    /// If true, fragments are drawn or discarded by their alpha using
+    /// Dither::screen_door, giving ordered dither transparency on opaque
+    /// targets. Discarded fragments do not write depth. Defaults to false.
+    pub screen_door: bool,
+    /// If true, the barycentric weights of each fragment are clamped to
+    /// non-negative values and renormalized to sum to 1.0 before depth
+    /// and varyings are interpolated. Prevents values slightly outside
+    /// the range of the vertices for pixels on triangle edges. Defaults
+    /// to false.
+    pub clamp_weights: bool,
+    /// The pixel traversal used for triangles. Defaults to Scanline.
+    pub traversal: Traversal,
+}
+impl Default for RasterOptions {
+    fn default() -> RasterOptions {
+        RasterOptions {
+            depth_bias:              0.0,
+            slope_scaled_depth_bias: 0.0,
+            winding:                 Winding::CounterClockwise,
+            cull:                    CullMode::Back,
+            depth_test:              true,
+            depth_write:             true,
+            screen_door:             false,
+            clamp_weights:           false,
+            traversal:               Traversal::Scanline,
+        }
+    }
+}
+impl RasterOptions {
+    pub fn depth_bias(mut self, depth_bias: f32) -> RasterOptions {
+        self.depth_bias = depth_bias;
+        self
+    }
+
+    pub fn slope_scaled_depth_bias(mut self, slope_scaled_depth_bias: f32) -> RasterOptions {
+        self.slope_scaled_depth_bias = slope_scaled_depth_bias;
+        self
+    }
+
+    pub fn winding(mut self, winding: Winding) -> RasterOptions {
+        self.winding = winding;
+        self
+    }
+
+    pub fn cull(mut self, cull: CullMode) -> RasterOptions {
+        self.cull = cull;
+        self
+    }
+
+    pub fn depth_test(mut self, depth_test: bool) -> RasterOptions {
+        self.depth_test = depth_test;
+        self
+    }
+
+    pub fn depth_write(mut self, depth_write: bool) -> RasterOptions {
+        self.depth_write = depth_write;
+        self
+    }
+
+    pub fn screen_door(mut self, screen_door: bool) -> RasterOptions {
+        self.screen_door = screen_door;
+        self
+    }
+
+    pub fn clamp_weights(mut self, clamp_weights: bool) -> RasterOptions {
+        self.clamp_weights = clamp_weights;
+        self
+    }
+
+    pub fn traversal(mut self, traversal: Traversal) -> RasterOptions {
+        self.traversal = traversal;
+        self
+    }
+
+    /// Completes a chain of option setters.
+    pub fn build(self) -> RasterOptions {
+        self
+    }
+}
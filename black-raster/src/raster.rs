@@ -26,40 +26,956 @@ THE SOFTWARE.
 
 ---------------------------------------------------------------------------*/
 
-use black_math::{Vec2};
+use black_math::{Vec2, Vec3, Vec4};
 use std::cmp::{max, min};
 use std::mem::swap;
 
 use super::DepthBuffer;
+use super::Dither;
 use super::FragmentProgram;
+use super::FragmentProgramMRT;
+use super::QuadFragmentProgram;
+use super::Quad;
 use super::Interpolate;
+use super::CullMode;
+use super::RasterOptions;
+use super::RasterError;
+use super::RasterStats;
 use super::TargetBuffer;
+use super::Traversal;
 use super::VertexProgram;
+use super::Winding;
+
+/// Triangles with an absolute screen space edge value below this are discarded.
+const EDGE_EPSILON: f32 = 1e-6;
+
+/// The width and height in pixels of the screen tiles used by Raster::mesh_binned.
+const BIN_SIZE: i32 = 64;
+
+/// A triangle that has passed the vertex stage and culling, with its
+/// varyings perspective corrected and positions mapped to screen space.
+/// Varyings are divided by clip space w and reciprocal_w is interpolated
+/// to undo the division. Depth is the normalized device z (z / w) which is
+/// linear in screen space.
+struct TriangleSetup<TVarying> {
+    varying_0:      TVarying,
+    varying_1:      TVarying,
+    varying_2:      TVarying,
+    clippos_0:      Vec2,
+    clippos_1:      Vec2,
+    clippos_2:      Vec2,
+    reciprocal_w_0: f32,
+    reciprocal_w_1: f32,
+    reciprocal_w_2: f32,
+    depth_0:        f32,
+    depth_1:        f32,
+    depth_2:        f32,
+}
+impl<TVarying> TriangleSetup<TVarying> {
+    /// Returns the inclusive pixel bounds of this triangle clamped to the
+    /// viewport, or None if the triangle lies outside the viewport.
+    fn bounds(&self, width: i32, height: i32) -> Option<(i32, i32, i32, i32)> {
+        let min_x = f32::min(self.clippos_0.x, f32::min(self.clippos_1.x, self.clippos_2.x));
+        let min_y = f32::min(self.clippos_0.y, f32::min(self.clippos_1.y, self.clippos_2.y));
+        let max_x = f32::max(self.clippos_0.x, f32::max(self.clippos_1.x, self.clippos_2.x));
+        let max_y = f32::max(self.clippos_0.y, f32::max(self.clippos_1.y, self.clippos_2.y));
+        let min_x = max(min_x.floor() as i32, 0);
+        let min_y = max(min_y.floor() as i32, 0);
+        let max_x = min(max_x.ceil() as i32, width - 1);
+        let max_y = min(max_y.ceil() as i32, height - 1);
+        if min_x > max_x || min_y > max_y {
+            None
+        } else {
+            Some((min_x, min_y, max_x, max_y))
+        }
+    }
+}
 
 pub struct Raster;
 impl Raster {
     #[inline(always)]
-    pub fn triangle<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+    pub fn triangle<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::triangle_with_options(
+            &RasterOptions::default(),
+            vertex,
+            fragment,
+            depth,
+            target,
+            uniform,
+            vertex_0,
+            vertex_1,
+            vertex_2,
+        )
+    }
+
+    #[inline(always)]
+    pub fn mesh<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::mesh_with_options(&RasterOptions::default(), vertex, fragment, depth, target, uniform, vertices, indices)
+    }
+
+    /// Renders a mesh as Raster::mesh with the given options.
+    #[inline(always)]
+    pub fn mesh_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::mesh_with_stats(options, &mut RasterStats::default(), vertex, fragment, depth, target, uniform, vertices, indices)
+    }
+
+    /// Renders a mesh as Raster::mesh_with_options, accumulating counters
+    /// for the draw into stats.
+    #[inline(always)]
+    pub fn mesh_with_stats<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        stats:    &mut RasterStats,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        for triangle in indices.chunks_exact(3) {
+            Self::triangle_with_stats(
+                options,
+                stats,
+                vertex,
+                fragment,
+                depth,
+                target,
+                uniform,
+                &vertices[triangle[0] as usize],
+                &vertices[triangle[1] as usize],
+                &vertices[triangle[2] as usize],
+            );
+        }
+    }
+
+    /// Renders a mesh as Raster::mesh after checking the depth buffer matches
+    /// the target, the target is not empty and the indices are valid.
+    /// Returns the stats for the draw, or an error without drawing anything.
+    pub fn try_mesh<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) -> Result<RasterStats, RasterError> where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::validate_draw(depth, target, vertices.len(), indices)?;
+        let mut stats = RasterStats::default();
+        Self::mesh_with_stats(&RasterOptions::default(), &mut stats, vertex, fragment, depth, target, uniform, vertices, indices);
+        Ok(stats)
+    }
+
+    /// Renders a triangle as Raster::triangle after checking the depth buffer
+    /// matches the target and the target is not empty. Returns the stats for
+    /// the draw, or an error without drawing anything.
+    pub fn try_triangle<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) -> Result<RasterStats, RasterError> where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::validate_draw(depth, target, 0, &[])?;
+        let mut stats = RasterStats::default();
+        Self::triangle_with_stats(&RasterOptions::default(), &mut stats, vertex, fragment, depth, target, uniform, vertex_0, vertex_1, vertex_2);
+        Ok(stats)
+    }
+
+    /// Asserts in debug builds that the depth buffer and target have the same
+    /// dimensions. Mismatched buffers are indexed with different strides.
+    #[inline(always)]
+    fn debug_assert_sizes<TTargetBuffer: TargetBuffer>(depth: &DepthBuffer, target: &TTargetBuffer) {
+        debug_assert!(
+            depth.width() as i32 == target.width() && depth.height() as i32 == target.height(),
+            "depth buffer is {}x{} but target is {}x{}",
+            depth.width(),
+            depth.height(),
+            target.width(),
+            target.height()
+        );
+    }
+
+    /// Writes a shaded fragment to the target. Returns false without writing
+    /// if the fragment program discarded the fragment, or if the screen door
+    /// test of the options discards it by alpha.
+    #[inline(always)]
+    fn write_fragment<TTargetBuffer: TargetBuffer>(options: &RasterOptions, target: &mut TTargetBuffer, x: i32, y: i32, color: Option<Vec4>) -> bool {
+        match color {
+            Some(color) if !options.screen_door || Dither::screen_door(color.w, x, y) => {
+                target.set(x, y, color);
+                true
+            },
+            _ => false
+        }
+    }
+
+    /// Checks the buffers and indices of a draw, see Raster::try_mesh.
+    fn validate_draw<TTargetBuffer: TargetBuffer>(
+        depth:        &DepthBuffer,
+        target:       &TTargetBuffer,
+        vertex_count: usize,
+        indices:      &[u32],
+    ) -> Result<(), RasterError> {
+        let width  = target.width();
+        let height = target.height();
+        if width <= 0 || height <= 0 {
+            return Err(RasterError::EmptyViewport { width, height });
+        }
+        if depth.width() != width as usize || depth.height() != height as usize {
+            return Err(RasterError::SizeMismatch {
+                depth_width:   depth.width(),
+                depth_height:  depth.height(),
+                target_width:  width,
+                target_height: height,
+            });
+        }
+        if indices.len() % 3 != 0 {
+            return Err(RasterError::IndexCount(indices.len()));
+        }
+        if let Some((offset, index)) = indices.iter().enumerate().find(|(_, index)| **index as usize >= vertex_count) {
+            return Err(RasterError::IndexOutOfRange { offset, index: *index, vertex_count });
+        }
+        Ok(())
+    }
+
+    /// Renders a mesh by first running the vertex stage for all triangles,
+    /// then binning them into fixed size screen tiles and rasterizing tile
+    /// by tile. Produces the same output as Raster::mesh. Keeping writes
+    /// within a tile improves locality on the depth and target buffers,
+    /// at the cost of storing the transformed triangles for the draw.
+    pub fn mesh_binned<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::mesh_binned_with_options(&RasterOptions::default(), vertex, fragment, depth, target, uniform, vertices, indices)
+    }
+
+    /// Renders a mesh as Raster::mesh_binned with the given options.
+    pub fn mesh_binned_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        let mut stats = RasterStats::default();
+        let width     = target.width();
+        let height    = target.height();
+        if width <= 0 || height <= 0 {
+            return;
+        }
+
+        // run the vertex stage once per triangle.
+        let mut triangles = Vec::with_capacity(indices.len() / 3);
+        for triangle in indices.chunks_exact(3) {
+            if let Some(setup) = Self::setup_triangle(
+                options,
+                &mut stats,
+                vertex,
+                width,
+                height,
+                uniform,
+                &vertices[triangle[0] as usize],
+                &vertices[triangle[1] as usize],
+                &vertices[triangle[2] as usize],
+            ) {
+                triangles.push(setup);
+            }
+        }
+
+        // bin triangles by their screen space bounds. Bins retain submission
+        // order so the depth test resolves identically to Raster::mesh.
+        let bins_x = (width  + BIN_SIZE - 1) / BIN_SIZE;
+        let bins_y = (height + BIN_SIZE - 1) / BIN_SIZE;
+        let mut bins: Vec<Vec<usize>> = vec![vec![]; (bins_x * bins_y) as usize];
+        for (index, setup) in triangles.iter().enumerate() {
+            if let Some((min_x, min_y, max_x, max_y)) = setup.bounds(width, height) {
+                for bin_y in (min_y / BIN_SIZE)..=(max_y / BIN_SIZE) {
+                    for bin_x in (min_x / BIN_SIZE)..=(max_x / BIN_SIZE) {
+                        bins[(bin_x + bin_y * bins_x) as usize].push(index);
+                    }
+                }
+            }
+        }
+
+        // rasterize each bin, clipped to its tile.
+        let mut func = |x: i32, y: i32, varying: &TVarying, _: &[f32; 3]| {
+            Self::write_fragment(options, target, x, y, fragment.main(uniform, varying))
+        };
+        for bin_y in 0..bins_y {
+            for bin_x in 0..bins_x {
+                let region = (
+                    bin_x * BIN_SIZE,
+                    bin_y * BIN_SIZE,
+                    min((bin_x + 1) * BIN_SIZE, width),
+                    min((bin_y + 1) * BIN_SIZE, height),
+                );
+                for index in &bins[(bin_x + bin_y * bins_x) as usize] {
+                    Self::draw_triangle(options, &mut stats, depth, region, &triangles[*index], &mut func);
+                }
+            }
+        }
+    }
+
+    /// Runs the vertex program over each vertex, returning the clip space
+    /// position and varying of each in vertex order.
+    pub fn transform_vertices<TVertexProgram, TUniform, TVertex, TVarying>(
+        vertex:   &TVertexProgram,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+    ) -> Vec<(Vec4, TVarying)> where
+        TVertexProgram: VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TVarying:       Interpolate,
+    {
+        vertices.iter().map(|input| {
+            let mut varying = TVarying::new();
+            let position = vertex.main(uniform, input, &mut varying);
+            (position, varying)
+        }).collect()
+    }
+
+    /// Runs the vertex program over each vertex in parallel. Produces the
+    /// same buffer as Raster::transform_vertices.
+    #[cfg(feature = "rayon")]
+    pub fn transform_vertices_parallel<TVertexProgram, TUniform, TVertex, TVarying>(
+        vertex:   &TVertexProgram,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+    ) -> Vec<(Vec4, TVarying)> where
+        TVertexProgram: VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying> + Sync,
+        TUniform:       Sync,
+        TVertex:        Sync,
+        TVarying:       Interpolate + Send,
+    {
+        use rayon::prelude::*;
+        vertices.par_iter().map(|input| {
+            let mut varying = TVarying::new();
+            let position = vertex.main(uniform, input, &mut varying);
+            (position, varying)
+        }).collect()
+    }
+
+    /// Renders a mesh as Raster::mesh, but runs the vertex program once per
+    /// vertex in parallel before rasterizing the triangles serially from the
+    /// transformed buffer. Vertices shared between triangles are shaded
+    /// once, and the output matches Raster::mesh.
+    #[cfg(feature = "rayon")]
+    pub fn mesh_parallel<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying> + Sync,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TUniform:         Sync,
+        TVertex:          Sync,
+        TVarying:         Interpolate + Send,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::mesh_parallel_with_options(&RasterOptions::default(), vertex, fragment, depth, target, uniform, vertices, indices)
+    }
+
+    /// Renders a mesh as Raster::mesh_parallel with the given options.
+    #[cfg(feature = "rayon")]
+    pub fn mesh_parallel_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying> + Sync,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TUniform:         Sync,
+        TVertex:          Sync,
+        TVarying:         Interpolate + Send,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        let mut stats   = RasterStats::default();
+        let width       = target.width();
+        let height      = target.height();
+        let transformed = Self::transform_vertices_parallel(vertex, uniform, vertices);
+        for triangle in indices.chunks_exact(3) {
+            let (position_0, varying_0) = &transformed[triangle[0] as usize];
+            let (position_1, varying_1) = &transformed[triangle[1] as usize];
+            let (position_2, varying_2) = &transformed[triangle[2] as usize];
+            if let Some(setup) = Self::setup_transformed(
+                options,
+                &mut stats,
+                width,
+                height,
+                (position_0, varying_0),
+                (position_1, varying_1),
+                (position_2, varying_2),
+            ) {
+                Self::draw_triangle(options, &mut stats, depth, (0, 0, width, height), &setup, |x, y, varying, _| {
+                    Self::write_fragment(options, target, x, y, fragment.main(uniform, varying))
+                });
+            }
+        }
+    }
+
+    /// Returns the number of pixels of the mesh that pass the depth test
+    /// against the given depth buffer. Only the vertex program is run and
+    /// neither the depth buffer nor any target is written. Each pixel is
+    /// counted once regardless of how many triangles cover it.
+    pub fn occlusion_query<TVertexProgram, TUniform, TVertex, TVarying>(
+        vertex:   &TVertexProgram,
+        depth:    &DepthBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) -> u32 where
+        TVertexProgram: VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TVarying:       Interpolate,
+    {
+        Self::occlusion_query_with_options(&RasterOptions::default(), vertex, depth, uniform, vertices, indices)
+    }
+
+    /// Counts visible pixels as Raster::occlusion_query with the given
+    /// options, so culling and depth bias match the draw being queried.
+    /// With depth_test disabled every covered pixel is counted.
+    pub fn occlusion_query_with_options<TVertexProgram, TUniform, TVertex, TVarying>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        depth:    &DepthBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) -> u32 where
+        TVertexProgram: VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TVarying:       Interpolate,
+    {
+        let mut stats = RasterStats::default();
+        let width     = depth.width()  as i32;
+        let height    = depth.height() as i32;
+        let mut covered = vec![false; depth.width() * depth.height()];
+        let mut count   = 0;
+        for triangle in indices.chunks_exact(3) {
+            let setup = match Self::setup_triangle(
+                options,
+                &mut stats,
+                vertex,
+                width,
+                height,
+                uniform,
+                &vertices[triangle[0] as usize],
+                &vertices[triangle[1] as usize],
+                &vertices[triangle[2] as usize],
+            ) {
+                Some(setup) => setup,
+                None => continue,
+            };
+            let depth_bias = Self::triangle_depth_bias(options, &setup.clippos_0, &setup.clippos_1, &setup.clippos_2, &setup.depth_0, &setup.depth_1, &setup.depth_2);
+            Self::rasterize_triangle(width, height, &setup.clippos_0, &setup.clippos_1, &setup.clippos_2, |x, y, weight_0, weight_1, weight_2| {
+                let calculated_depth =
+                      (weight_0 * setup.depth_0)
+                    + (weight_1 * setup.depth_1)
+                    + (weight_2 * setup.depth_2);
+                let index = (x + (y * width)) as usize;
+                if !covered[index] && (!options.depth_test || depth.test(x as usize, y as usize, calculated_depth + depth_bias)) {
+                    covered[index] = true;
+                    count += 1;
+                }
+            });
+        }
+        count
+    }
+
+    #[inline(always)]
+    pub fn mesh_instanced<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniforms: &[TUniform],
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::mesh_instanced_with_options(&RasterOptions::default(), vertex, fragment, depth, target, uniforms, vertices, indices)
+    }
+
+    /// Renders a mesh once per uniform as Raster::mesh_instanced with the
+    /// given options.
+    #[inline(always)]
+    pub fn mesh_instanced_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniforms: &[TUniform],
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        for uniform in uniforms {
+            Self::mesh_with_options(options, vertex, fragment, depth, target, uniform, vertices, indices);
+        }
+    }
+
+    #[inline(always)]
+    pub fn triangle_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::triangle_with_stats(
+            options,
+            &mut RasterStats::default(),
+            vertex,
+            fragment,
+            depth,
+            target,
+            uniform,
+            vertex_0,
+            vertex_1,
+            vertex_2,
+        )
+    }
+
+    /// Renders a triangle as Raster::triangle_with_options, accumulating
+    /// counters for the draw into stats.
+    #[inline(always)]
+    pub fn triangle_with_stats<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        stats:    &mut RasterStats,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        let width  = target.width();
+        let height = target.height();
+        Self::process_triangle(
+            options,
+            stats,
+            vertex,
+            depth,
+            width,
+            height,
+            uniform,
+            vertex_0,
+            vertex_1,
+            vertex_2,
+            |x, y, varying, _| Self::write_fragment(options, target, x, y, fragment.main(uniform, varying)),
+        )
+    }
+
+    /// Renders a triangle from clip space positions and varyings that are
+    /// already computed, skipping the vertex stage. Culling, the perspective
+    /// divide and rasterization are as Raster::triangle.
+    #[inline(always)]
+    pub fn triangle_clip<TFragmentProgram, TUniform, TVarying, TTargetBuffer>(
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        clip_pos: [Vec4; 3],
+        varyings: [TVarying; 3],
+    ) where
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::triangle_clip_with_options(&RasterOptions::default(), fragment, depth, target, uniform, clip_pos, varyings)
+    }
+
+    /// Renders a triangle as Raster::triangle_clip with the given options.
+    #[inline(always)]
+    pub fn triangle_clip_with_options<TFragmentProgram, TUniform, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        clip_pos: [Vec4; 3],
+        varyings: [TVarying; 3],
+    ) where
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        let mut stats = RasterStats::default();
+        let width     = target.width();
+        let height    = target.height();
+        if let Some(setup) = Self::setup_transformed(
+            options,
+            &mut stats,
+            width,
+            height,
+            (&clip_pos[0], &varyings[0]),
+            (&clip_pos[1], &varyings[1]),
+            (&clip_pos[2], &varyings[2]),
+        ) {
+            Self::draw_triangle(options, &mut stats, depth, (0, 0, width, height), &setup, |x, y, varying, _| {
+                Self::write_fragment(options, target, x, y, fragment.main(uniform, varying))
+            });
+        }
+    }
+
+    /// Renders a triangle with a fragment program that writes N outputs, with
+    /// each output written to the corresponding target. All targets are expected
+    /// to be the same size as the depth buffer.
+    #[inline(always)]
+    pub fn triangle_mrt<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer, const N: usize>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        targets:  &mut [TTargetBuffer; N],
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgramMRT<N, Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::triangle_mrt_with_options(&RasterOptions::default(), vertex, fragment, depth, targets, uniform, vertex_0, vertex_1, vertex_2)
+    }
+
+    /// Renders a triangle as Raster::triangle_mrt with the given options. The
+    /// screen door test uses the alpha of the first output.
+    #[inline(always)]
+    pub fn triangle_mrt_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer, const N: usize>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        targets:  &mut [TTargetBuffer; N],
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgramMRT<N, Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        for target in targets.iter() {
+            Self::debug_assert_sizes(depth, target);
+        }
+        if N == 0 {
+            return;
+        }
+        let width  = targets[0].width();
+        let height = targets[0].height();
+        Self::process_triangle(
+            options,
+            &mut RasterStats::default(),
+            vertex,
+            depth,
+            width,
+            height,
+            uniform,
+            vertex_0,
+            vertex_1,
+            vertex_2,
+            |x, y, varying, _| {
+                let colors = fragment.main(uniform, varying);
+                if options.screen_door && !Dither::screen_door(colors[0].w, x, y) {
+                    return false;
+                }
+                for (target, color) in targets.iter_mut().zip(colors.iter()) {
+                    target.set(x, y, *color);
+                }
+                true
+            },
+        )
+    }
+
+    /// Renders a triangle with a fragment program that may query screen
+    /// space derivatives of its varyings. Fragments are shaded as part of
+    /// 2x2 pixel quads, see Quad for how derivatives are computed.
+    pub fn triangle_quad<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: QuadFragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::triangle_quad_with_options(&RasterOptions::default(), vertex, fragment, depth, target, uniform, vertex_0, vertex_1, vertex_2)
+    }
+
+    /// Renders a triangle as Raster::triangle_quad with the given options.
+    pub fn triangle_quad_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: QuadFragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        let mut stats = RasterStats::default();
+        let width     = target.width();
+        let height    = target.height();
+        let setup = match Self::setup_triangle(options, &mut stats, vertex, width, height, uniform, vertex_0, vertex_1, vertex_2) {
+            Some(setup) => setup,
+            None => return,
+        };
+
+        // per pixel steps of the barycentric weights, used to evaluate the
+        // neighbouring pixels of each quad.
+        let TriangleSetup { clippos_0, clippos_1, clippos_2, .. } = &setup;
+        let inverse_edge = 1.0 / Self::edge(clippos_0, clippos_1, clippos_2);
+        let step_x = [
+            (clippos_2.y - clippos_1.y) * inverse_edge,
+            (clippos_0.y - clippos_2.y) * inverse_edge,
+            (clippos_1.y - clippos_0.y) * inverse_edge,
+        ];
+        let step_y = [
+            (clippos_1.x - clippos_2.x) * inverse_edge,
+            (clippos_2.x - clippos_0.x) * inverse_edge,
+            (clippos_0.x - clippos_1.x) * inverse_edge,
+        ];
+        Self::draw_triangle(options, &mut stats, depth, (0, 0, width, height), &setup, |x, y, varying, weights| {
+            let quad = Quad {
+                varyings:    [&setup.varying_0, &setup.varying_1, &setup.varying_2],
+                reciprocal_w: [setup.reciprocal_w_0, setup.reciprocal_w_1, setup.reciprocal_w_2],
+                weights:     *weights,
+                step_x,
+                step_y,
+                x,
+                y,
+            };
+            Self::write_fragment(options, target, x, y, fragment.main(uniform, varying, &quad))
+        });
+    }
+
+    /// Renders a mesh with a fragment program that may query screen space
+    /// derivatives. See Raster::triangle_quad.
+    #[inline(always)]
+    pub fn mesh_quad<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: QuadFragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::mesh_quad_with_options(&RasterOptions::default(), vertex, fragment, depth, target, uniform, vertices, indices)
+    }
+
+    /// Renders a mesh as Raster::mesh_quad with the given options.
+    #[inline(always)]
+    pub fn mesh_quad_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertices: &[TVertex],
+        indices:  &[u32],
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: QuadFragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        for triangle in indices.chunks_exact(3) {
+            Self::triangle_quad_with_options(
+                options,
+                vertex,
+                fragment,
+                depth,
+                target,
+                uniform,
+                &vertices[triangle[0] as usize],
+                &vertices[triangle[1] as usize],
+                &vertices[triangle[2] as usize],
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn process_triangle<TVertexProgram, TUniform, TVertex, TVarying, TFunc>(
+        options:  &RasterOptions,
+        stats:    &mut RasterStats,
         vertex:   &TVertexProgram,
-        fragment: &TFragmentProgram,
         depth:    &mut DepthBuffer,
-        target:   &mut TTargetBuffer,
+        width:    i32,
+        height:   i32,
         uniform:  &TUniform,
         vertex_0: &TVertex,
         vertex_1: &TVertex,
         vertex_2: &TVertex,
+        func:     TFunc,
     ) where
-        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
-        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
-        TVarying:         Interpolate,
-        TTargetBuffer:    TargetBuffer,
+        TVertexProgram: VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TVarying:       Interpolate,
+        TFunc:          FnMut(i32, i32, &TVarying, &[f32; 3]) -> bool,
     {
-        // compute half width and height.
-        let width       = target.width()  as f32;
-        let height      = target.height() as f32;
-        let half_width  = width  * 0.5;
-        let half_height = height * 0.5;
+        if let Some(setup) = Self::setup_triangle(options, stats, vertex, width, height, uniform, vertex_0, vertex_1, vertex_2) {
+            Self::draw_triangle(options, stats, depth, (0, 0, width, height), &setup, func);
+        }
+    }
 
+    /// Runs the vertex stage for a triangle and maps it to screen space.
+    /// Returns None if the triangle is discarded or culled.
+    #[inline(always)]
+    fn setup_triangle<TVertexProgram, TUniform, TVertex, TVarying>(
+        options:  &RasterOptions,
+        stats:    &mut RasterStats,
+        vertex:   &TVertexProgram,
+        width:    i32,
+        height:   i32,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+        vertex_2: &TVertex,
+    ) -> Option<TriangleSetup<TVarying>> where
+        TVertexProgram: VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TVarying:       Interpolate,
+    {
         // setup vrs for this primitive.
         let mut varying_0 = Interpolate::new();
         let mut varying_1 = Interpolate::new();
@@ -69,67 +985,489 @@ impl Raster {
         let position_0 = vertex.main(&uniform, &vertex_0, &mut varying_0);
         let position_1 = vertex.main(&uniform, &vertex_1, &mut varying_1);
         let position_2 = vertex.main(&uniform, &vertex_2, &mut varying_2);
+        Self::setup_transformed(
+            options,
+            stats,
+            width,
+            height,
+            (&position_0, &varying_0),
+            (&position_1, &varying_1),
+            (&position_2, &varying_2),
+        )
+    }
+
+    /// Maps a triangle whose vertices have passed the vertex stage to screen
+    /// space. Returns None if the triangle is discarded or culled.
+    #[inline(always)]
+    fn setup_transformed<TVarying>(
+        options:  &RasterOptions,
+        stats:    &mut RasterStats,
+        width:    i32,
+        height:   i32,
+        vertex_0: (&Vec4, &TVarying),
+        vertex_1: (&Vec4, &TVarying),
+        vertex_2: (&Vec4, &TVarying),
+    ) -> Option<TriangleSetup<TVarying>> where
+        TVarying: Interpolate,
+    {
+        stats.triangles_submitted += 1;
+        let (position_0, varying_0) = vertex_0;
+        let (position_1, varying_1) = vertex_1;
+        let (position_2, varying_2) = vertex_2;
 
-        // prevent z less than 0.0 errors, discard the triangle.
-        if position_0.z < 0.0 || position_1.z < 0.0 || position_2.z < 0.0 {
+        // discard triangles with vertices behind the camera, the homogeneous
+        // divide is undefined for w <= 0.0.
+        if position_0.w <= 0.0 || position_1.w <= 0.0 || position_2.w <= 0.0 {
             // todo: implement frustum clipping
+            stats.triangles_clipped += 1;
+            return None;
+        }
+        // discard triangles crossing the near or far plane.
+        if Self::outside_depth_range(position_0) || Self::outside_depth_range(position_1) || Self::outside_depth_range(position_2) {
+            stats.triangles_clipped += 1;
+            return None;
+        }
+
+        // calculate positions in screen space.
+        let clippos_0 = Self::ndc_to_screen(&Self::clip_to_ndc(position_0), width, height);
+        let clippos_1 = Self::ndc_to_screen(&Self::clip_to_ndc(position_1), width, height);
+        let clippos_2 = Self::ndc_to_screen(&Self::clip_to_ndc(position_2), width, height);
+
+        // discard degenerate triangles with (near) zero area.
+        let edge = Self::edge(&clippos_0, &clippos_1, &clippos_2);
+        if edge.abs() < EDGE_EPSILON {
+            stats.triangles_culled += 1;
+            return None;
+        }
+
+        if Self::is_culled(options, edge) {
+            stats.triangles_culled += 1;
+            return None;
+        }
+        Some(TriangleSetup {
+            varying_0:      Interpolate::correct(varying_0, &position_0.w),
+            varying_1:      Interpolate::correct(varying_1, &position_1.w),
+            varying_2:      Interpolate::correct(varying_2, &position_2.w),
+            clippos_0,
+            clippos_1,
+            clippos_2,
+            reciprocal_w_0: 1.0 / position_0.w,
+            reciprocal_w_1: 1.0 / position_1.w,
+            reciprocal_w_2: 1.0 / position_2.w,
+            depth_0:        position_0.z / position_0.w,
+            depth_1:        position_1.z / position_1.w,
+            depth_2:        position_2.z / position_2.w,
+        })
+    }
+
+    /// Fills a convex polygon given in screen space, in either winding,
+    /// with one varying per vertex. The polygon is drawn as a triangle fan
+    /// so shared edges are filled once. Screen positions carry no depth so
+    /// varyings are interpolated linearly.
+    ///
+    /// Every fragment has a constant depth of 1.0, the far plane with the
+    /// default DepthConfig. The polygon is depth tested and written at that
+    /// depth, so it is hidden by any nearer geometry already drawn. Use
+    /// options with depth_test or depth_write disabled for overlays.
+    ///
+    /// positions and varyings must have the same length, which is checked
+    /// in debug builds.
+    pub fn convex_polygon<TFragmentProgram, TUniform, TVarying, TTargetBuffer>(
+        positions: &[Vec2],
+        varyings:  &[TVarying],
+        fragment:  &TFragmentProgram,
+        depth:     &mut DepthBuffer,
+        target:    &mut TTargetBuffer,
+        uniform:   &TUniform,
+    ) where
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::convex_polygon_with_options(&RasterOptions::default(), positions, varyings, fragment, depth, target, uniform)
+    }
+
+    /// Fills a polygon as Raster::convex_polygon with the given options.
+    /// Polygons are drawn in either winding, the cull and winding options
+    /// do not apply.
+    pub fn convex_polygon_with_options<TFragmentProgram, TUniform, TVarying, TTargetBuffer>(
+        options:   &RasterOptions,
+        positions: &[Vec2],
+        varyings:  &[TVarying],
+        fragment:  &TFragmentProgram,
+        depth:     &mut DepthBuffer,
+        target:    &mut TTargetBuffer,
+        uniform:   &TUniform,
+    ) where
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        debug_assert_eq!(positions.len(), varyings.len(), "convex_polygon needs one varying per position");
+        let mut stats = RasterStats::default();
+        let region    = (0, 0, target.width(), target.height());
+        let count     = min(positions.len(), varyings.len());
+        for index in 1..max(count, 2) - 1 {
+            let setup = TriangleSetup {
+                varying_0:      Interpolate::correct(&varyings[0], &1.0),
+                varying_1:      Interpolate::correct(&varyings[index], &1.0),
+                varying_2:      Interpolate::correct(&varyings[index + 1], &1.0),
+                clippos_0:      positions[0],
+                clippos_1:      positions[index],
+                clippos_2:      positions[index + 1],
+                reciprocal_w_0: 1.0,
+                reciprocal_w_1: 1.0,
+                reciprocal_w_2: 1.0,
+                depth_0:        1.0,
+                depth_1:        1.0,
+                depth_2:        1.0,
+            };
+            Self::draw_triangle(options, &mut stats, depth, region, &setup, |x, y, varying, _| {
+                Self::write_fragment(options, target, x, y, fragment.main(uniform, varying))
+            });
+        }
+    }
+
+    /// Renders a line between two vertices. The line is clipped to the
+    /// viewport and walked one pixel per step along its major axis, with
+    /// varyings interpolated perspective correct between the end points.
+    /// Lines are depth tested and write depth.
+    pub fn line<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::line_with_options(&RasterOptions::default(), vertex, fragment, depth, target, uniform, vertex_0, vertex_1)
+    }
+
+    /// Renders a line as Raster::line with the given options. Lines have
+    /// no facing or area, so cull, winding and slope_scaled_depth_bias do
+    /// not apply.
+    pub fn line_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        vertex_1: &TVertex,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::debug_assert_sizes(depth, target);
+        let width  = target.width();
+        let height = target.height();
+
+        // execute vertex shader, store position for interpolation.
+        let mut varying_0 = Interpolate::new();
+        let mut varying_1 = Interpolate::new();
+        let position_0 = vertex.main(uniform, vertex_0, &mut varying_0);
+        let position_1 = vertex.main(uniform, vertex_1, &mut varying_1);
+
+        // discard lines with vertices behind the camera or crossing the near or far plane.
+        if position_0.w <= 0.0 || position_1.w <= 0.0 || Self::outside_depth_range(&position_0) || Self::outside_depth_range(&position_1) {
             return;
         }
+        let clippos_0 = Self::ndc_to_screen(&Self::clip_to_ndc(&position_0), width, height);
+        let clippos_1 = Self::ndc_to_screen(&Self::clip_to_ndc(&position_1), width, height);
 
-        // calculate positions in clip space.
-        let clippos_0 = Vec2::new(
-            ((position_0.x  / position_0.w) * width) + half_width,
-            ((-position_0.y / position_0.w) * height) + half_height,
-        );
-        let clippos_1 = Vec2::new(
-            ((position_1.x  / position_1.w) * width) + half_width,
-            ((-position_1.y / position_1.w) * height) + half_height,
-        );
-        let clippos_2 = Vec2::new(
-            ((position_2.x  / position_2.w) * width) + half_width,
-            ((-position_2.y / position_2.w) * height) + half_height,
-        );
+        // clip to the viewport, t_0 and t_1 are the visible range along the line.
+        let (t_0, t_1) = match Self::clip_line(&clippos_0, &clippos_1, width as f32, height as f32) {
+            Some(range) => range,
+            None => return,
+        };
+        let delta = clippos_1 - clippos_0;
+        let steps = (f32::max(delta.x.abs(), delta.y.abs()) * (t_1 - t_0)).ceil().max(1.0) as i32;
 
-        // run fragment processor
-        if Self::edge(&clippos_0, &clippos_1, &clippos_2) >= 0.0 {
-            Self::draw_triangle(
-                fragment,
-                depth,
-                target,
-                uniform,
-                &Interpolate::correct(&varying_0, &position_0.z),
-                &Interpolate::correct(&varying_1, &position_1.z),
-                &Interpolate::correct(&varying_2, &position_2.z),
-                &clippos_0,
-                &clippos_1,
-                &clippos_2,
-                &(1.0 / position_0.z),
-                &(1.0 / position_1.z),
-                &(1.0 / position_2.z),
-            );
+        let varying_0      = Interpolate::correct(&varying_0, &position_0.w);
+        let varying_1      = Interpolate::correct(&varying_1, &position_1.w);
+        let reciprocal_w_0 = 1.0 / position_0.w;
+        let reciprocal_w_1 = 1.0 / position_1.w;
+        let depth_0        = position_0.z / position_0.w;
+        let depth_1        = position_1.z / position_1.w;
+        for step in 0..=steps {
+            let t = t_0 + (t_1 - t_0) * (step as f32 / steps as f32);
+            let x = (clippos_0.x + delta.x * t).floor() as i32;
+            let y = (clippos_0.y + delta.y * t).floor() as i32;
+            if x < 0 || y < 0 || x >= width || y >= height {
+                continue;
+            }
+            let weight_0 = 1.0 - t;
+            let weight_1 = t;
+            let calculated_depth = (weight_0 * depth_0) + (weight_1 * depth_1) + options.depth_bias;
+            if !options.depth_test || depth.test(x as usize, y as usize, calculated_depth) {
+                let varying = TVarying::interpolate(
+                    &varying_0,
+                    &varying_1,
+                    &varying_1,
+                    &weight_0,
+                    &weight_1,
+                    &0.0,
+                    &((weight_0 * reciprocal_w_0) + (weight_1 * reciprocal_w_1)),
+                );
+                if Self::write_fragment(options, target, x, y, fragment.main(uniform, &varying)) && options.depth_write {
+                    depth.set(x as usize, y as usize, calculated_depth);
+                }
+            }
         }
     }
 
-    #[inline(always)]
-    fn draw_triangle<TTargetBuffer, TFragmentProgram, TVarying, TUniform>(
-        fragment:      &TFragmentProgram,
-        depth:         &mut DepthBuffer,
-        target:        &mut TTargetBuffer,
-        uniform:       &TUniform,
-        varying_0:     &TVarying,
-        varying_1:     &TVarying,
-        varying_2:     &TVarying,
-        clippos_0:     &Vec2,
-        clippos_1:     &Vec2,
-        clippos_2:     &Vec2,
-        corrected_z_0: &f32,
-        corrected_z_1: &f32,
-        corrected_z_2: &f32,
+    /// Renders a point as a size x size pixel square centered on the
+    /// projected vertex. Pixels are covered if their centers lie within
+    /// [center - size / 2, center + size / 2). The fragment program runs
+    /// once per covered pixel with the vertex's varying. Points are depth
+    /// tested at the vertex depth and write depth.
+    pub fn point<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        size:     f32,
+    ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
+        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
+        TVarying:         Interpolate,
+        TTargetBuffer:    TargetBuffer,
+    {
+        Self::point_with_options(&RasterOptions::default(), vertex, fragment, depth, target, uniform, vertex_0, size)
+    }
+
+    /// Renders a point as Raster::point with the given options. Points have
+    /// no facing or depth slope, so cull, winding and slope_scaled_depth_bias
+    /// do not apply.
+    pub fn point_with_options<TVertexProgram, TFragmentProgram, TUniform, TVertex, TVarying, TTargetBuffer>(
+        options:  &RasterOptions,
+        vertex:   &TVertexProgram,
+        fragment: &TFragmentProgram,
+        depth:    &mut DepthBuffer,
+        target:   &mut TTargetBuffer,
+        uniform:  &TUniform,
+        vertex_0: &TVertex,
+        size:     f32,
     ) where
+        TVertexProgram:   VertexProgram<Uniform = TUniform, Vertex = TVertex, Varying = TVarying>,
         TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
         TVarying:         Interpolate,
         TTargetBuffer:    TargetBuffer,
     {
+        Self::debug_assert_sizes(depth, target);
+        let width  = target.width();
+        let height = target.height();
+
+        // execute vertex shader, the varying is constant across the point.
+        let mut varying = Interpolate::new();
+        let position = vertex.main(uniform, vertex_0, &mut varying);
+
+        // discard points behind the camera or outside the near and far planes.
+        if position.w <= 0.0 || Self::outside_depth_range(&position) {
+            return;
+        }
+        let center = Self::ndc_to_screen(&Self::clip_to_ndc(&position), width, height);
+        let calculated_depth = (position.z / position.w) + options.depth_bias;
+
+        // pixel centers within the square, max exclusive.
+        let half  = size * 0.5;
+        let min_x = max((center.x - half - 0.5).ceil() as i32, 0);
+        let min_y = max((center.y - half - 0.5).ceil() as i32, 0);
+        let max_x = min((center.x + half - 0.5).ceil() as i32, width);
+        let max_y = min((center.y + half - 0.5).ceil() as i32, height);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                if (!options.depth_test || depth.test(x as usize, y as usize, calculated_depth))
+                    && Self::write_fragment(options, target, x, y, fragment.main(uniform, &varying))
+                    && options.depth_write
+                {
+                    depth.set(x as usize, y as usize, calculated_depth);
+                }
+            }
+        }
+    }
+
+    /// Clips the line p0 -> p1 to the rectangle (0, 0) -> (width, height)
+    /// using Liang-Barsky. Returns the visible range of the line as
+    /// parameters in [0, 1], or None if the line is outside.
+    #[inline(always)]
+    fn clip_line(p0: &Vec2, p1: &Vec2, width: f32, height: f32) -> Option<(f32, f32)> {
+        let dx = p1.x - p0.x;
+        let dy = p1.y - p0.y;
+        let mut t_0 = 0.0;
+        let mut t_1 = 1.0;
+        for (p, q) in [(-dx, p0.x), (dx, width - p0.x), (-dy, p0.y), (dy, height - p0.y)].iter() {
+            if *p == 0.0 {
+                if *q < 0.0 {
+                    return None;
+                }
+            } else {
+                let r = q / p;
+                if *p < 0.0 {
+                    t_0 = f32::max(t_0, r);
+                } else {
+                    t_1 = f32::min(t_1, r);
+                }
+            }
+        }
+        if t_0 > t_1 {
+            None
+        } else {
+            Some((t_0, t_1))
+        }
+    }
+
+    /// Returns true if a clip space position lies outside 0.0 <= z <= w. Both
+    /// the standard and the reversed-Z projections map the view volume to
+    /// this range, with the near plane at z = 0.0 or z = w respectively.
+    #[inline(always)]
+    fn outside_depth_range(position: &Vec4) -> bool {
+        position.z < 0.0 || position.z > position.w
+    }
+
+    /// Performs the homogeneous divide, returning the normalized device
+    /// coordinates for the given clip space position.
+    #[inline(always)]
+    pub fn clip_to_ndc(position: &Vec4) -> Vec3 {
+        Vec3::new(
+            position.x / position.w,
+            position.y / position.w,
+            position.z / position.w,
+        )
+    }
+
+    /// Maps normalized device coordinates to screen space for a viewport of
+    /// the given width and height. The y axis is flipped such that screen y
+    /// increases downwards.
+    #[inline(always)]
+    pub fn ndc_to_screen(ndc: &Vec3, width: i32, height: i32) -> Vec2 {
+        let width  = width  as f32;
+        let height = height as f32;
+        Vec2::new(
+            (ndc.x  * width)  + (width  * 0.5),
+            (-ndc.y * height) + (height * 0.5),
+        )
+    }
+
+    /// Renders the depth of a triangle given in clip space without running a
+    /// fragment program. Both front and back faces are written. Used for depth
+    /// pre-passes and shadow maps.
+    #[inline(always)]
+    pub fn triangle_depth(
+        depth:      &mut DepthBuffer,
+        position_0: &Vec4,
+        position_1: &Vec4,
+        position_2: &Vec4,
+    ) {
+        Self::triangle_depth_with_options(&RasterOptions::default().cull(CullMode::None), depth, position_0, position_1, position_2)
+    }
+
+    /// Renders the depth of a triangle as Raster::triangle_depth with the
+    /// given options. Unlike Raster::triangle_depth the cull mode of the
+    /// options applies, and depth bias can be used to offset shadow maps.
+    #[inline(always)]
+    pub fn triangle_depth_with_options(
+        options:    &RasterOptions,
+        depth:      &mut DepthBuffer,
+        position_0: &Vec4,
+        position_1: &Vec4,
+        position_2: &Vec4,
+    ) {
+        let width  = depth.width()  as i32;
+        let height = depth.height() as i32;
+
+        // discard triangles with vertices behind the camera, the homogeneous
+        // divide is undefined for w <= 0.0.
+        if position_0.w <= 0.0 || position_1.w <= 0.0 || position_2.w <= 0.0 {
+            return;
+        }
+        // discard triangles crossing the near or far plane.
+        if Self::outside_depth_range(position_0) || Self::outside_depth_range(position_1) || Self::outside_depth_range(position_2) {
+            return;
+        }
+
+        // calculate positions in screen space.
+        let clippos_0 = Self::ndc_to_screen(&Self::clip_to_ndc(position_0), width, height);
+        let clippos_1 = Self::ndc_to_screen(&Self::clip_to_ndc(position_1), width, height);
+        let clippos_2 = Self::ndc_to_screen(&Self::clip_to_ndc(position_2), width, height);
+        if Self::is_culled(options, Self::edge(&clippos_0, &clippos_1, &clippos_2)) {
+            return;
+        }
+
+        let depth_0    = position_0.z / position_0.w;
+        let depth_1    = position_1.z / position_1.w;
+        let depth_2    = position_2.z / position_2.w;
+        let depth_bias = Self::triangle_depth_bias(options, &clippos_0, &clippos_1, &clippos_2, &depth_0, &depth_1, &depth_2);
+        Self::rasterize_triangle(width, height, &clippos_0, &clippos_1, &clippos_2, |x, y, weight_0, weight_1, weight_2| {
+            let calculated_depth =
+                  (weight_0 * depth_0)
+                + (weight_1 * depth_1)
+                + (weight_2 * depth_2)
+                + depth_bias;
+            if (!options.depth_test || depth.test(x as usize, y as usize, calculated_depth)) && options.depth_write {
+                depth.set(x as usize, y as usize, calculated_depth);
+            }
+        });
+    }
+
+    /// Walks the pixels covered by the given screen space triangle, calling func
+    /// with the x, y pixel coordinate and barycentric weights of each. Pixels are
+    /// clamped to the given width and height.
+    #[inline(always)]
+    pub fn rasterize_triangle<TFunc>(
+        width:     i32,
+        height:    i32,
+        clippos_0: &Vec2,
+        clippos_1: &Vec2,
+        clippos_2: &Vec2,
+        func:      TFunc,
+    ) where
+        TFunc: FnMut(i32, i32, f32, f32, f32),
+    {
+        Self::rasterize_triangle_region((0, 0, width, height), clippos_0, clippos_1, clippos_2, func)
+    }
+
+    /// Walks the pixels covered by the given screen space triangle that lie
+    /// within the region (min_x, min_y, max_x, max_y), with max exclusive.
+    #[inline(always)]
+    fn rasterize_triangle_region<TFunc>(
+        region:    (i32, i32, i32, i32),
+        clippos_0: &Vec2,
+        clippos_1: &Vec2,
+        clippos_2: &Vec2,
+        mut func:  TFunc,
+    ) where
+        TFunc: FnMut(i32, i32, f32, f32, f32),
+    {
+        let (region_min_x, region_min_y, region_max_x, region_max_y) = region;
+
+        // calculate edge value, discard if degenerate.
+        let edge = Self::edge(clippos_0, clippos_1, clippos_2);
+        if edge == 0.0 {
+            return;
+        }
+        let inverse_edge = 1.0 / edge;
+
+        // the edge function is linear in x, so weights along a scanline are
+        // evaluated from the weights at x = 0 plus a constant delta per pixel
+        // rather than being recomputed from the edges. Anchoring at x = 0
+        // keeps weights independent of the region being rasterized.
+        let step_0 = (clippos_2.y - clippos_1.y) * inverse_edge;
+        let step_1 = (clippos_0.y - clippos_2.y) * inverse_edge;
+        let step_2 = (clippos_1.y - clippos_0.y) * inverse_edge;
+
         // clone clippos for sorting.
         let mut ordered_0 = clippos_0.clone();
         let mut ordered_1 = clippos_1.clone();
@@ -146,221 +1484,212 @@ impl Raster {
             swap(&mut ordered_0, &mut ordered_1);
         }
 
-        // calculate slopes for the given triangle types.
-        //       P0
-        //       /|
-        //      / | 
-        //     /  |
-        //    /   |
-        // P1 \   | 
-        //     \  |
-        //      \ |
-        //       \|
-        //       P2
-        let slope_0 = if ordered_1.y - ordered_0.y > 0.0 {
-            (ordered_1.x - ordered_0.x) / (ordered_1.y - ordered_0.y)
-        } else {
-            0.0
-        };
-        //  P0
-        //  |\
-        //  | \
-        //  |  \
-        //  |   \
-        //  |   / P1
-        //  |  /
-        //  | /
-        //  |/
-        //  P2
-        let slope_1 = if ordered_2.y - ordered_0.y > 0.0 {
-            (ordered_2.x - ordered_0.x) / (ordered_2.y - ordered_0.y)
+        // determine which side of the long edge P0 -> P2 that P1 is on.
+        //       P0                P0
+        //       /|                |\
+        //      / |                | \
+        //     /  |                |  \
+        //    /   |                |   \
+        // P1 \   |                |   / P1
+        //     \  |                |  /
+        //      \ |                | /
+        //       \|                |/
+        //       P2                P2
+        let long_x = if ordered_2.y - ordered_0.y > 0.0 {
+            ordered_0.x + (ordered_2.x - ordered_0.x) * ((ordered_1.y - ordered_0.y) / (ordered_2.y - ordered_0.y))
         } else {
-            0.0
+            ordered_0.x
         };
+        let long_edge_left = ordered_1.x > long_x;
 
-        // draw scanlines
-        if slope_0 > slope_1 {
-            for y in ordered_0.y as i32..=ordered_2.y as i32 {
-                if (y as f32) < ordered_1.y {
-                    let (min_x, max_x) = Self::calculate_x_scan_range(
-                        y, 
-                        &ordered_0,
-                        &ordered_2,
-                        &ordered_0,
-                        &ordered_1,
-                    );
-                    Self::draw_line(
-                        fragment,
-                        depth,
-                        target,
-                        uniform,
-                        &clippos_0,
-                        &clippos_1,
-                        &clippos_2,
-                        &varying_0,
-                        &varying_1,
-                        &varying_2,
-                        &corrected_z_0,
-                        &corrected_z_1,
-                        &corrected_z_2,
-                        min_x,
-                        max_x,
-                        y,
-                    )
-                } else {
-                    let (min_x, max_x) = Self::calculate_x_scan_range(
-                        y, 
-                        &ordered_0,
-                        &ordered_2,
-                        &ordered_1,
-                        &ordered_2,
-                    );
-                    Self::draw_line(
-                        fragment,
-                        depth,
-                        target,
-                        uniform,
-                        &clippos_0,
-                        &clippos_1,
-                        &clippos_2,
-                        &varying_0,
-                        &varying_1,
-                        &varying_2,
-                        &corrected_z_0,
-                        &corrected_z_1,
-                        &corrected_z_2,
-                        min_x,
-                        max_x,
-                        y,
-                    )
-                }
+        // walk scanlines. Pixels are sampled at their centers and a scanline
+        // covers [top, bottom) vertically and [left, right) horizontally. This
+        // ensures pixels on edges shared by adjacent triangles are visited once.
+        let min_y = max((ordered_0.y - 0.5).ceil() as i32, region_min_y);
+        let max_y = min((ordered_2.y - 0.5).ceil() as i32, region_max_y);
+        for y in min_y..max_y {
+            let sample_y = (y as f32) + 0.5;
+            let (short_0, short_1) = if sample_y < ordered_1.y {
+                (&ordered_0, &ordered_1)
+            } else {
+                (&ordered_1, &ordered_2)
+            };
+            let (min_x, max_x) = if long_edge_left {
+                Self::calculate_x_scan_range(sample_y, &ordered_0, &ordered_2, short_0, short_1)
+            } else {
+                Self::calculate_x_scan_range(sample_y, short_0, short_1, &ordered_0, &ordered_2)
+            };
+
+            // min | max within region, max_x is exclusive.
+            let min_x = max(min_x, region_min_x);
+            let max_x = min(max_x, region_max_x);
+            if min_x >= max_x {
+                continue;
             }
-        } else {
-            for y in ordered_0.y as i32 ..= ordered_2.y as i32 {
-                if (y as f32) < ordered_1.y {
-                   let (min_x, max_x) = Self::calculate_x_scan_range(
-                        y, 
-                        &ordered_0,
-                        &ordered_1,
-                        &ordered_0,
-                        &ordered_2,
-                    );
-                    Self::draw_line(
-                        fragment,
-                        depth,
-                        target,
-                        uniform,
-                        &clippos_0,
-                        &clippos_1,
-                        &clippos_2,
-                        &varying_0,
-                        &varying_1,
-                        &varying_2,
-                        &corrected_z_0,
-                        &corrected_z_1,
-                        &corrected_z_2,
-                        min_x,
-                        max_x,
-                        y,
-                    )
-                } else {
-                    let (min_x, max_x) = Self::calculate_x_scan_range(
-                        y, 
-                        &ordered_1,
-                        &ordered_2,
-                        &ordered_0,
-                        &ordered_2,
-                    );
-                    Self::draw_line(
-                        fragment,
-                        depth,
-                        target,
-                        uniform,
-                        &clippos_0,
-                        &clippos_1,
-                        &clippos_2,
-                        &varying_0,
-                        &varying_1,
-                        &varying_2,
-                        &corrected_z_0,
-                        &corrected_z_1,
-                        &corrected_z_2,
-                        min_x,
-                        max_x,
-                        y,
-                    )
-                }
+            let pixel_coordinate = Vec2::new(0.5, sample_y);
+            let origin_0 = Self::edge(clippos_1, clippos_2, &pixel_coordinate) * inverse_edge;
+            let origin_1 = Self::edge(clippos_2, clippos_0, &pixel_coordinate) * inverse_edge;
+            let origin_2 = Self::edge(clippos_0, clippos_1, &pixel_coordinate) * inverse_edge;
+            for x in min_x..max_x {
+                let n0 = x as f32;
+                func(x, y, origin_0 + step_0 * n0, origin_1 + step_1 * n0, origin_2 + step_2 * n0);
             }
         }
     }
 
+    /// Walks the pixels covered by the given screen space triangle as
+    /// Raster::rasterize_triangle, visiting the same pixels with the same
+    /// weights, but by testing the edge functions of each pixel in the
+    /// triangle's bounding box rather than walking scanline spans.
     #[inline(always)]
-    fn calculate_x_scan_range(y: i32, ordered_0: &Vec2, ordered_1: &Vec2, ordered_2: &Vec2, ordered_3: &Vec2) -> (i32, i32) {
-        let gradient_0 = if ordered_0.y != ordered_1.y {
-            (y as f32 - ordered_0.y) / (ordered_1.y - ordered_0.y)
-        } else {
-            1.0
-        };
-        let gradient_1 = if ordered_2.y != ordered_3.y {
-            (y as f32 - ordered_2.y) / (ordered_3.y - ordered_2.y)
-        } else {
-            1.0
-        };
-        let min_x = ordered_0.x + (ordered_1.x - ordered_0.x) * Self::clamp(gradient_0, 0.0, 1.0);
-        let max_x = ordered_2.x + (ordered_3.x - ordered_2.x) * Self::clamp(gradient_1, 0.0, 1.0);
-        (min_x as i32, max_x as i32)
-    }
-
-    #[inline(always)]
-    fn draw_line<TTargetBuffer, TFragmentProgram, TVarying, TUniform>(
-        fragment:      &TFragmentProgram,
-        depth:         &mut DepthBuffer,
-        target:        &mut TTargetBuffer,
-        uniform:       &TUniform,
-        clippos_0:     &Vec2,
-        clippos_1:     &Vec2,
-        clippos_2:     &Vec2,
-        varying_0:     &TVarying,
-        varying_1:     &TVarying,
-        varying_2:     &TVarying,
-        corrected_z_0: &f32,
-        corrected_z_1: &f32,
-        corrected_z_2: &f32,
-        min_x:          i32,
-        max_x:          i32,
-        y:              i32,
+    pub fn rasterize_triangle_halfspace<TFunc>(
+        width:     i32,
+        height:    i32,
+        clippos_0: &Vec2,
+        clippos_1: &Vec2,
+        clippos_2: &Vec2,
+        func:      TFunc,
     ) where
-        TFragmentProgram: FragmentProgram<Uniform = TUniform, Varying = TVarying>,
-        TVarying:         Interpolate,
-        TTargetBuffer:    TargetBuffer,
+        TFunc: FnMut(i32, i32, f32, f32, f32),
+    {
+        Self::rasterize_triangle_halfspace_region((0, 0, width, height), clippos_0, clippos_1, clippos_2, func)
+    }
+
+    /// Walks the pixels covered by the given screen space triangle that lie
+    /// within the region (min_x, min_y, max_x, max_y), with max exclusive,
+    /// by testing the edge functions of each pixel in the bounding box.
+    #[inline(always)]
+    fn rasterize_triangle_halfspace_region<TFunc>(
+        region:    (i32, i32, i32, i32),
+        clippos_0: &Vec2,
+        clippos_1: &Vec2,
+        clippos_2: &Vec2,
+        mut func:  TFunc,
+    ) where
+        TFunc: FnMut(i32, i32, f32, f32, f32),
     {
-        // exit if outside viewport height.
-        if y < 0 || y >= target.height() {
+        let (region_min_x, region_min_y, region_max_x, region_max_y) = region;
+
+        // calculate edge value, discard if degenerate.
+        let edge = Self::edge(clippos_0, clippos_1, clippos_2);
+        if edge == 0.0 {
             return;
         }
-        // min | max within viewport width.
-        let min_x = max(min_x, 0);
-        let max_x = min(max_x, target.width() - 1);
+        let inverse_edge = 1.0 / edge;
 
-        // calculate edge value
-        let edge = Self::edge(clippos_0, clippos_1, clippos_2);
+        // weights are stepped along each row as in rasterize_triangle_region
+        // so both traversals produce identical weights.
+        let step_0 = (clippos_2.y - clippos_1.y) * inverse_edge;
+        let step_1 = (clippos_0.y - clippos_2.y) * inverse_edge;
+        let step_2 = (clippos_1.y - clippos_0.y) * inverse_edge;
+
+        // coverage is tested with edge functions evaluated directly at each
+        // pixel center, as stepped weights accumulate rounding error that
+        // moves pixels lying exactly on an edge. Those pixels are owned by
+        // the triangle if the edge is a top or left edge. Edge values are
+        // multiplied by the sign of the area so the interior is positive
+        // for either winding.
+        let sign = f32::signum(edge);
+        let owns_0 = Self::is_top_left(clippos_1, clippos_2, clippos_0);
+        let owns_1 = Self::is_top_left(clippos_2, clippos_0, clippos_1);
+        let owns_2 = Self::is_top_left(clippos_0, clippos_1, clippos_2);
+
+        // pixel centers within the bounding box, max exclusive.
+        let min_x = f32::min(clippos_0.x, f32::min(clippos_1.x, clippos_2.x));
+        let min_y = f32::min(clippos_0.y, f32::min(clippos_1.y, clippos_2.y));
+        let max_x = f32::max(clippos_0.x, f32::max(clippos_1.x, clippos_2.x));
+        let max_y = f32::max(clippos_0.y, f32::max(clippos_1.y, clippos_2.y));
+        let min_x = max((min_x - 0.5).ceil() as i32, region_min_x);
+        let min_y = max((min_y - 0.5).ceil() as i32, region_min_y);
+        let max_x = min((max_x - 0.5).ceil() as i32 + 1, region_max_x);
+        let max_y = min((max_y - 0.5).ceil() as i32 + 1, region_max_y);
+        for y in min_y..max_y {
+            let pixel_coordinate = Vec2::new(0.5, (y as f32) + 0.5);
+            let origin_0 = Self::edge(clippos_1, clippos_2, &pixel_coordinate) * inverse_edge;
+            let origin_1 = Self::edge(clippos_2, clippos_0, &pixel_coordinate) * inverse_edge;
+            let origin_2 = Self::edge(clippos_0, clippos_1, &pixel_coordinate) * inverse_edge;
+            for x in min_x..max_x {
+                let n0 = x as f32;
+                let sample = Vec2::new(n0 + 0.5, pixel_coordinate.y);
+                let edge_0 = Self::edge(clippos_1, clippos_2, &sample) * sign;
+                let edge_1 = Self::edge(clippos_2, clippos_0, &sample) * sign;
+                let edge_2 = Self::edge(clippos_0, clippos_1, &sample) * sign;
+                let inside_0 = edge_0 > 0.0 || (edge_0 == 0.0 && owns_0);
+                let inside_1 = edge_1 > 0.0 || (edge_1 == 0.0 && owns_1);
+                let inside_2 = edge_2 > 0.0 || (edge_2 == 0.0 && owns_2);
+                if inside_0 && inside_1 && inside_2 {
+                    func(x, y, origin_0 + step_0 * n0, origin_1 + step_1 * n0, origin_2 + step_2 * n0);
+                }
+            }
+        }
+    }
+
+    /// Returns true if the edge v0 -> v1 is a top or left edge of the
+    /// triangle with opposite vertex v2. Screen space y points down, so a
+    /// top edge is horizontal with the triangle below it and a left edge
+    /// has the triangle to its right.
+    #[inline(always)]
+    fn is_top_left(v0: &Vec2, v1: &Vec2, v2: &Vec2) -> bool {
+        // outward normal of the edge, pointing away from v2.
+        let mut normal = Vec2::new(v1.y - v0.y, v0.x - v1.x);
+        if Vec2::dot(&normal, &(v2 - v0)) > 0.0 {
+            normal *= -1.0;
+        }
+        normal.x < 0.0 || (normal.x == 0.0 && normal.y < 0.0)
+    }
+
+    #[inline(always)]
+    fn draw_triangle<TVarying, TFunc>(
+        options:  &RasterOptions,
+        stats:    &mut RasterStats,
+        depth:    &mut DepthBuffer,
+        region:   (i32, i32, i32, i32),
+        setup:    &TriangleSetup<TVarying>,
+        mut func: TFunc,
+    ) where
+        TVarying: Interpolate,
+        TFunc:    FnMut(i32, i32, &TVarying, &[f32; 3]) -> bool,
+    {
+        let TriangleSetup {
+            varying_0,
+            varying_1,
+            varying_2,
+            clippos_0,
+            clippos_1,
+            clippos_2,
+            reciprocal_w_0,
+            reciprocal_w_1,
+            reciprocal_w_2,
+            depth_0,
+            depth_1,
+            depth_2,
+        } = setup;
+
+        // calculate depth bias for this triangle.
+        let depth_bias = Self::triangle_depth_bias(options, clippos_0, clippos_1, clippos_2, depth_0, depth_1, depth_2);
 
-        for x in min_x..max_x {
-            // calculate weights
-            let pixel_coordinate = Vec2::new((x as f32) + 0.0, (y as f32) + 0.0);
-            let weight_0 = Self::edge(clippos_2, clippos_1, &pixel_coordinate) / edge;
-            let weight_1 = Self::edge(clippos_0, clippos_2, &pixel_coordinate) / edge;
-            let weight_2 = Self::edge(clippos_1, clippos_0, &pixel_coordinate) / edge;
+        let shade = |x, y, weight_0: f32, weight_1: f32, weight_2: f32| {
+            // clamp weights of pixels on edges back inside the triangle.
+            let (weight_0, weight_1, weight_2) = if options.clamp_weights {
+                Self::clamp_weights(weight_0, weight_1, weight_2)
+            } else {
+                (weight_0, weight_1, weight_2)
+            };
 
             // calculate depth of fragment.
-            let calculated_depth = 
-                  (weight_0 * corrected_z_0)
-                + (weight_1 * corrected_z_1)
-                + (weight_2 * corrected_z_2);
+            let calculated_depth =
+                  (weight_0 * depth_0)
+                + (weight_1 * depth_1)
+                + (weight_2 * depth_2);
 
-            // check depth and discard, interpolate and render.
-            if calculated_depth < depth.get(x as usize, y as usize) {
-                depth.set(x as usize, y as usize, calculated_depth);
+            // the biased depth is both tested and written, as with polygon
+            // offset on the gpu, so coplanar geometry drawn later compares
+            // against the biased value.
+            let biased_depth = calculated_depth + depth_bias;
+
+            // check depth and discard, interpolate and render. Depth is
+            // only written if the fragment was not discarded.
+            if !options.depth_test || depth.test(x as usize, y as usize, biased_depth) {
                 let varying = TVarying::interpolate(
                     varying_0,
                     varying_1,
@@ -368,11 +1697,109 @@ impl Raster {
                     &weight_0,
                     &weight_1,
                     &weight_2,
-                    &calculated_depth,
+                    &((weight_0 * reciprocal_w_0) + (weight_1 * reciprocal_w_1) + (weight_2 * reciprocal_w_2)),
                 );
-                let color = fragment.main(uniform, &varying);
-                target.set(x, y, color);
+                if func(x, y, &varying, &[weight_0, weight_1, weight_2]) {
+                    stats.pixels_shaded += 1;
+                    if options.depth_write {
+                        depth.set(x as usize, y as usize, biased_depth);
+                    }
+                }
+            } else {
+                stats.depth_rejects += 1;
             }
+        };
+        match options.traversal {
+            Traversal::Scanline  => Self::rasterize_triangle_region(region, clippos_0, clippos_1, clippos_2, shade),
+            Traversal::HalfSpace => Self::rasterize_triangle_halfspace_region(region, clippos_0, clippos_1, clippos_2, shade),
+        }
+    }
+
+    #[inline(always)]
+    fn calculate_x_scan_range(y: f32, ordered_0: &Vec2, ordered_1: &Vec2, ordered_2: &Vec2, ordered_3: &Vec2) -> (i32, i32) {
+        let gradient_0 = if ordered_0.y != ordered_1.y {
+            (y - ordered_0.y) / (ordered_1.y - ordered_0.y)
+        } else {
+            1.0
+        };
+        let gradient_1 = if ordered_2.y != ordered_3.y {
+            (y - ordered_2.y) / (ordered_3.y - ordered_2.y)
+        } else {
+            1.0
+        };
+        let min_x = ordered_0.x + (ordered_1.x - ordered_0.x) * Self::clamp(gradient_0, 0.0, 1.0);
+        let max_x = ordered_2.x + (ordered_3.x - ordered_2.x) * Self::clamp(gradient_1, 0.0, 1.0);
+
+        // pixel centers on the left edge are included, right edge excluded.
+        ((min_x - 0.5).ceil() as i32, (max_x - 0.5).ceil() as i32)
+    }
+
+    /// Returns the constant and slope scaled depth bias of the options for
+    /// a triangle.
+    #[inline(always)]
+    fn triangle_depth_bias(
+        options:   &RasterOptions,
+        clippos_0: &Vec2,
+        clippos_1: &Vec2,
+        clippos_2: &Vec2,
+        depth_0:   &f32,
+        depth_1:   &f32,
+        depth_2:   &f32,
+    ) -> f32 {
+        options.depth_bias + options.slope_scaled_depth_bias * Self::depth_slope(clippos_0, clippos_1, clippos_2, depth_0, depth_1, depth_2)
+    }
+
+    /// Returns true if a triangle with the given screen space edge value is
+    /// culled by the cull mode and winding of the options. Screen space y is
+    /// flipped so counter clockwise triangles have a positive edge value.
+    #[inline(always)]
+    fn is_culled(options: &RasterOptions, edge: f32) -> bool {
+        let front_facing = match options.winding {
+            Winding::CounterClockwise => edge > 0.0,
+            Winding::Clockwise        => edge < 0.0,
+        };
+        match options.cull {
+            CullMode::None  => false,
+            CullMode::Front => front_facing,
+            CullMode::Back  => !front_facing,
+        }
+    }
+
+    /// Returns the maximum screen space slope of the depth values written
+    /// for this triangle. Used to compute slope scaled depth bias.
+    #[inline(always)]
+    fn depth_slope(
+        clippos_0: &Vec2,
+        clippos_1: &Vec2,
+        clippos_2: &Vec2,
+        depth_0:   &f32,
+        depth_1:   &f32,
+        depth_2:   &f32,
+    ) -> f32 {
+        let e1 = clippos_1 - clippos_0;
+        let e2 = clippos_2 - clippos_0;
+        let d1 = depth_1 - depth_0;
+        let d2 = depth_2 - depth_0;
+        let determinant = (e1.x * e2.y) - (e2.x * e1.y);
+        if determinant == 0.0 {
+            return 0.0;
+        }
+        let dx = ((d1 * e2.y) - (d2 * e1.y)) / determinant;
+        let dy = ((d2 * e1.x) - (d1 * e2.x)) / determinant;
+        f32::max(f32::abs(dx), f32::abs(dy))
+    }
+
+    /// Clamps barycentric weights to non-negative values that sum to 1.0.
+    #[inline(always)]
+    fn clamp_weights(weight_0: f32, weight_1: f32, weight_2: f32) -> (f32, f32, f32) {
+        let weight_0 = f32::max(weight_0, 0.0);
+        let weight_1 = f32::max(weight_1, 0.0);
+        let weight_2 = f32::max(weight_2, 0.0);
+        let sum = weight_0 + weight_1 + weight_2;
+        if sum > 0.0 {
+            (weight_0 / sum, weight_1 / sum, weight_2 / sum)
+        } else {
+            (1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0)
         }
     }
 
@@ -383,6 +1810,6 @@ impl Raster {
 
     #[inline(always)]
     fn edge(v0: &Vec2, v1: &Vec2, v2: &Vec2) -> f32 {
-        (v2.x - v0.x) * (v1.y - v0.y) - (v2.y - v0.y) * (v1.x - v0.x)
+        Vec2::cross(&(v2 - v0), &(v1 - v0))
     }
 }
@@ -0,0 +1,106 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+/// Rect
+///
+/// An axis aligned rectangle in screen space pixels. The rectangle covers
+/// x to x + width exclusive and y to y + height exclusive, and is empty if
+/// either width or height is zero or negative.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x:      i32,
+    pub y:      i32,
+    pub width:  i32,
+    pub height: i32,
+}
+impl Rect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Rect {
+        Rect { x, y, width, height }
+    }
+
+    /// Returns a rectangle at the origin with the given size, such as the
+    /// full viewport of a target.
+    pub fn from_size(width: i32, height: i32) -> Rect {
+        Rect::new(0, 0, width, height)
+    }
+
+    /// Returns the exclusive right edge, x + width.
+    #[inline(always)]
+    pub fn right(&self) -> i32 {
+        self.x + self.width
+    }
+
+    /// Returns the exclusive bottom edge, y + height.
+    #[inline(always)]
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height
+    }
+
+    /// Returns true if this rectangle covers no pixels.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Returns true if the pixel at x, y lies within this rectangle.
+    #[inline(always)]
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.right() && y < self.bottom()
+    }
+
+    /// Returns the overlap of this rectangle and other. Returns an empty
+    /// rectangle if they do not overlap.
+    #[inline(always)]
+    pub fn intersect(&self, other: &Rect) -> Rect {
+        let min_x = i32::max(self.x, other.x);
+        let min_y = i32::max(self.y, other.y);
+        let max_x = i32::min(self.right(), other.right());
+        let max_y = i32::min(self.bottom(), other.bottom());
+        if self.is_empty() || other.is_empty() || min_x >= max_x || min_y >= max_y {
+            return Rect::default();
+        }
+        Rect::new(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
+
+    /// Returns the smallest rectangle containing both this rectangle and
+    /// other. Empty rectangles are ignored.
+    #[inline(always)]
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return if other.is_empty() { Rect::default() } else { *other };
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        let min_x = i32::min(self.x, other.x);
+        let min_y = i32::min(self.y, other.y);
+        let max_x = i32::max(self.right(), other.right());
+        let max_y = i32::max(self.bottom(), other.bottom());
+        Rect::new(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
+}
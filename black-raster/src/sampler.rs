@@ -0,0 +1,152 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Vec2, Vec4};
+
+/// Sampler
+/// 
+/// Returns a color for the given texture coordinate. Fragment programs
+/// may be written generically over this trait to support textures
+/// and procedural sources interchangeably.
+pub trait Sampler {
+    fn sample(&self, uv: &Vec2) -> Vec4;
+
+    /// Samples with the given wrap mode applied to the coordinate.
+    #[inline(always)]
+    fn sample_wrapped(&self, uv: &Vec2, wrap: WrapMode) -> Vec4 {
+        self.sample(&wrap.apply_uv(uv))
+    }
+}
+
+/// WrapMode
+/// 
+/// Specifies how texture coordinates outside the 0.0 to 1.0 range
+/// are mapped back into range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Tiles the texture, 1.25 maps to 0.25.
+    Repeat,
+    /// Clamps to the edge, 1.25 maps to 1.0.
+    Clamp,
+    /// Tiles the texture, flipping every other tile, 1.25 maps to 0.75.
+    Mirror,
+}
+impl WrapMode {
+    #[inline(always)]
+    pub fn apply(&self, value: f32) -> f32 {
+        match self {
+            WrapMode::Repeat => value - value.floor(),
+            WrapMode::Clamp  => value.clamp(0.0, 1.0),
+            WrapMode::Mirror => {
+                let n0 = value.rem_euclid(2.0);
+                if n0 > 1.0 { 2.0 - n0 } else { n0 }
+            }
+        }
+    }
+    #[inline(always)]
+    pub fn apply_uv(&self, uv: &Vec2) -> Vec2 {
+        Vec2::new(self.apply(uv.x), self.apply(uv.y))
+    }
+}
+
+/// A sampler returning a single color for all coordinates.
+#[derive(Debug, Clone, Copy)]
+pub struct SolidSampler {
+    pub color: Vec4,
+}
+impl SolidSampler {
+    pub fn new(color: Vec4) -> SolidSampler {
+        SolidSampler { color }
+    }
+}
+impl Sampler for SolidSampler {
+    #[inline(always)]
+    fn sample(&self, _uv: &Vec2) -> Vec4 {
+        self.color
+    }
+}
+
+/// A sampler linearly interpolating between two colors along u.
+#[derive(Debug, Clone, Copy)]
+pub struct GradientSampler {
+    pub color_0: Vec4,
+    pub color_1: Vec4,
+    pub wrap:    WrapMode,
+}
+impl GradientSampler {
+    pub fn new(color_0: Vec4, color_1: Vec4) -> GradientSampler {
+        GradientSampler { color_0, color_1, wrap: WrapMode::Clamp }
+    }
+}
+impl Sampler for GradientSampler {
+    #[inline(always)]
+    fn sample(&self, uv: &Vec2) -> Vec4 {
+        Vec4::lerp(&self.color_0, &self.color_1, self.wrap.apply(uv.x))
+    }
+}
+
+/// A bilinear filtered checkerboard with frequency cells along each axis.
+#[derive(Debug, Clone, Copy)]
+pub struct CheckerSampler {
+    pub frequency: f32,
+    pub color_0:   Vec4,
+    pub color_1:   Vec4,
+    pub wrap:      WrapMode,
+}
+impl CheckerSampler {
+    pub fn new(frequency: f32, color_0: Vec4, color_1: Vec4) -> CheckerSampler {
+        CheckerSampler { frequency, color_0, color_1, wrap: WrapMode::Repeat }
+    }
+
+    #[inline(always)]
+    fn cell(&self, x: i32, y: i32) -> Vec4 {
+        if (x + y).rem_euclid(2) == 0 {
+            self.color_0
+        } else {
+            self.color_1
+        }
+    }
+}
+impl Sampler for CheckerSampler {
+    #[inline(always)]
+    fn sample(&self, uv: &Vec2) -> Vec4 {
+        // offset by half a cell so cell centers sample unfiltered.
+        let uv = self.wrap.apply_uv(uv);
+        let x  = (uv.x * self.frequency) - 0.5;
+        let y  = (uv.y * self.frequency) - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let x0 = x0 as i32;
+        let y0 = y0 as i32;
+        let top    = Vec4::lerp(&self.cell(x0, y0),     &self.cell(x0 + 1, y0),     fx);
+        let bottom = Vec4::lerp(&self.cell(x0, y0 + 1), &self.cell(x0 + 1, y0 + 1), fx);
+        Vec4::lerp(&top, &bottom, fy)
+    }
+}
@@ -0,0 +1,103 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Mat4, Vec3};
+
+use super::DepthBuffer;
+use super::Mesh;
+use super::Raster;
+
+/// ShadowMap
+/// 
+/// A depth only render target rendered from the point of view of a
+/// light. Sampling the map with a world position returns 1.0 if the
+/// position is occluded from the light, and 0.0 if it is lit.
+pub struct ShadowMap {
+    pub depth:  DepthBuffer,
+    pub matrix: Mat4,
+    pub bias:   f32,
+}
+impl ShadowMap {
+    pub fn new(width: usize, height: usize) -> ShadowMap {
+        let mut depth = DepthBuffer::new(width, height);
+        depth.clear();
+        ShadowMap { depth, matrix: Mat4::identity(), bias: 0.0001 }
+    }
+
+    /// Clears the map and renders the given mesh into it using the light's
+    /// view projection matrix. The position function returns the world
+    /// position for each vertex.
+    pub fn render<TVertex, TFunc>(&mut self, mesh: &Mesh<TVertex>, light_vp: &Mat4, position: TFunc)
+    where
+        TFunc: Fn(&TVertex) -> Vec3,
+    {
+        self.matrix = *light_vp;
+        self.depth.clear();
+        self.render_additional(mesh, position);
+    }
+
+    /// Renders an additional mesh into the map without clearing it. Uses the
+    /// view projection matrix from the last call to render.
+    pub fn render_additional<TVertex, TFunc>(&mut self, mesh: &Mesh<TVertex>, position: TFunc)
+    where
+        TFunc: Fn(&TVertex) -> Vec3,
+    {
+        for triangle in mesh.indices.chunks_exact(3) {
+            let position_0 = position(&mesh.vertices[triangle[0] as usize]).xyzw() * self.matrix;
+            let position_1 = position(&mesh.vertices[triangle[1] as usize]).xyzw() * self.matrix;
+            let position_2 = position(&mesh.vertices[triangle[2] as usize]).xyzw() * self.matrix;
+            Raster::triangle_depth(&mut self.depth, &position_0, &position_1, &position_2);
+        }
+    }
+
+    /// Returns 1.0 if the given world position is occluded from the light,
+    /// otherwise 0.0. Positions outside the map are treated as lit.
+    pub fn sample(&self, world_pos: &Vec3) -> f32 {
+        let position = world_pos.xyzw() * self.matrix;
+        if position.z <= 0.0 {
+            return 0.0;
+        }
+        let width  = self.depth.width()  as i32;
+        let height = self.depth.height() as i32;
+        let screen = Raster::ndc_to_screen(&Raster::clip_to_ndc(&position), width, height);
+        let x = screen.x.floor();
+        let y = screen.y.floor();
+        if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+            return 0.0;
+        }
+        let depth = position.z / position.w;
+        // occluded if the stored depth would pass the depth test against
+        // this position, that is, something nearer the light was written.
+        let stored = self.depth.get(x as usize, y as usize);
+        if self.depth.config.func.test(stored, depth - self.bias) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
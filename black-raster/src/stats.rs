@@ -0,0 +1,52 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+/// RasterStats
+///
+/// Counters accumulated by the rasterizer over one or more draw calls. Pass
+/// the same RasterStats to several draws to accumulate a frame total, or
+/// reset it with RasterStats::default().
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RasterStats {
+    /// Triangles passed to the rasterizer.
+    pub triangles_submitted: u32,
+    /// Triangles discarded as back facing or degenerate.
+    pub triangles_culled: u32,
+    /// Triangles discarded for having vertices behind the camera or
+    /// crossing the near plane.
+    pub triangles_clipped: u32,
+    /// Fragments written to the target.
+    pub pixels_shaded: u32,
+    /// Fragments that failed the depth test.
+    pub depth_rejects: u32,
+}
+impl RasterStats {
+    pub fn new() -> RasterStats {
+        RasterStats::default()
+    }
+}
@@ -0,0 +1,130 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black_math::{Vec2, Vec4};
+
+use super::Sampler;
+use super::WrapMode;
+
+/// Texture2D
+///
+/// A grid of colors stored in row major order, sampled with bilinear
+/// filtering. Texel centers lie at half texel offsets, so uv (0.5 / width,
+/// 0.5 / height) returns the first texel unfiltered. Coordinates outside
+/// the texture are mapped back with wrap.
+#[derive(Debug, Clone)]
+pub struct Texture2D {
+    pub data:   Vec<Vec4>,
+    pub width:  usize,
+    pub height: usize,
+    pub wrap:   WrapMode,
+}
+impl Texture2D {
+    /// Creates a texture of the given size filled with zero.
+    pub fn new(width: usize, height: usize) -> Texture2D {
+        Texture2D::from_data(width, height, vec![Vec4::zero(); width * height])
+    }
+
+    /// Creates a texture from row major data. Panics if the data length
+    /// is not width * height.
+    pub fn from_data(width: usize, height: usize, data: Vec<Vec4>) -> Texture2D {
+        assert_eq!(data.len(), width * height, "texture data length does not match {}x{}", width, height);
+        Texture2D { data, width, height, wrap: WrapMode::Repeat }
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, x: usize, y: usize, value: Vec4) {
+        self.data[x + y * self.width] = value;
+    }
+
+    #[inline(always)]
+    pub fn get(&self, x: usize, y: usize) -> Vec4 {
+        self.data[x + y * self.width]
+    }
+
+    /// Returns the texel at x, y with the wrap mode applied to out of
+    /// range coordinates.
+    #[inline(always)]
+    pub fn texel(&self, x: i32, y: i32) -> Vec4 {
+        let x = Self::wrap_index(self.wrap, x, self.width as i32);
+        let y = Self::wrap_index(self.wrap, y, self.height as i32);
+        self.get(x, y)
+    }
+
+    /// Computes the mipmap level of detail from the screen space uv
+    /// derivatives of a fragment, as returned by `Quad::ddx` and
+    /// `Quad::ddy`. The derivatives are scaled to texels and the level
+    /// is log2 of the longer footprint axis. Values below 0.0 indicate
+    /// magnification and are not clamped; zero derivatives return
+    /// negative infinity. Callers clamp to the available levels.
+    #[inline(always)]
+    pub fn compute_lod(ddx_uv: &Vec2, ddy_uv: &Vec2, texture_size: &Vec2) -> f32 {
+        let n0 = Vec2::length(&Vec2::mul(ddx_uv, texture_size));
+        let n1 = Vec2::length(&Vec2::mul(ddy_uv, texture_size));
+        n0.max(n1).log2()
+    }
+
+    /// Computes the level of detail for this texture. See `compute_lod`.
+    #[inline(always)]
+    pub fn lod(&self, ddx_uv: &Vec2, ddy_uv: &Vec2) -> f32 {
+        Self::compute_lod(ddx_uv, ddy_uv, &Vec2::new(self.width as f32, self.height as f32))
+    }
+
+    #[inline(always)]
+    fn wrap_index(wrap: WrapMode, index: i32, size: i32) -> usize {
+        let index = match wrap {
+            WrapMode::Repeat => index.rem_euclid(size),
+            WrapMode::Clamp  => index.clamp(0, size - 1),
+            WrapMode::Mirror => {
+                let n0 = index.rem_euclid(size * 2);
+                if n0 >= size { (size * 2) - 1 - n0 } else { n0 }
+            }
+        };
+        index as usize
+    }
+}
+impl Sampler for Texture2D {
+    #[inline(always)]
+    fn sample(&self, uv: &Vec2) -> Vec4 {
+        // wrap first so huge or non finite coordinates cannot overflow
+        // the texel index, then offset by half a texel so texel centers
+        // sample unfiltered.
+        let uv = self.wrap.apply_uv(uv);
+        let x  = (uv.x * self.width  as f32) - 0.5;
+        let y  = (uv.y * self.height as f32) - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let x0 = x0 as i32;
+        let y0 = y0 as i32;
+        let top    = Vec4::lerp(&self.texel(x0, y0),     &self.texel(x0 + 1, y0),     fx);
+        let bottom = Vec4::lerp(&self.texel(x0, y0 + 1), &self.texel(x0 + 1, y0 + 1), fx);
+        Vec4::lerp(&top, &bottom, fy)
+    }
+}
@@ -35,6 +35,7 @@ extern crate black_raster as raster;
 pub use {
     math::Mat4,
     math::BoundingBox,
+    math::Bvh,
     math::Frustum,
     math::Plane,
     math::Vec2,
@@ -44,13 +45,47 @@ pub use {
     math::Ray,
     math::Sphere,
     math::Triangle,
+    math::Winding,
+    math::Rng,
+    math::noise,
+    math::skinning,
 };
 
 pub use {
     raster::FragmentProgram,
+    raster::FragmentProgramMRT,
+    raster::QuadFragmentProgram,
+    raster::Quad,
     raster::VertexProgram,
     raster::Interpolate,
+    raster::Buffer2D,
     raster::DepthBuffer,
+    raster::DepthConfig,
+    raster::DepthFunc,
     raster::TargetBuffer,
     raster::Raster,
-};
\ No newline at end of file
+    raster::RasterOptions,
+    raster::CullMode,
+    raster::Traversal,
+    raster::RasterStats,
+    raster::RasterError,
+    raster::Mesh,
+    raster::ShadowMap,
+    raster::DirtyRegion,
+    raster::Sampler,
+    raster::WrapMode,
+    raster::SolidSampler,
+    raster::GradientSampler,
+    raster::CheckerSampler,
+    raster::Texture2D,
+    raster::Cubemap,
+    raster::Draw2D,
+    raster::ImageBuffer,
+    raster::Color,
+    raster::Light,
+    raster::Rect,
+    raster::Dither,
+};
+
+#[cfg(feature = "rayon")]
+pub use raster::AtomicDepthBuffer;
\ No newline at end of file
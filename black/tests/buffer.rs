@@ -0,0 +1,80 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{Buffer2D, DepthBuffer, ImageBuffer, Vec4};
+
+#[test]
+fn get_and_set() {
+    let mut buffer: Buffer2D<u32> = Buffer2D::new(4, 3);
+    assert_eq!(buffer.data.len(), 12);
+    buffer.set(3, 2, 7);
+    assert_eq!(buffer.get(3, 2), 7);
+    assert_eq!(buffer.data[3 + 2 * 4], 7);
+    assert_eq!(buffer.get(0, 0), 0);
+}
+
+#[test]
+fn checked_get_and_set() {
+    let mut buffer: Buffer2D<f32> = Buffer2D::new(4, 3);
+    assert_eq!(buffer.try_set(1, 1, 0.5), Some(()));
+    assert_eq!(buffer.try_get(1, 1), Some(0.5));
+    assert_eq!(buffer.try_set(4, 0, 0.5), None);
+    assert_eq!(buffer.try_get(0, 3), None);
+}
+
+#[test]
+fn stencil_clear_and_fill() {
+    let mut stencil: Buffer2D<u8> = Buffer2D::new(8, 8);
+    stencil.clear(1);
+    assert!(stencil.data.iter().all(|n| *n == 1));
+    stencil.fill(6, 6, 4, 4, 2);
+    for y in 0..8 {
+        for x in 0..8 {
+            let expect = if x >= 6 && y >= 6 { 2 } else { 1 };
+            assert_eq!(stencil.get(x, y), expect);
+        }
+    }
+}
+
+#[test]
+fn clear_fills_full_hd_buffers() {
+    // larger than one parallel clear chunk, with a partial last chunk.
+    let (width, height) = (1920, 1081);
+    let mut buffer: Buffer2D<u32> = Buffer2D::new(width, height);
+    buffer.clear(0xFF00FF);
+    assert!(buffer.data.iter().all(|n| *n == 0xFF00FF));
+
+    let mut depth = DepthBuffer::new_tiled(width, height);
+    depth.clear_to(0.25);
+    assert!((0..height).all(|y| (0..width).all(|x| depth.get(x, y) == 0.25)));
+
+    let color = Vec4::new(0.1, 0.2, 0.3, 1.0);
+    let mut image = ImageBuffer::new(width, height);
+    image.clear(color);
+    assert!(image.data.iter().all(|n| *n == color));
+}
@@ -0,0 +1,57 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{Color, Vec4};
+
+#[test]
+fn from_hex_red() {
+    assert_eq!(Color::from_hex(0xFF0000), Color::new(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(Color::from_hex(0x00FF00), Color::rgb(0, 255, 0));
+    assert_eq!(Color::from_hex(0x0000FF).to_vec4(), Vec4::new(0.0, 0.0, 1.0, 1.0));
+}
+
+#[test]
+fn rgba_scales_bytes() {
+    let color = Color::rgba(255, 0, 51, 0);
+    assert_eq!(color, Color::new(1.0, 0.0, 0.2, 0.0));
+}
+
+#[test]
+fn vec4_round_trip() {
+    let v = Vec4::new(0.1, 0.2, 0.3, 0.4);
+    assert_eq!(Color::from_vec4(&v).to_vec4(), v);
+    let c: Color = v.into();
+    let back: Vec4 = c.into();
+    assert_eq!(back, v);
+}
+
+#[test]
+fn to_u32_packs_argb_and_clamps() {
+    assert_eq!(Color::from_hex(0x112233).to_u32(), 0xFF112233);
+    assert_eq!(Color::new(2.0, -1.0, 0.5, 1.0).to_u32(), 0xFFFF007F);
+}
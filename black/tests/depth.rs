@@ -0,0 +1,256 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{DepthBuffer, Rect};
+#[cfg(feature = "rayon")]
+use black::{AtomicDepthBuffer, DepthConfig, Raster, Rng, Vec2, Vec3};
+
+fn all_equal(depth: &DepthBuffer, value: f32) -> bool {
+    (0..depth.height()).all(|y| (0..depth.width()).all(|x| depth.get(x, y) == value))
+}
+
+#[test]
+fn new_buffers_start_at_clear_value() {
+    let flat  = DepthBuffer::new(13, 9);
+    let tiled = DepthBuffer::new_tiled(13, 9);
+    assert!(all_equal(&flat, flat.config.clear_value));
+    assert!(all_equal(&tiled, tiled.config.clear_value));
+    assert!(tiled.test(12, 8, 0.5));
+}
+
+#[test]
+fn clear_fills_with_max() {
+    let mut depth = DepthBuffer::new(16, 8);
+    depth.set(3, 4, 0.5);
+    depth.clear();
+    assert_eq!((depth.width(), depth.height()), (16, 8));
+    assert!(all_equal(&depth, f32::MAX));
+}
+
+#[test]
+fn clear_to_fills_with_value() {
+    let mut depth = DepthBuffer::new(16, 8);
+    depth.clear_to(0.25);
+    assert!(all_equal(&depth, 0.25));
+}
+
+#[test]
+fn clear_to_fills_flat_and_tiled_buffers() {
+    for mut depth in [DepthBuffer::new(37, 21), DepthBuffer::new_tiled(37, 21)] {
+        for y in 0..21 {
+            for x in 0..37 {
+                depth.set(x, y, (x * y) as f32);
+            }
+        }
+        depth.clear_to(0.75);
+        assert!(all_equal(&depth, 0.75));
+        depth.clear();
+        assert!(all_equal(&depth, f32::MAX));
+    }
+}
+
+#[test]
+fn tiled_get_returns_set_value() {
+    let mut depth = DepthBuffer::new_tiled(19, 11);
+    depth.clear();
+    for y in 0..11 {
+        for x in 0..19 {
+            depth.set(x, y, (x + y * 19) as f32);
+        }
+    }
+    assert!(depth.is_tiled());
+    for y in 0..11 {
+        for x in 0..19 {
+            assert_eq!(depth.get(x, y), (x + y * 19) as f32);
+        }
+    }
+    assert_eq!(depth.try_get(19, 0), None);
+    assert_eq!(depth.try_set(0, 11, 0.0), None);
+}
+
+#[test]
+fn tiled_fill_writes_region_only() {
+    let mut depth = DepthBuffer::new_tiled(19, 11);
+    depth.clear_to(1.0);
+    depth.fill(6, 5, 20, 3, 0.5);
+    for y in 0..11 {
+        for x in 0..19 {
+            let expect = if x >= 6 && (5..8).contains(&y) { 0.5 } else { 1.0 };
+            assert_eq!(depth.get(x, y), expect);
+        }
+    }
+}
+
+fn gradient(mut depth: DepthBuffer) -> DepthBuffer {
+    for y in 0..depth.height() {
+        for x in 0..depth.width() {
+            depth.set(x, y, (x + y * 100) as f32);
+        }
+    }
+    depth
+}
+
+#[test]
+fn copy_from_copies_all_values() {
+    let source = gradient(DepthBuffer::new(13, 9));
+    for mut copy in [DepthBuffer::new(13, 9), DepthBuffer::new_tiled(13, 9)] {
+        copy.clear();
+        copy.copy_from(&source);
+        for y in 0..9 {
+            for x in 0..13 {
+                assert_eq!(copy.get(x, y), source.get(x, y));
+            }
+        }
+    }
+    let mut copy = DepthBuffer::new(13, 9);
+    copy.copy_from(&gradient(DepthBuffer::new_tiled(13, 9)));
+    for y in 0..9 {
+        for x in 0..13 {
+            assert_eq!(copy.get(x, y), source.get(x, y));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn copy_from_panics_on_size_mismatch() {
+    DepthBuffer::new(8, 8).copy_from(&DepthBuffer::new(8, 4));
+}
+
+#[test]
+fn blit_region_places_sub_rect_at_offset() {
+    let source = gradient(DepthBuffer::new(16, 16));
+    let mut depth = DepthBuffer::new(16, 16);
+    depth.clear_to(-1.0);
+    depth.blit_region(&source, &Rect::new(2, 3, 4, 5), 10, 1);
+    for y in 0..16 {
+        for x in 0..16 {
+            let inside = (10..14).contains(&x) && (1..6).contains(&y);
+            let expect = if inside { source.get(x - 8, y + 2) } else { -1.0 };
+            assert_eq!(depth.get(x, y), expect, "at {}, {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn blit_region_clips_to_both_buffers() {
+    let source = gradient(DepthBuffer::new(8, 8));
+    let mut depth = DepthBuffer::new(6, 6);
+    depth.clear_to(-1.0);
+    // the source rect overhangs the source, and the destination overhangs depth.
+    depth.blit_region(&source, &Rect::new(-2, 4, 6, 8), 3, 2);
+    for y in 0..6 {
+        for x in 0..6 {
+            // source x in 0..4 maps to x + 5, source y in 4..8 maps to y - 2.
+            let inside = (5..6).contains(&x) && (2..6).contains(&y);
+            let expect = if inside { source.get(x - 5, y + 2) } else { -1.0 };
+            assert_eq!(depth.get(x, y), expect, "at {}, {}", x, y);
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+type DepthTriangle = (Vec2, Vec2, Vec2, Vec3);
+
+#[cfg(feature = "rayon")]
+fn depth_triangles(count: usize) -> Vec<DepthTriangle> {
+    let mut rng = Rng::new(7);
+    (0..count).map(|_| {
+        let p0 = Vec2::new(rng.next_range(-8.0, 72.0), rng.next_range(-8.0, 72.0));
+        let p1 = Vec2::new(rng.next_range(-8.0, 72.0), rng.next_range(-8.0, 72.0));
+        let p2 = Vec2::new(rng.next_range(-8.0, 72.0), rng.next_range(-8.0, 72.0));
+        let z  = Vec3::new(rng.next_range(-1.0, 1.0), rng.next_range(-1.0, 1.0), rng.next_range(-1.0, 1.0));
+        (p0, p1, p2, z)
+    }).collect()
+}
+
+#[cfg(feature = "rayon")]
+fn draw_depth_triangle<F: FnMut(usize, usize, f32)>(triangle: &DepthTriangle, mut func: F) {
+    let (p0, p1, p2, z) = triangle;
+    Raster::rasterize_triangle(64, 64, p0, p1, p2, |x, y, w0, w1, w2| {
+        func(x as usize, y as usize, (z.x * w0) + (z.y * w1) + (z.z * w2));
+    });
+    Raster::rasterize_triangle(64, 64, p0, p2, p1, |x, y, w0, w2, w1| {
+        func(x as usize, y as usize, (z.x * w0) + (z.y * w1) + (z.z * w2));
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn atomic_depth_matches_serial_under_contention() {
+    let triangles = depth_triangles(512);
+    for config in [DepthConfig::default(), DepthConfig::reverse_z()] {
+        let mut expected = DepthBuffer::new(64, 64).with_config(config);
+        for triangle in &triangles {
+            draw_depth_triangle(triangle, |x, y, depth| {
+                if expected.test(x, y, depth) {
+                    expected.set(x, y, depth);
+                }
+            });
+        }
+        let atomic = AtomicDepthBuffer::new(64, 64).with_config(config);
+        std::thread::scope(|scope| {
+            for thread in 0..8 {
+                let atomic    = &atomic;
+                let triangles = &triangles;
+                scope.spawn(move || {
+                    // every thread draws every triangle, in a different order.
+                    for n in 0..triangles.len() {
+                        let triangle = &triangles[(n * 7 + thread * 61) % triangles.len()];
+                        draw_depth_triangle(triangle, |x, y, depth| {
+                            atomic.test_and_set(x, y, depth);
+                        });
+                    }
+                });
+            }
+        });
+        let result = atomic.to_depth_buffer();
+        for y in 0..64 {
+            for x in 0..64 {
+                assert_eq!(result.get(x, y).to_bits(), expected.get(x, y).to_bits(), "depth at {}, {}", x, y);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn atomic_depth_test_and_set_respects_config() {
+    let mut depth = AtomicDepthBuffer::new(4, 4);
+    assert_eq!(depth.get(1, 1), f32::MAX);
+    assert!(depth.test_and_set(1, 1, 0.5));
+    assert!(!depth.test_and_set(1, 1, 0.75));
+    assert!(depth.test_and_set(1, 1, -0.25));
+    assert_eq!(depth.get(1, 1), -0.25);
+    depth.clear_to(0.0);
+    assert_eq!(depth.get(1, 1), 0.0);
+    let depth = depth.with_config(DepthConfig::reverse_z());
+    assert!(depth.test_and_set(2, 3, 0.5));
+    assert!(!depth.test_and_set(2, 3, 0.25));
+    assert_eq!(depth.get(2, 3), 0.5);
+}
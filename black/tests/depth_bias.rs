@@ -0,0 +1,117 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{DepthBuffer, FragmentProgram, Interpolate, Raster, RasterOptions, TargetBuffer, Vec4, VertexProgram};
+
+struct Target {
+    width:  i32,
+    height: i32,
+    data:   Vec<Vec4>,
+}
+impl Target {
+    pub fn new(width: i32, height: i32) -> Target {
+        Target { width, height, data: vec![Vec4::zero(); (width * height) as usize] }
+    }
+    pub fn get(&self, x: i32, y: i32) -> Vec4 {
+        self.data[(x + (y * self.width)) as usize]
+    }
+}
+impl TargetBuffer for Target {
+    fn width(&self) -> i32 { self.width }
+    fn height(&self) -> i32 { self.height }
+    fn set(&mut self, x: i32, y: i32, color: Vec4) {
+        self.data[(x + (y * self.width)) as usize] = color;
+    }
+}
+
+struct Uniform {
+    color: Vec4,
+}
+
+#[derive(Interpolate)]
+struct Varying {
+    position: Vec4,
+}
+
+struct VertexShader;
+impl VertexProgram for VertexShader {
+    type Uniform = Uniform;
+    type Varying = Varying;
+    type Vertex  = Vec4;
+    fn main(&self, _uniform: &Uniform, vertex: &Vec4, varying: &mut Varying) -> Vec4 {
+        varying.position = *vertex;
+        *vertex
+    }
+}
+
+struct FragmentShader;
+impl FragmentProgram for FragmentShader {
+    type Uniform = Uniform;
+    type Varying = Varying;
+    fn main(&self, uniform: &Uniform, _varying: &Varying) -> Option<Vec4> {
+        Some(uniform.color)
+    }
+}
+
+/// Returns a vertex in clip space that maps to the given screen position.
+fn screen(target: &Target, x: f32, y: f32) -> Vec4 {
+    let width  = target.width()  as f32;
+    let height = target.height() as f32;
+    Vec4::new((x - (width * 0.5)) / width, -(y - (height * 0.5)) / height, 0.5, 1.0)
+}
+
+#[test]
+fn depth_bias_wins_against_coplanar_triangle() {
+    let surface = Uniform { color: Vec4::new(1.0, 0.0, 0.0, 1.0) };
+    let decal   = Uniform { color: Vec4::new(0.0, 0.0, 1.0, 1.0) };
+    let biased  = RasterOptions { depth_bias: -0.001, ..RasterOptions::default() };
+    let plain   = RasterOptions::default();
+    for decal_first in [true, false].iter() {
+        let mut target = Target::new(32, 32);
+        let mut depth  = DepthBuffer::new(32, 32);
+        depth.clear();
+        let v0 = screen(&target, 1.0, 1.0);
+        let v1 = screen(&target, 4.0, 30.0);
+        let v2 = screen(&target, 31.0, 3.0);
+        let passes = if *decal_first { [(&biased, &decal), (&plain, &surface)] } else { [(&plain, &surface), (&biased, &decal)] };
+        for (options, uniform) in passes.iter() {
+            Raster::triangle_with_options(options, &VertexShader, &FragmentShader, &mut depth, &mut target, *uniform, &v0, &v1, &v2);
+        }
+        let mut covered = 0;
+        for y in 0..32 {
+            for x in 0..32 {
+                let color = target.get(x, y);
+                if color.w > 0.0 {
+                    covered += 1;
+                    assert_eq!(color, decal.color, "pixel {}, {} with decal first {}", x, y, decal_first);
+                }
+            }
+        }
+        assert!(covered > 0);
+    }
+}
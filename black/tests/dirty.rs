@@ -0,0 +1,59 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{DirtyRegion, Rect};
+
+#[test]
+fn dirty_region_tightly_bounds_pixels() {
+    let mut dirty = DirtyRegion::new();
+    assert!(dirty.is_empty());
+    assert_eq!(dirty.bounds(), None);
+    dirty.add(10, 4);
+    dirty.add(3, 12);
+    dirty.add(7, 7);
+    assert!(!dirty.is_empty());
+    assert_eq!(dirty.bounds(), Some(Rect::new(3, 4, 8, 9)));
+    dirty.reset();
+    assert_eq!(dirty.bounds(), None);
+}
+
+#[test]
+fn dirty_region_single_pixel() {
+    let mut dirty = DirtyRegion::new();
+    dirty.add(5, 6);
+    assert_eq!(dirty.bounds(), Some(Rect::new(5, 6, 1, 1)));
+}
+
+#[test]
+fn dirty_region_add_rect() {
+    let mut dirty = DirtyRegion::new();
+    dirty.add_rect(&Rect::new(2, 3, 4, 5));
+    assert_eq!(dirty.bounds(), Some(Rect::new(2, 3, 4, 5)));
+    dirty.add_rect(&Rect::new(0, 0, 0, 10));
+    assert_eq!(dirty.bounds(), Some(Rect::new(2, 3, 4, 5)));
+}
@@ -0,0 +1,69 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::Dither;
+
+#[test]
+fn half_alpha_draws_half_of_each_4x4_block() {
+    let mut drawn = 0;
+    for y in 0..16 {
+        for x in 0..16 {
+            if Dither::screen_door(0.5, x, y) {
+                drawn += 1;
+            }
+        }
+    }
+    assert_eq!(drawn, 128);
+    // the first row of the Bayer matrix is 0, 8, 2, 10.
+    assert!(Dither::screen_door(0.5, 0, 0));
+    assert!(!Dither::screen_door(0.5, 1, 0));
+    assert!(Dither::screen_door(0.5, 2, 0));
+    assert!(!Dither::screen_door(0.5, 3, 0));
+    // the pattern repeats every 4 pixels, including negative coordinates.
+    assert_eq!(Dither::screen_door(0.5, 5, 6), Dither::screen_door(0.5, 1, 2));
+    assert_eq!(Dither::screen_door(0.5, -3, -2), Dither::screen_door(0.5, 1, 2));
+}
+
+#[test]
+fn screen_door_is_monotonic_in_alpha() {
+    for y in 0..4 {
+        for x in 0..4 {
+            assert!(!Dither::screen_door(0.0, x, y));
+            assert!(Dither::screen_door(1.0, x, y));
+            let mut was_drawn = false;
+            for n in 0..=64 {
+                let drawn = Dither::screen_door(n as f32 / 64.0, x, y);
+                assert!(drawn || !was_drawn);
+                was_drawn = drawn;
+            }
+        }
+    }
+    let count = |alpha: f32| (0..16).filter(|n| Dither::screen_door(alpha, n % 4, n / 4)).count();
+    assert_eq!(count(0.25), 4);
+    assert_eq!(count(0.75), 12);
+}
@@ -0,0 +1,155 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{Draw2D, TargetBuffer, Vec2, Vec4};
+
+struct Image {
+    width:  i32,
+    height: i32,
+    data:   Vec<u32>,
+}
+impl Image {
+    pub fn new(width: i32, height: i32) -> Image {
+        Image { width, height, data: vec![0; (width * height) as usize] }
+    }
+    pub fn get(&self, x: i32, y: i32) -> u32 {
+        self.data[(x + (y * self.width)) as usize]
+    }
+    pub fn count(&self) -> usize {
+        self.data.iter().filter(|count| **count > 0).count()
+    }
+}
+impl TargetBuffer for Image {
+    fn width(&self) -> i32 { self.width }
+    fn height(&self) -> i32 { self.height }
+    fn set(&mut self, x: i32, y: i32, _color: Vec4) {
+        self.data[(x + (y * self.width)) as usize] += 1;
+    }
+}
+
+#[test]
+fn rect_fills_region_once() {
+    let mut image = Image::new(16, 16);
+    Draw2D::rect(&mut image, 3, 4, 5, 6, Vec4::one());
+    for y in 0..16 {
+        for x in 0..16 {
+            let inside = (3..8).contains(&x) && (4..10).contains(&y);
+            assert_eq!(image.get(x, y), if inside { 1 } else { 0 }, "at {}, {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn rect_is_clipped_to_target() {
+    let mut image = Image::new(16, 16);
+    Draw2D::rect(&mut image, -4, 12, 8, 10, Vec4::one());
+    assert_eq!(image.count(), 4 * 4);
+    assert_eq!(image.get(0, 15), 1);
+    Draw2D::rect(&mut image, 20, 0, 4, 4, Vec4::one());
+    assert_eq!(image.count(), 4 * 4);
+}
+
+#[test]
+fn line_covers_end_points_without_gaps() {
+    let mut image = Image::new(16, 16);
+    Draw2D::line(&mut image, &Vec2::new(1.5, 2.5), &Vec2::new(13.5, 7.5), Vec4::one());
+    assert_eq!(image.get(1, 2), 1);
+    assert_eq!(image.get(13, 7), 1);
+    // one pixel per column along the major axis.
+    assert_eq!(image.count(), 13);
+    for x in 1..14 {
+        assert_eq!((0..16).filter(|y| image.get(x, *y) > 0).count(), 1);
+    }
+}
+
+#[test]
+fn line_is_clipped_to_target() {
+    let mut image = Image::new(8, 8);
+    Draw2D::line(&mut image, &Vec2::new(-10.0, 4.5), &Vec2::new(20.0, 4.5), Vec4::one());
+    assert_eq!(image.count(), 8);
+}
+
+#[test]
+fn circle_covers_pixel_centers_within_radius() {
+    let mut image = Image::new(16, 16);
+    let center = Vec2::new(8.0, 8.0);
+    Draw2D::circle(&mut image, &center, 3.0, Vec4::one());
+    for y in 0..16 {
+        for x in 0..16 {
+            let distance = Vec2::distance(&Vec2::new(x as f32 + 0.5, y as f32 + 0.5), &center);
+            assert_eq!(image.get(x, y) > 0, distance <= 3.0, "at {}, {}", x, y);
+        }
+    }
+    assert!(image.data.iter().all(|count| *count <= 1));
+}
+
+fn pattern(image: &Image, x: i32, y: i32) -> Vec<String> {
+    (y..y + 8).map(|y| (x..x + 8).map(|x| if image.get(x, y) > 0 { '#' } else { '.' }).collect()).collect()
+}
+
+#[test]
+fn text_draws_glyph_pattern() {
+    let mut image = Image::new(8, 8);
+    Draw2D::text(&mut image, 0, 0, "A", Vec4::one());
+    assert_eq!(pattern(&image, 0, 0), vec![
+        "..##....",
+        ".####...",
+        "##..##..",
+        "##..##..",
+        "######..",
+        "##..##..",
+        "##..##..",
+        "........",
+    ]);
+}
+
+#[test]
+fn text_advances_and_wraps_on_newline() {
+    let mut image = Image::new(24, 24);
+    Draw2D::text(&mut image, 4, 2, "1 \n1", Vec4::one());
+    let one = vec![
+        "..##....",
+        ".###....",
+        "..##....",
+        "..##....",
+        "..##....",
+        "..##....",
+        "######..",
+        "........",
+    ];
+    assert_eq!(pattern(&image, 4, 2), one);
+    assert_eq!(pattern(&image, 4, 10), one);
+    assert!(pattern(&image, 12, 2).iter().all(|row| row == "........"));
+}
+
+#[test]
+fn text_is_clipped_to_target() {
+    let mut image = Image::new(8, 8);
+    Draw2D::text(&mut image, -4, -4, "\u{e9}#", Vec4::one());
+    assert!(image.count() > 0);
+}
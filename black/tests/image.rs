@@ -0,0 +1,41 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{ImageBuffer, TargetBuffer, Vec4};
+
+#[test]
+fn write_ppm_emits_header_and_rgb_bytes() {
+    let mut image = ImageBuffer::new(2, 1);
+    image.set(0, 0, Vec4::new(1.0, 0.0, 0.0, 1.0));
+    image.set(1, 0, Vec4::new(0.0, 2.0, 1.0, 0.0));
+    let mut bytes = Vec::new();
+    image.write_ppm(&mut bytes).unwrap();
+    let mut expect = b"P6\n2 1\n255\n".to_vec();
+    expect.extend_from_slice(&[255, 0, 0, 0, 255, 255]);
+    assert_eq!(bytes, expect);
+}
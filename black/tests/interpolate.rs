@@ -0,0 +1,111 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+// Compares the scalar and SSE Interpolate backends. The SSE backend is
+// compiled for any x86 target, to have the intrinsics inlined run with:
+//
+// $ RUSTFLAGS="-C target-feature=+sse4.1" cargo test -p black --test interpolate
+#![cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+
+use black::{Interpolate, InterpolateSse41, Vec2, Vec3, Vec4};
+
+#[derive(Interpolate)]
+struct Scalar {
+    position: Vec4,
+    normal:   Vec3,
+    uv:       Vec2,
+    depth:    f32,
+}
+
+#[derive(InterpolateSse41)]
+struct Sse {
+    position: Vec4,
+    normal:   Vec3,
+    uv:       Vec2,
+    depth:    f32,
+}
+
+struct Lcg(u32);
+impl Lcg {
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(1664525).wrapping_add(1013904223);
+        ((self.0 >> 8) as f32 / (1 << 24) as f32) * 200.0 - 100.0
+    }
+    fn vec4(&mut self) -> Vec4 { Vec4::new(self.next(), self.next(), self.next(), self.next()) }
+    fn vec3(&mut self) -> Vec3 { Vec3::new(self.next(), self.next(), self.next()) }
+    fn vec2(&mut self) -> Vec2 { Vec2::new(self.next(), self.next()) }
+}
+
+fn pair(lcg: &mut Lcg) -> (Scalar, Sse) {
+    let (position, normal, uv, depth) = (lcg.vec4(), lcg.vec3(), lcg.vec2(), lcg.next());
+    (Scalar { position, normal, uv, depth }, Sse { position, normal, uv, depth })
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() <= 1e-5 * f32::max(1.0, f32::max(a.abs(), b.abs()))
+}
+
+fn agree(scalar: &Scalar, sse: &Sse) -> bool {
+    (0..4).all(|i| close(scalar.position[i], sse.position[i]))
+        && (0..3).all(|i| close(scalar.normal[i], sse.normal[i]))
+        && (0..2).all(|i| close(scalar.uv[i], sse.uv[i]))
+        && close(scalar.depth, sse.depth)
+}
+
+#[test]
+fn new_agrees() {
+    assert!(agree(&Scalar::new(), &Sse::new()));
+}
+
+#[test]
+fn correct_agrees() {
+    let mut lcg = Lcg(1);
+    for _ in 0..1000 {
+        let (scalar, sse) = pair(&mut lcg);
+        let w = lcg.next();
+        if w == 0.0 {
+            continue;
+        }
+        assert!(agree(&Scalar::correct(&scalar, &w), &Sse::correct(&sse, &w)));
+    }
+}
+
+#[test]
+fn interpolate_agrees() {
+    let mut lcg = Lcg(2);
+    for _ in 0..1000 {
+        let (scalar_0, sse_0) = pair(&mut lcg);
+        let (scalar_1, sse_1) = pair(&mut lcg);
+        let (scalar_2, sse_2) = pair(&mut lcg);
+        let (w0, w1, w2) = (lcg.next() / 100.0, lcg.next() / 100.0, lcg.next() / 100.0);
+        let w = 1.0 + lcg.next().abs();
+        let scalar = Scalar::interpolate(&scalar_0, &scalar_1, &scalar_2, &w0, &w1, &w2, &w);
+        let sse    = Sse::interpolate(&sse_0, &sse_1, &sse_2, &w0, &w1, &w2, &w);
+        assert!(agree(&scalar, &sse));
+    }
+}
@@ -0,0 +1,72 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{Light, Vec3};
+
+#[test]
+fn lambert_is_max_for_light_directly_above() {
+    let normal = Vec3::unit_y();
+    let light  = Light::new(Vec3::new(0.0, 10.0, 0.0), Vec3::one(), 1.0);
+    let light_dir = light.direction(&Vec3::zero());
+    assert_eq!(light_dir, Vec3::unit_y());
+    assert_eq!(Light::lambert(&normal, &light_dir), 1.0);
+}
+
+#[test]
+fn lambert_falls_off_with_angle_and_clamps_below_horizon() {
+    let normal = Vec3::unit_y();
+    let angled = Vec3::normalize(&Vec3::new(1.0, 1.0, 0.0));
+    assert!((Light::lambert(&normal, &angled) - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+    assert_eq!(Light::lambert(&normal, &Vec3::unit_x()), 0.0);
+    assert_eq!(Light::lambert(&normal, &Vec3::new(0.0, -1.0, 0.0)), 0.0);
+}
+
+#[test]
+fn blinn_phong_peaks_at_mirror_direction() {
+    let normal    = Vec3::unit_y();
+    let light_dir = Vec3::normalize(&Vec3::new(1.0, 1.0, 0.0));
+    let mirror    = Vec3::normalize(&Vec3::new(-1.0, 1.0, 0.0));
+    let off_axis  = Vec3::normalize(&Vec3::new(-1.0, 1.0, 1.0));
+    assert!((Light::blinn_phong(&normal, &mirror, &light_dir, 32.0) - 1.0).abs() < 1e-5);
+    assert!(Light::blinn_phong(&normal, &off_axis, &light_dir, 32.0) < 0.5);
+    // a tighter highlight falls off faster.
+    assert!(Light::blinn_phong(&normal, &off_axis, &light_dir, 64.0) < Light::blinn_phong(&normal, &off_axis, &light_dir, 8.0));
+    // no highlight when lit from behind.
+    assert_eq!(Light::blinn_phong(&normal, &mirror, &Vec3::new(0.0, -1.0, 0.0), 32.0), 0.0);
+}
+
+#[test]
+fn illuminate_sums_lights() {
+    let red   = Light::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(1.0, 0.0, 0.0), 2.0);
+    let blue  = Light::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, 1.0), 1.0);
+    let below = Light::new(Vec3::new(0.0, -5.0, 0.0), Vec3::one(), 4.0);
+    let (diffuse, specular) = Light::illuminate(&[red, blue, below], &Vec3::zero(), &Vec3::unit_y(), &Vec3::unit_y(), 16.0);
+    assert_eq!(diffuse, Vec3::new(2.0, 0.0, 0.0));
+    assert_eq!(specular, Vec3::new(2.0, 0.0, 0.0));
+    assert_eq!(Light::illuminate(&[], &Vec3::zero(), &Vec3::unit_y(), &Vec3::unit_y(), 16.0), (Vec3::zero(), Vec3::zero()));
+}
@@ -0,0 +1,1621 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{CullMode, DepthBuffer, DepthConfig, DepthFunc, Dither, FragmentProgram, FragmentProgramMRT, ImageBuffer, Interpolate, Mat4, Mesh, Quad, QuadFragmentProgram, Raster, RasterError, RasterOptions, RasterStats, TargetBuffer, Traversal, Triangle, Vec2, Vec3, Vec4, VertexProgram, Winding};
+
+struct Target {
+    width:  i32,
+    height: i32,
+    data:   Vec<u32>,
+    colors: Vec<Vec4>,
+}
+impl Target {
+    pub fn new(width: i32, height: i32) -> Target {
+        let data   = vec![0; (width * height) as usize];
+        let colors = vec![Vec4::zero(); (width * height) as usize];
+        Target { width, height, data, colors }
+    }
+    pub fn get(&self, x: i32, y: i32) -> u32 {
+        self.data[(x + (y * self.width)) as usize]
+    }
+    pub fn color(&self, x: i32, y: i32) -> Vec4 {
+        self.colors[(x + (y * self.width)) as usize]
+    }
+}
+impl TargetBuffer for Target {
+    fn width(&self) -> i32 { self.width }
+    fn height(&self) -> i32 { self.height }
+    fn set(&mut self, x: i32, y: i32, color: Vec4) {
+        self.data[(x + (y * self.width)) as usize] += 1;
+        self.colors[(x + (y * self.width)) as usize] = color;
+    }
+}
+
+struct Uniform {
+    matrix: Mat4,
+}
+
+#[derive(Interpolate)]
+struct Varying {
+    position: Vec4,
+}
+
+struct VertexShader;
+impl VertexProgram for VertexShader {
+    type Uniform = Uniform;
+    type Varying = Varying;
+    type Vertex  = Vec4;
+    fn main(&self, uniform: &Uniform, vertex: &Vec4, varying: &mut Varying) -> Vec4 {
+        varying.position = *vertex;
+        *vertex * uniform.matrix
+    }
+}
+
+struct FragmentShader;
+impl FragmentProgram for FragmentShader {
+    type Uniform = Uniform;
+    type Varying = Varying;
+    fn main(&self, _uniform: &Uniform, varying: &Varying) -> Option<Vec4> {
+        Some(varying.position)
+    }
+}
+
+#[derive(Interpolate)]
+struct ColorVarying {
+    color: Vec4,
+}
+
+struct ColorVertexShader;
+impl VertexProgram for ColorVertexShader {
+    type Uniform = Uniform;
+    type Varying = ColorVarying;
+    type Vertex  = (Vec4, Vec4);
+    fn main(&self, uniform: &Uniform, vertex: &(Vec4, Vec4), varying: &mut ColorVarying) -> Vec4 {
+        varying.color = vertex.1;
+        vertex.0 * uniform.matrix
+    }
+}
+
+struct ColorFragmentShader;
+impl FragmentProgram for ColorFragmentShader {
+    type Uniform = Uniform;
+    type Varying = ColorVarying;
+    fn main(&self, _uniform: &Uniform, varying: &ColorVarying) -> Option<Vec4> {
+        Some(varying.color)
+    }
+}
+
+/// Returns the values of a depth buffer in row major order.
+fn depth_values(depth: &DepthBuffer) -> Vec<f32> {
+    (0..depth.height()).flat_map(|y| (0..depth.width()).map(move |x| depth.get(x, y))).collect()
+}
+
+/// Returns a vertex in clip space that maps to the given screen position.
+fn screen(target: &Target, x: f32, y: f32) -> Vec4 {
+    let width  = target.width()  as f32;
+    let height = target.height() as f32;
+    Vec4::new((x - (width * 0.5)) / width, -(y - (height * 0.5)) / height, 1.0, 1.0)
+}
+
+#[test]
+fn triangle_one_pixel_wide_produces_vertical_line() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&target, 10.0, 2.0);
+    let v1 = screen(&target, 11.0, 2.0);
+    let v2 = screen(&target, 11.0, 30.0);
+    let v3 = screen(&target, 10.0, 30.0);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v3, &v2);
+    for y in 2..30 {
+        assert_eq!(target.get(10, y), 1, "expected column 10 to be drawn once at y = {}", y);
+        assert_eq!(target.get(9, y), 0);
+        assert_eq!(target.get(11, y), 0);
+    }
+}
+
+#[test]
+fn triangle_touching_right_edge_fills_last_column() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&target, 16.0, 0.0);
+    let v1 = screen(&target, 32.0, 0.0);
+    let v2 = screen(&target, 32.0, 32.0);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+    for y in 0..31 {
+        assert_eq!(target.get(31, y), 1, "expected column 31 to be drawn at y = {}", y);
+    }
+}
+
+#[test]
+fn triangle_with_collinear_vertices_is_discarded() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&target, 2.0, 2.0);
+    let v1 = screen(&target, 16.0, 16.0);
+    let v2 = screen(&target, 30.0, 30.0);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v1, &v2);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+    assert!(target.data.iter().all(|count| *count == 0));
+    assert!(depth_values(&depth).into_iter().all(|value| !value.is_nan() && value == f32::MAX));
+}
+
+/// A user defined vertex, drawn through Mesh into an ImageBuffer.
+#[derive(Clone, Copy)]
+struct MyVertex {
+    position: Vec3,
+    color:    Vec4,
+}
+
+struct MyVertexShader;
+impl VertexProgram for MyVertexShader {
+    type Uniform = Uniform;
+    type Varying = ColorVarying;
+    type Vertex  = MyVertex;
+    fn main(&self, uniform: &Uniform, vertex: &MyVertex, varying: &mut ColorVarying) -> Vec4 {
+        varying.color = vertex.color;
+        Vec4::new(vertex.position.x, vertex.position.y, vertex.position.z, 1.0) * uniform.matrix
+    }
+}
+
+#[test]
+fn mesh_of_user_vertices_renders_into_image_buffer() {
+    let mut image = ImageBuffer::new(32, 32);
+    let mut depth = DepthBuffer::new(32, 32);
+    let uniform   = Uniform { matrix: Mat4::identity() };
+    let color     = Vec4::new(0.25, 0.5, 0.75, 1.0);
+    let vertex    = |x: f32, y: f32| MyVertex { position: Vec3::new((x - 16.0) / 32.0, -(y - 16.0) / 32.0, 1.0), color };
+    let mesh = Mesh::new(
+        vec![
+            vertex(8.0, 8.0),
+            vertex(24.0, 8.0),
+            vertex(24.0, 24.0),
+            vertex(8.0, 24.0),
+        ],
+        vec![0, 2, 1, 0, 3, 2],
+    );
+    mesh.draw(&MyVertexShader, &ColorFragmentShader, &mut depth, &mut image, &uniform);
+    for y in 0..32 {
+        for x in 0..32 {
+            let inside = (8..24).contains(&x) && (8..24).contains(&y);
+            assert_eq!(image.get(x, y), if inside { color } else { Vec4::zero() }, "at {}, {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn mesh_instanced_draws_once_per_uniform() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let vertices = vec![
+        screen(&target, 4.0, 12.0),
+        screen(&target, 12.0, 12.0),
+        screen(&target, 12.0, 20.0),
+        screen(&target, 4.0, 20.0),
+    ];
+    let indices = vec![0, 2, 1, 0, 3, 2];
+    let uniforms = vec![
+        Uniform { matrix: Mat4::identity() },
+        Uniform { matrix: Mat4::translation(&Vec3::new(16.0 / 32.0, 0.0, 0.0)) },
+    ];
+    Raster::mesh_instanced(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniforms, &vertices, &indices);
+    for y in 12..20 {
+        for x in 4..12 {
+            assert_eq!(target.get(x, y), 1, "first instance at {}, {}", x, y);
+            assert_eq!(target.get(x + 16, y), 1, "second instance at {}, {}", x + 16, y);
+        }
+        for x in 12..20 {
+            assert_eq!(target.get(x, y), 0, "gap at {}, {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn rasterize_triangle_visits_covered_pixels() {
+    let mut count = 0;
+    let c0 = Vec2::new(0.0, 0.0);
+    let c1 = Vec2::new(16.0, 0.0);
+    let c2 = Vec2::new(0.0, 16.0);
+    Raster::rasterize_triangle(32, 32, &c0, &c1, &c2, |x, y, weight_0, weight_1, weight_2| {
+        assert!(x + y < 16);
+        assert!((weight_0 + weight_1 + weight_2 - 1.0).abs() < 1e-5);
+        count += 1;
+    });
+    assert_eq!(count, 120);
+}
+
+#[test]
+fn rasterize_triangle_clamps_to_viewport() {
+    let mut count = 0;
+    let c0 = Vec2::new(-16.0, -16.0);
+    let c1 = Vec2::new(64.0, -16.0);
+    let c2 = Vec2::new(-16.0, 64.0);
+    Raster::rasterize_triangle(8, 8, &c0, &c1, &c2, |x, y, _, _, _| {
+        assert!((0..8).contains(&x) && (0..8).contains(&y));
+        count += 1;
+    });
+    assert_eq!(count, 64);
+}
+
+#[test]
+fn vertex_color_at_centroid_is_average_of_corners() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = (screen(&target, 1.5, 1.5),  Vec4::new(1.0, 0.0, 0.0, 1.0));
+    let v1 = (screen(&target, 31.5, 1.5), Vec4::new(0.0, 1.0, 0.0, 1.0));
+    let v2 = (screen(&target, 1.5, 31.5), Vec4::new(0.0, 0.0, 1.0, 1.0));
+    Raster::triangle(&ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+    let color  = target.color(11, 11);
+    let expect = (v0.1 + v1.1 + v2.1) * (1.0 / 3.0);
+    assert!((color.x - expect.x).abs() < 1e-4, "{}", color);
+    assert!((color.y - expect.y).abs() < 1e-4, "{}", color);
+    assert!((color.z - expect.z).abs() < 1e-4, "{}", color);
+    assert!((color.w - expect.w).abs() < 1e-4, "{}", color);
+}
+
+struct TwoOutputFragmentShader;
+impl FragmentProgramMRT<2> for TwoOutputFragmentShader {
+    type Uniform = Uniform;
+    type Varying = Varying;
+    fn main(&self, _uniform: &Uniform, _varying: &Varying) -> [Vec4; 2] {
+        [Vec4::new(1.0, 0.0, 0.0, 1.0), Vec4::new(0.0, 0.0, 1.0, 1.0)]
+    }
+}
+
+#[test]
+fn triangle_mrt_writes_each_output_to_its_target() {
+    let mut targets = [Target::new(32, 32), Target::new(32, 32)];
+    let mut depth   = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&targets[0], 0.0, 0.0);
+    let v1 = screen(&targets[0], 32.0, 0.0);
+    let v2 = screen(&targets[0], 0.0, 32.0);
+    Raster::triangle_mrt(&VertexShader, &TwoOutputFragmentShader, &mut depth, &mut targets, &uniform, &v0, &v2, &v1);
+    assert_eq!(targets[0].data, targets[1].data);
+    assert_eq!(targets[0].get(4, 4), 1);
+    assert_eq!(targets[0].color(4, 4), Vec4::new(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(targets[1].color(4, 4), Vec4::new(0.0, 0.0, 1.0, 1.0));
+}
+
+struct DiscardFragmentShader;
+impl FragmentProgram for DiscardFragmentShader {
+    type Uniform = Uniform;
+    type Varying = ColorVarying;
+    fn main(&self, _uniform: &Uniform, varying: &ColorVarying) -> Option<Vec4> {
+        if varying.color.x < 0.5 { None } else { Some(varying.color) }
+    }
+}
+
+#[test]
+fn discarded_fragments_write_neither_color_nor_depth() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = (screen(&target, 0.0, 0.0),   Vec4::new(0.0, 0.0, 0.0, 1.0));
+    let v1 = (screen(&target, 32.0, 0.0),  Vec4::new(1.0, 0.0, 0.0, 1.0));
+    let v2 = (screen(&target, 32.0, 32.0), Vec4::new(1.0, 0.0, 0.0, 1.0));
+    let v3 = (screen(&target, 0.0, 32.0),  Vec4::new(0.0, 0.0, 0.0, 1.0));
+    Raster::triangle(&ColorVertexShader, &DiscardFragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+    Raster::triangle(&ColorVertexShader, &DiscardFragmentShader, &mut depth, &mut target, &uniform, &v0, &v3, &v2);
+    for y in 0..32 {
+        for x in 0..32 {
+            if x < 16 {
+                assert_eq!(target.get(x, y), 0, "color at {}, {}", x, y);
+                assert_eq!(depth.get(x as usize, y as usize), f32::MAX, "depth at {}, {}", x, y);
+            } else {
+                assert_eq!(target.get(x, y), 1, "color at {}, {}", x, y);
+                assert!(depth.get(x as usize, y as usize) < f32::MAX, "depth at {}, {}", x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn clip_space_center_maps_to_viewport_center() {
+    let ndc = Raster::clip_to_ndc(&Vec4::new(0.0, 0.0, 0.5, 2.0));
+    assert_eq!(ndc, Vec3::new(0.0, 0.0, 0.25));
+    assert_eq!(Raster::ndc_to_screen(&ndc, 64, 32), Vec2::new(32.0, 16.0));
+}
+
+#[test]
+fn clip_to_ndc_divides_by_w() {
+    let ndc = Raster::clip_to_ndc(&Vec4::new(1.0, -2.0, 3.0, 4.0));
+    assert_eq!(ndc, Vec3::new(0.25, -0.5, 0.75));
+    let screen = Raster::ndc_to_screen(&ndc, 64, 32);
+    assert_eq!(screen, Vec2::new(48.0, 32.0));
+}
+
+#[test]
+fn triangle_with_negative_w_vertex_is_discarded() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&target, 4.0, 4.0);
+    let v1 = screen(&target, 28.0, 4.0);
+    let v2 = Vec4::new(0.25, -0.25, 1.0, -1.0);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v1, &v2);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+    Raster::triangle_depth(&mut depth, &v0, &v1, &v2);
+    assert!(target.data.iter().all(|count| *count == 0));
+    assert!(depth_values(&depth).into_iter().all(|value| value == f32::MAX));
+}
+
+#[test]
+fn perspective_projection_interpolates_varyings_by_w() {
+    // perspective_fov produces clip space z != w, varyings interpolated at
+    // each pixel must project back onto that pixel.
+    let mut target = Target::new(64, 64);
+    let mut depth  = DepthBuffer::new(64, 64);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::perspective_fov(1.2, 1.0, 0.1, 100.0) };
+    let vertex_0 = Vec4::new(-1.0, -1.0, -2.0, 1.0);
+    let vertex_1 = Vec4::new( 1.5, -0.5, -9.0, 1.0);
+    let vertex_2 = Vec4::new(-0.5,  1.5, -4.0, 1.0);
+    let clip = vertex_1 * uniform.matrix;
+    assert!((clip.z - clip.w).abs() > 0.01);
+
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertex_0, &vertex_2, &vertex_1);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertex_0, &vertex_1, &vertex_2);
+
+    let mut shaded = 0;
+    for y in 0..64 {
+        for x in 0..64 {
+            if target.get(x, y) == 0 {
+                continue;
+            }
+            shaded += 1;
+            let position = target.color(x, y) * uniform.matrix;
+            let screen   = Raster::ndc_to_screen(&Raster::clip_to_ndc(&position), 64, 64);
+            assert!((screen.x - (x as f32 + 0.5)).abs() < 1e-2, "x at {}, {}", x, y);
+            assert!((screen.y - (y as f32 + 0.5)).abs() < 1e-2, "y at {}, {}", x, y);
+            assert!((depth.get(x as usize, y as usize) - position.z / position.w).abs() < 1e-5);
+        }
+    }
+    assert!(shaded > 100);
+}
+
+#[test]
+fn reverse_z_config_keeps_nearest_fragment() {
+    let uniform = Uniform { matrix: Mat4::perspective_fov_reverse_z(1.2, 1.0, 0.1, 100.0) };
+    let quad = |z: f32, color: Vec4| {
+        [(-4.0, -4.0), (4.0, -4.0), (4.0, 4.0), (-4.0, 4.0)].iter()
+            .map(|(x, y)| (Vec4::new(*x, *y, z, 1.0), color))
+            .collect::<Vec<_>>()
+    };
+    let near = quad(-2.0, Vec4::new(1.0, 0.0, 0.0, 1.0));
+    let far  = quad(-5.0, Vec4::new(0.0, 0.0, 1.0, 1.0));
+    let indices = vec![0, 1, 2, 0, 2, 3];
+    for order in [[&near, &far], [&far, &near]].iter() {
+        let mut target = Target::new(32, 32);
+        let mut depth  = DepthBuffer::new(32, 32).with_config(DepthConfig::reverse_z());
+        assert_eq!(depth.get(0, 0), 0.0);
+        for mesh in order.iter() {
+            Raster::mesh(&ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, mesh, &indices);
+        }
+        assert_eq!(target.color(16, 16), Vec4::new(1.0, 0.0, 0.0, 1.0));
+        let expect = Vec4::new(0.0, 0.0, -2.0, 1.0) * uniform.matrix;
+        assert!((depth.get(16, 16) - expect.z / expect.w).abs() < 1e-5);
+        assert!(depth.get(16, 16) > 0.0);
+
+        // occlusion queries read the same config.
+        assert_eq!(Raster::occlusion_query(&ColorVertexShader, &depth, &uniform, &far, &indices), 0);
+    }
+}
+
+#[test]
+fn reverse_z_discards_triangles_crossing_the_near_plane() {
+    let uniform = Uniform { matrix: Mat4::perspective_fov_reverse_z(1.2, 1.0, 0.1, 100.0) };
+    let blue    = Vec4::new(0.0, 0.0, 1.0, 1.0);
+    let red     = Vec4::new(1.0, 0.0, 0.0, 1.0);
+    let far     = [(-4.0, -4.0), (4.0, -4.0), (4.0, 4.0), (-4.0, 4.0)].iter()
+        .map(|(x, y)| (Vec4::new(*x, *y, -5.0, 1.0), blue))
+        .collect::<Vec<_>>();
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32).with_config(DepthConfig::reverse_z());
+    Raster::mesh(&ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &far, &[0, 1, 2, 0, 2, 3]);
+    assert_eq!(target.color(16, 16), blue);
+
+    // the apex lies between the camera and the near plane, where reversed-Z
+    // clip z exceeds w and would write an NDC depth above 1.0.
+    let v0 = (Vec4::new(-4.0, -4.0, -2.0, 1.0), red);
+    let v1 = (Vec4::new(4.0, -4.0, -2.0, 1.0), red);
+    let v2 = (Vec4::new(0.0, 0.5, -0.05, 1.0), red);
+    let apex = v2.0 * uniform.matrix;
+    assert!(apex.w > 0.0 && apex.z > apex.w);
+    let mut stats = RasterStats::default();
+    Raster::triangle_with_stats(&RasterOptions::default(), &mut stats, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &v0, &v1, &v2);
+    assert_eq!(stats.triangles_clipped, 1);
+    assert_eq!(target.color(16, 16), blue);
+}
+
+#[test]
+fn depth_func_compares_fragment_against_stored_depth() {
+    assert!(DepthFunc::Less.test(0.5, 1.0));
+    assert!(!DepthFunc::Less.test(1.0, 1.0));
+    assert!(DepthFunc::LessEqual.test(1.0, 1.0));
+    assert!(DepthFunc::Greater.test(1.0, 0.5));
+    assert!(DepthFunc::GreaterEqual.test(1.0, 1.0));
+    assert!(DepthFunc::Always.test(2.0, 1.0));
+    assert!(!DepthFunc::Never.test(0.0, 1.0));
+    let mut depth = DepthBuffer::new(4, 4);
+    depth.clear();
+    assert_eq!(depth.get(3, 3), DepthConfig::default().clear_value);
+}
+
+#[test]
+fn flat_shaded_cube_has_uniform_color_per_face() {
+    let axes = [
+        (Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()),
+        (Vec3::new(-1.0, 0.0, 0.0), Vec3::unit_z(), Vec3::unit_y()),
+        (Vec3::unit_y(), Vec3::unit_z(), Vec3::unit_x()),
+        (Vec3::new(0.0, -1.0, 0.0), Vec3::unit_x(), Vec3::unit_z()),
+        (Vec3::unit_z(), Vec3::unit_x(), Vec3::unit_y()),
+        (Vec3::new(0.0, 0.0, -1.0), Vec3::unit_y(), Vec3::unit_x()),
+    ];
+    let mut vertices = vec![];
+    for (n, u, v) in axes.iter() {
+        let corner = |a: f32, b: f32| n + &(u * a) + (v * b);
+        let corners = [corner(-1.0, -1.0), corner(1.0, -1.0), corner(1.0, 1.0), corner(-1.0, 1.0)];
+        for triangle in [[0, 1, 2], [0, 2, 3]].iter() {
+            let face   = Triangle::new(corners[triangle[0]], corners[triangle[1]], corners[triangle[2]]);
+            let normal = Triangle::face_normal(&face);
+            let color  = Vec4::new(normal.x * 0.5 + 0.5, normal.y * 0.5 + 0.5, normal.z * 0.5 + 0.5, 1.0);
+            for corner in triangle.iter() {
+                vertices.push((corners[*corner].xyzw(), color));
+            }
+        }
+    }
+    let indices = (0..vertices.len() as u32).collect::<Vec<_>>();
+    let view    = Mat4::look_at(&Vec3::new(3.0, 2.5, 4.0), &Vec3::zero(), &Vec3::unit_y());
+    let uniform = Uniform { matrix: Mat4::mul(&view, &Mat4::perspective_fov(1.0, 1.0, 0.1, 100.0)) };
+    let mut target = Target::new(64, 64);
+    let mut depth  = DepthBuffer::new(64, 64);
+    depth.clear();
+    Raster::mesh(&ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &vertices, &indices);
+
+    // the +x, +y and +z faces are visible, each a single quantized color.
+    let mut colors = std::collections::HashMap::new();
+    for y in 0..64 {
+        for x in 0..64 {
+            if target.get(x, y) > 0 {
+                let color = target.color(x, y);
+                let key = ((color.x * 255.0).round() as u8, (color.y * 255.0).round() as u8, (color.z * 255.0).round() as u8);
+                *colors.entry(key).or_insert(0) += 1;
+            }
+        }
+    }
+    let mut keys = colors.keys().cloned().collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, vec![(128, 128, 255), (128, 255, 128), (255, 128, 128)]);
+    assert!(colors.values().all(|count| *count > 50));
+}
+
+#[test]
+fn winding_option_selects_front_faces() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let draw = |winding: Winding, clockwise: bool| {
+        let mut target = Target::new(32, 32);
+        let mut depth  = DepthBuffer::new(32, 32);
+        depth.clear();
+        let v0 = screen(&target, 4.0, 4.0);
+        let v1 = screen(&target, 28.0, 4.0);
+        let v2 = screen(&target, 4.0, 28.0);
+        let options = RasterOptions { winding, ..RasterOptions::default() };
+        if clockwise {
+            Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v1, &v2);
+        } else {
+            Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+        }
+        target.data.iter().filter(|count| **count > 0).count()
+    };
+    assert!(draw(Winding::CounterClockwise, false) > 0);
+    assert_eq!(draw(Winding::CounterClockwise, true), 0);
+    assert!(draw(Winding::Clockwise, true) > 0);
+    assert_eq!(draw(Winding::Clockwise, false), 0);
+}
+
+#[test]
+fn cull_mode_selects_discarded_faces() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let draw = |cull: CullMode, clockwise: bool| {
+        let mut target = Target::new(32, 32);
+        let mut depth  = DepthBuffer::new(32, 32);
+        depth.clear();
+        let v0 = screen(&target, 4.0, 4.0);
+        let v1 = screen(&target, 28.0, 4.0);
+        let v2 = screen(&target, 4.0, 28.0);
+        let options = RasterOptions::default().cull(cull).build();
+        if clockwise {
+            Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v1, &v2);
+        } else {
+            Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+        }
+        target.data.iter().filter(|count| **count > 0).count()
+    };
+    assert!(draw(CullMode::Back, false) > 0);
+    assert_eq!(draw(CullMode::Back, true), 0);
+    assert_eq!(draw(CullMode::Front, false), 0);
+    assert!(draw(CullMode::Front, true) > 0);
+    assert!(draw(CullMode::None, false) > 0);
+    assert!(draw(CullMode::None, true) > 0);
+}
+
+#[test]
+fn options_builder_defaults_match_default() {
+    assert_eq!(RasterOptions::default().build(), RasterOptions::default());
+    let default = RasterOptions::default();
+    assert_eq!(default.depth_bias, 0.0);
+    assert_eq!(default.slope_scaled_depth_bias, 0.0);
+    assert_eq!(default.winding, Winding::CounterClockwise);
+    assert_eq!(default.cull, CullMode::Back);
+    assert!(default.depth_test);
+    assert!(default.depth_write);
+    assert!(!default.screen_door);
+    assert!(!default.clamp_weights);
+    assert_eq!(default.traversal, Traversal::Scanline);
+
+    let options = RasterOptions::default()
+        .depth_bias(-0.01)
+        .slope_scaled_depth_bias(2.0)
+        .winding(Winding::Clockwise)
+        .cull(CullMode::None)
+        .depth_test(false)
+        .depth_write(false)
+        .screen_door(true)
+        .clamp_weights(true)
+        .traversal(Traversal::HalfSpace)
+        .build();
+    assert_eq!(options, RasterOptions {
+        depth_bias:              -0.01,
+        slope_scaled_depth_bias: 2.0,
+        winding:                 Winding::Clockwise,
+        cull:                    CullMode::None,
+        depth_test:              false,
+        depth_write:             false,
+        screen_door:             true,
+        clamp_weights:           true,
+        traversal:               Traversal::HalfSpace,
+    });
+}
+
+#[test]
+fn is_front_facing_matches_raster_culling() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let target  = Target::new(32, 32);
+    let v0 = screen(&target, 4.0, 4.0);
+    let v1 = screen(&target, 28.0, 4.0);
+    let v2 = screen(&target, 4.0, 28.0);
+    for winding in [Winding::CounterClockwise, Winding::Clockwise].iter() {
+        for order in [[v0, v1, v2], [v0, v2, v1]].iter() {
+            let mut target = Target::new(32, 32);
+            let mut depth  = DepthBuffer::new(32, 32);
+            depth.clear();
+            let options = RasterOptions { winding: *winding, ..RasterOptions::default() };
+            Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &order[0], &order[1], &order[2]);
+            let drawn = target.data.iter().any(|count| *count > 0);
+            let a = Raster::clip_to_ndc(&order[0]).xy();
+            let b = Raster::clip_to_ndc(&order[1]).xy();
+            let c = Raster::clip_to_ndc(&order[2]).xy();
+            assert_eq!(Triangle::is_front_facing(&a, &b, &c, *winding), drawn);
+        }
+    }
+}
+
+/// Returns count small randomly placed triangles in clip space with a
+/// random depth per vertex and a random color per triangle. The sequence
+/// is deterministic for a given seed.
+fn random_triangles(mut seed: u32, count: usize) -> (Vec<(Vec4, Vec4)>, Vec<u32>) {
+    let mut random = || {
+        seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+        (seed >> 8) as f32 / (1 << 24) as f32
+    };
+    let mut vertices = vec![];
+    let mut indices  = vec![];
+    for _ in 0..count {
+        let x = random() * 1.4 - 0.7;
+        let y = random() * 1.4 - 0.7;
+        let size = 0.02 + random() * 0.2;
+        let color = Vec4::new(random(), random(), random(), 1.0);
+        for _ in 0..3 {
+            let z = 0.1 + random() * 0.9;
+            let position = Vec4::new(x + (random() - 0.5) * size, y + (random() - 0.5) * size, z, 1.0);
+            indices.push(vertices.len() as u32);
+            vertices.push((position, color));
+        }
+    }
+    (vertices, indices)
+}
+
+#[test]
+fn mesh_binned_matches_mesh() {
+    let (width, height) = (200, 150);
+    let (vertices, indices) = random_triangles(3, 300);
+    let uniform = Uniform { matrix: Mat4::identity() };
+
+    let mut target_0 = Target::new(width, height);
+    let mut depth_0  = DepthBuffer::new(width as usize, height as usize);
+    depth_0.clear();
+    Raster::mesh(&ColorVertexShader, &ColorFragmentShader, &mut depth_0, &mut target_0, &uniform, &vertices, &indices);
+
+    let mut target_1 = Target::new(width, height);
+    let mut depth_1  = DepthBuffer::new(width as usize, height as usize);
+    depth_1.clear();
+    Raster::mesh_binned(&ColorVertexShader, &ColorFragmentShader, &mut depth_1, &mut target_1, &uniform, &vertices, &indices);
+
+    assert!(target_0.data.iter().any(|count| *count > 0));
+    assert_eq!(target_0.data, target_1.data);
+    assert_eq!(target_0.colors, target_1.colors);
+    assert_eq!(depth_values(&depth_0), depth_values(&depth_1));
+}
+
+#[test]
+fn tiled_depth_matches_flat_depth() {
+    let (width, height) = (203, 147);
+    let (vertices, indices) = random_triangles(11, 300);
+    let uniform = Uniform { matrix: Mat4::identity() };
+
+    let mut target_0 = Target::new(width, height);
+    let mut depth_0  = DepthBuffer::new(width as usize, height as usize);
+    depth_0.clear();
+    Raster::mesh(&ColorVertexShader, &ColorFragmentShader, &mut depth_0, &mut target_0, &uniform, &vertices, &indices);
+
+    let mut target_1 = Target::new(width, height);
+    let mut depth_1  = DepthBuffer::new_tiled(width as usize, height as usize);
+    depth_1.clear();
+    Raster::mesh(&ColorVertexShader, &ColorFragmentShader, &mut depth_1, &mut target_1, &uniform, &vertices, &indices);
+
+    assert!(target_0.data.iter().any(|count| *count > 0));
+    assert_eq!(target_0.data, target_1.data);
+    assert_eq!(target_0.colors, target_1.colors);
+    for y in 0..height as usize {
+        for x in 0..width as usize {
+            assert_eq!(depth_0.get(x, y), depth_1.get(x, y));
+        }
+    }
+    let query_0 = Raster::occlusion_query(&ColorVertexShader, &depth_0, &uniform, &vertices, &indices);
+    let query_1 = Raster::occlusion_query(&ColorVertexShader, &depth_1, &uniform, &vertices, &indices);
+    assert_eq!(query_0, query_1);
+}
+
+#[test]
+fn rasterize_triangle_weights_match_edge_functions() {
+    let edge = |a: &Vec2, b: &Vec2, c: &Vec2| Vec2::cross(&(c - a), &(b - a));
+    let triangles = [
+        (Vec2::new(3.3, 1.7), Vec2::new(250.1, 40.6), Vec2::new(90.7, 180.2)),
+        (Vec2::new(200.4, 7.1), Vec2::new(11.9, 120.3), Vec2::new(255.6, 190.8)),
+    ];
+    for (c0, c1, c2) in triangles.iter() {
+        let area = edge(c0, c1, c2);
+        let mut visited = vec![false; 256 * 200];
+        Raster::rasterize_triangle(256, 200, c0, c1, c2, |x, y, w0, w1, w2| {
+            let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+            assert!((w0 - edge(c1, c2, &p) / area).abs() < 1e-4);
+            assert!((w1 - edge(c2, c0, &p) / area).abs() < 1e-4);
+            assert!((w2 - edge(c0, c1, &p) / area).abs() < 1e-4);
+            assert!((w0 + w1 + w2 - 1.0).abs() < 1e-4);
+            visited[(x + y * 256) as usize] = true;
+        });
+        for y in 0..200 {
+            for x in 0..256 {
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let inside = edge(c1, c2, &p) / area > 0.0
+                    && edge(c2, c0, &p) / area > 0.0
+                    && edge(c0, c1, &p) / area > 0.0;
+                assert_eq!(visited[(x + y * 256) as usize], inside, "at {}, {}", x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn occlusion_query_counts_visible_pixels() {
+    let target  = Target::new(32, 32);
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let quad = |x0: f32, y0: f32, x1: f32, y1: f32, z: f32| {
+        [(x0, y0), (x1, y0), (x1, y1), (x0, y1)].iter().map(|(x, y)| {
+            let mut position = screen(&target, *x, *y);
+            position.z = z;
+            position
+        }).collect::<Vec<_>>()
+    };
+    let indices = vec![0, 2, 1, 0, 3, 2];
+    let mesh = quad(8.0, 8.0, 24.0, 24.0, 1.0);
+
+    // unoccluded, counts the projected area.
+    let mut depth = DepthBuffer::new(32, 32);
+    depth.clear();
+    assert_eq!(Raster::occlusion_query(&VertexShader, &depth, &uniform, &mesh, &indices), 256);
+
+    // overlapping triangles in the same mesh count each pixel once.
+    let doubled = vec![0, 2, 1, 0, 3, 2, 0, 2, 1];
+    assert_eq!(Raster::occlusion_query(&VertexShader, &depth, &uniform, &mesh, &doubled), 256);
+
+    // fully occluded by a nearer quad.
+    let occluder = quad(0.0, 0.0, 32.0, 32.0, 0.5);
+    let mut target = Target::new(32, 32);
+    Raster::mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &occluder, &indices);
+    assert_eq!(Raster::occlusion_query(&VertexShader, &depth, &uniform, &mesh, &indices), 0);
+
+    // the query does not write depth.
+    let before = depth_values(&depth);
+    let nearer = quad(8.0, 8.0, 24.0, 24.0, 0.25);
+    assert_eq!(Raster::occlusion_query(&VertexShader, &depth, &uniform, &nearer, &indices), 256);
+    assert_eq!(depth_values(&depth), before);
+}
+
+#[test]
+fn depth_write_disabled_tests_without_writing() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let indices: Vec<u32> = vec![0, 2, 1, 0, 3, 2];
+    let quad = |target: &Target, z: f32| {
+        [(8.0, 8.0), (24.0, 8.0), (24.0, 24.0), (8.0, 24.0)].iter().map(|(x, y)| {
+            let mut position = screen(target, *x, *y);
+            position.z = z;
+            position
+        }).collect::<Vec<_>>()
+    };
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+
+    // opaque
+    let solid = quad(&target, 0.5);
+    Raster::mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &solid, &indices);
+    let opaque = depth_values(&depth);
+    assert_eq!(target.get(16, 16), 1);
+
+    // transparent in front, drawn without writing depth.
+    let options = RasterOptions { depth_write: false, ..RasterOptions::default() };
+    let transparent = quad(&target, 0.25);
+    for triangle in indices.chunks(3) {
+        let (v0, v1, v2) = (&transparent[triangle[0] as usize], &transparent[triangle[1] as usize], &transparent[triangle[2] as usize]);
+        Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, v0, v1, v2);
+    }
+    assert_eq!(target.get(16, 16), 2);
+    assert_eq!(depth_values(&depth), opaque);
+
+    // transparent behind, still depth tested.
+    let behind = quad(&target, 0.75);
+    for triangle in indices.chunks(3) {
+        let (v0, v1, v2) = (&behind[triangle[0] as usize], &behind[triangle[1] as usize], &behind[triangle[2] as usize]);
+        Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, v0, v1, v2);
+    }
+    assert_eq!(target.get(16, 16), 2);
+
+    // depth test disabled, drawn regardless.
+    let options = RasterOptions { depth_test: false, depth_write: false, ..RasterOptions::default() };
+    for triangle in indices.chunks(3) {
+        let (v0, v1, v2) = (&behind[triangle[0] as usize], &behind[triangle[1] as usize], &behind[triangle[2] as usize]);
+        Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, v0, v1, v2);
+    }
+    assert_eq!(target.get(16, 16), 3);
+    assert_eq!(depth_values(&depth), opaque);
+}
+
+#[test]
+fn line_is_clipped_to_viewport() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = (screen(&target, -32.0, 8.5), Vec4::new(0.0, 0.0, 0.0, 1.0));
+    let v1 = (screen(&target, 20.5, 8.5), Vec4::new(1.0, 0.0, 0.0, 1.0));
+    Raster::line(&ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &v0, &v1);
+    for y in 0..32 {
+        for x in 0..32 {
+            let expect = if y == 8 && x <= 20 { 1 } else { 0 };
+            assert_eq!(target.get(x, y), expect, "at {}, {}", x, y);
+        }
+    }
+    // the visible start is 32 pixels along a 52.5 pixel line.
+    let start = target.color(0, 8);
+    assert!((start.x - 32.0 / 52.5).abs() < 1e-3, "start color {}", start.x);
+    let end = target.color(20, 8);
+    assert!((end.x - 1.0).abs() < 1e-3, "end color {}", end.x);
+}
+
+#[test]
+fn line_outside_viewport_draws_nothing() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&target, -100.0, -10.0);
+    let v1 = screen(&target, 100.0, -10.0);
+    Raster::line(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v1);
+    let v0 = screen(&target, -1.0e6, 16.5);
+    let v1 = screen(&target, -1.0, 16.5);
+    Raster::line(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v1);
+    assert!(target.data.iter().all(|count| *count == 0));
+}
+
+#[test]
+fn convex_polygon_fills_interior_once() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let positions = vec![
+        Vec2::new(8.0, 8.0),
+        Vec2::new(24.0, 8.0),
+        Vec2::new(24.0, 24.0),
+        Vec2::new(8.0, 24.0),
+    ];
+    let varyings = vec![
+        ColorVarying { color: Vec4::new(0.0, 0.0, 0.0, 1.0) },
+        ColorVarying { color: Vec4::new(1.0, 0.0, 0.0, 1.0) },
+        ColorVarying { color: Vec4::new(1.0, 0.0, 0.0, 1.0) },
+        ColorVarying { color: Vec4::new(0.0, 0.0, 0.0, 1.0) },
+    ];
+    Raster::convex_polygon(&positions, &varyings, &ColorFragmentShader, &mut depth, &mut target, &uniform);
+    for y in 0..32 {
+        for x in 0..32 {
+            let inside = (8..24).contains(&x) && (8..24).contains(&y);
+            assert_eq!(target.get(x, y), if inside { 1 } else { 0 }, "at {}, {}", x, y);
+        }
+    }
+    // red increases linearly from the left edge to the right edge.
+    let color = target.color(15, 12);
+    assert!((color.x - 7.5 / 16.0).abs() < 1e-4);
+}
+
+#[test]
+fn convex_polygon_hexagon_has_no_overdraw() {
+    let mut target = Target::new(64, 64);
+    let mut depth  = DepthBuffer::new(64, 64);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let positions = (0..6).map(|i| {
+        let angle = i as f32 * std::f32::consts::PI / 3.0;
+        Vec2::new(32.0 + angle.cos() * 20.0, 32.0 - angle.sin() * 20.0)
+    }).collect::<Vec<_>>();
+    let varyings = (0..6).map(|_| ColorVarying { color: Vec4::one() }).collect::<Vec<_>>();
+    Raster::convex_polygon(&positions, &varyings, &ColorFragmentShader, &mut depth, &mut target, &uniform);
+    assert!(target.data.iter().all(|count| *count <= 1));
+    assert_eq!(target.get(32, 32), 1);
+    assert!(target.data.iter().filter(|count| **count == 1).count() > 900);
+}
+
+#[test]
+fn mesh_with_stats_counts_draw() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut vertices = vec![
+        screen(&target, 8.0, 8.0),
+        screen(&target, 24.0, 8.0),
+        screen(&target, 24.0, 24.0),
+        screen(&target, 8.0, 24.0),
+    ];
+    vertices.push(Vec4::new(0.0, 0.0, 1.0, -1.0));
+    let indices = vec![
+        0, 2, 1, 0, 3, 2, // front facing
+        0, 1, 2, 0, 2, 3, // back facing
+        0, 2, 1, 0, 3, 2, // front facing, occluded by the first
+        0, 1, 4,          // behind the camera
+    ];
+    let mut stats = RasterStats::default();
+    Raster::mesh_with_stats(&RasterOptions::default(), &mut stats, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertices, &indices);
+    assert_eq!(stats, RasterStats {
+        triangles_submitted: 7,
+        triangles_culled:    2,
+        triangles_clipped:   1,
+        pixels_shaded:       256,
+        depth_rejects:       256,
+    });
+
+    // stats accumulate across draws.
+    Raster::mesh_with_stats(&RasterOptions::default(), &mut stats, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertices, &indices[0..6]);
+    assert_eq!(stats.triangles_submitted, 9);
+    assert_eq!(stats.depth_rejects, 512);
+}
+
+struct DerivativeFragmentShader;
+impl QuadFragmentProgram for DerivativeFragmentShader {
+    type Uniform = Uniform;
+    type Varying = ColorVarying;
+    fn main(&self, _uniform: &Uniform, _varying: &ColorVarying, quad: &Quad<ColorVarying>) -> Option<Vec4> {
+        Some(Vec4::new(quad.ddx().color.x, quad.ddy().color.y, quad.ddx().color.y, quad.ddy().color.x))
+    }
+}
+
+#[test]
+fn triangle_quad_ddx_matches_per_pixel_slope() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+
+    // color.x increases by 0.5 per pixel in x, color.y by 0.25 per pixel in y.
+    let v0 = (screen(&target, 2.0, 2.0),   Vec4::new(1.0,  0.5,  0.0, 0.0));
+    let v1 = (screen(&target, 30.0, 4.0),  Vec4::new(15.0, 1.0,  0.0, 0.0));
+    let v2 = (screen(&target, 6.0, 29.0),  Vec4::new(3.0,  7.25, 0.0, 0.0));
+    Raster::triangle_quad(&ColorVertexShader, &DerivativeFragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+    let mut shaded = 0;
+    for y in 0..32 {
+        for x in 0..32 {
+            if target.get(x, y) == 0 {
+                continue;
+            }
+            shaded += 1;
+            let derivatives = target.color(x, y);
+            assert!((derivatives.x - 0.5).abs()  < 1e-3, "ddx at {}, {} was {}", x, y, derivatives.x);
+            assert!((derivatives.y - 0.25).abs() < 1e-3, "ddy at {}, {} was {}", x, y, derivatives.y);
+            assert!(derivatives.z.abs() < 1e-3);
+            assert!(derivatives.w.abs() < 1e-3);
+        }
+    }
+    assert!(shaded > 100);
+}
+
+#[test]
+fn try_mesh_reports_mismatched_depth_and_target_sizes() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(16, 32);
+    depth.clear();
+    let uniform  = Uniform { matrix: Mat4::identity() };
+    let vertices = vec![screen(&target, 2.0, 2.0), screen(&target, 30.0, 2.0), screen(&target, 2.0, 30.0)];
+    let result = Raster::try_mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertices, &[0, 2, 1]);
+    let error = result.unwrap_err();
+    assert_eq!(error, RasterError::SizeMismatch { depth_width: 16, depth_height: 32, target_width: 32, target_height: 32 });
+    assert_eq!(error.to_string(), "depth buffer is 16x32 but target is 32x32");
+    assert!(target.data.iter().all(|count| *count == 0));
+}
+
+#[test]
+fn try_mesh_reports_invalid_indices() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform  = Uniform { matrix: Mat4::identity() };
+    let vertices = vec![screen(&target, 2.0, 2.0), screen(&target, 30.0, 2.0), screen(&target, 2.0, 30.0)];
+    let result = Raster::try_mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertices, &[0, 2]);
+    assert_eq!(result.unwrap_err(), RasterError::IndexCount(2));
+    let result = Raster::try_mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertices, &[0, 2, 3]);
+    assert_eq!(result.unwrap_err(), RasterError::IndexOutOfRange { offset: 2, index: 3, vertex_count: 3 });
+    assert!(target.data.iter().all(|count| *count == 0));
+}
+
+#[test]
+fn try_mesh_reports_empty_viewport() {
+    let mut target = Target::new(0, 32);
+    let mut depth  = DepthBuffer::new(0, 32);
+    let uniform  = Uniform { matrix: Mat4::identity() };
+    let result = Raster::try_mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &[], &[]);
+    assert_eq!(result.unwrap_err(), RasterError::EmptyViewport { width: 0, height: 32 });
+}
+
+#[test]
+fn try_mesh_draws_valid_mesh_and_returns_stats() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform  = Uniform { matrix: Mat4::identity() };
+    let vertices = vec![screen(&target, 2.0, 2.0), screen(&target, 30.0, 2.0), screen(&target, 2.0, 30.0)];
+    let stats = Raster::try_mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertices, &[0, 2, 1]).unwrap();
+    assert_eq!(stats.triangles_submitted, 1);
+    assert!(stats.pixels_shaded > 0);
+    assert_eq!(stats.pixels_shaded as usize, target.data.iter().filter(|count| **count > 0).count());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "depth buffer is 16x16 but target is 32x32")]
+fn triangle_with_mismatched_depth_asserts_in_debug() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(16, 16);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&target, 2.0, 2.0);
+    let v1 = screen(&target, 30.0, 2.0);
+    let v2 = screen(&target, 2.0, 30.0);
+    Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "depth buffer is 32x16 but target is 32x32")]
+fn mesh_with_mismatched_depth_asserts_in_debug() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 16);
+    depth.clear();
+    let uniform  = Uniform { matrix: Mat4::identity() };
+    let vertices = vec![screen(&target, 2.0, 2.0), screen(&target, 30.0, 2.0), screen(&target, 2.0, 30.0)];
+    Raster::mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &vertices, &[0, 2, 1]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn every_draw_path_asserts_mismatched_depth_in_debug() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    let uniform  = Uniform { matrix: Mat4::identity() };
+    let target   = Target::new(32, 32);
+    let color    = Vec4::one();
+    let vertices = vec![(screen(&target, 2.0, 2.0), color), (screen(&target, 30.0, 2.0), color), (screen(&target, 2.0, 30.0), color)];
+    let indices  = [0, 2, 1];
+    let options  = RasterOptions::default();
+    let draws: Vec<(&str, Box<dyn Fn(&mut DepthBuffer, &mut Target)>)> = vec![
+        ("mesh_binned", Box::new(|depth, target| Raster::mesh_binned(&ColorVertexShader, &ColorFragmentShader, depth, target, &uniform, &vertices, &indices))),
+        ("mesh_instanced", Box::new(|depth, target| Raster::mesh_instanced_with_options(&options, &ColorVertexShader, &ColorFragmentShader, depth, target, &[], &vertices, &indices))),
+        ("triangle_mrt", Box::new(|depth, target| {
+            let mut targets = [Target::new(target.width, target.height), Target::new(target.width, target.height)];
+            Raster::triangle_mrt(&VertexShader, &TwoOutputFragmentShader, depth, &mut targets, &uniform, &vertices[0].0, &vertices[2].0, &vertices[1].0)
+        })),
+        ("triangle_quad", Box::new(|depth, target| Raster::triangle_quad(&ColorVertexShader, &QuadColorFragmentShader, depth, target, &uniform, &vertices[0], &vertices[2], &vertices[1]))),
+        ("mesh_quad", Box::new(|depth, target| Raster::mesh_quad(&ColorVertexShader, &QuadColorFragmentShader, depth, target, &uniform, &vertices, &[]))),
+        ("convex_polygon", Box::new(|depth, target| Raster::convex_polygon(&[], &[] as &[ColorVarying], &ColorFragmentShader, depth, target, &uniform))),
+        ("line", Box::new(|depth, target| Raster::line(&ColorVertexShader, &ColorFragmentShader, depth, target, &uniform, &vertices[0], &vertices[1]))),
+        ("point", Box::new(|depth, target| Raster::point(&ColorVertexShader, &ColorFragmentShader, depth, target, &uniform, &vertices[0], 2.0))),
+    ];
+    for (name, draw) in draws.iter() {
+        // a larger depth buffer is never indexed out of range, so only the
+        // size assertion can panic.
+        let mut target = Target::new(32, 32);
+        let mut depth  = DepthBuffer::new(64, 64);
+        let result = catch_unwind(AssertUnwindSafe(|| draw(&mut depth, &mut target)));
+        assert!(result.is_err(), "{} did not assert on mismatched sizes", name);
+    }
+}
+
+#[test]
+fn try_triangle_reports_mismatched_sizes() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 8);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&target, 2.0, 2.0);
+    let v1 = screen(&target, 30.0, 2.0);
+    let v2 = screen(&target, 2.0, 30.0);
+    let result = Raster::try_triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1);
+    assert_eq!(result.unwrap_err(), RasterError::SizeMismatch { depth_width: 32, depth_height: 8, target_width: 32, target_height: 32 });
+    let mut depth = DepthBuffer::new(32, 32);
+    depth.clear();
+    let stats = Raster::try_triangle(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &v0, &v2, &v1).unwrap();
+    assert!(stats.pixels_shaded > 0);
+}
+
+#[test]
+fn rasterize_triangle_halfspace_matches_scanline() {
+    // a small lcg so the triangles are repeatable.
+    let mut seed = 12345u32;
+    let mut random = move |scale: f32| {
+        seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+        ((seed >> 8) as f32 / (1 << 24) as f32) * scale
+    };
+    let mut triangles = vec![
+        (Vec2::new(0.0, 0.0), Vec2::new(16.0, 0.0), Vec2::new(0.0, 16.0)),
+        (Vec2::new(2.5, 2.5), Vec2::new(20.5, 2.5), Vec2::new(20.5, 30.5)),
+        (Vec2::new(-10.0, 4.0), Vec2::new(70.0, 20.0), Vec2::new(30.0, 60.0)),
+        (Vec2::new(10.0, 2.0), Vec2::new(11.0, 2.0), Vec2::new(10.0, 40.0)),
+    ];
+    for _ in 0..64 {
+        triangles.push((
+            Vec2::new(random(64.0), random(48.0)),
+            Vec2::new(random(64.0), random(48.0)),
+            Vec2::new(random(64.0), random(48.0)),
+        ));
+    }
+    for (c0, c1, c2) in triangles.iter() {
+        // either winding.
+        for (c1, c2) in [(c1, c2), (c2, c1)].iter() {
+            let mut scanline  = vec![];
+            let mut halfspace = vec![];
+            Raster::rasterize_triangle(64, 48, c0, c1, c2, |x, y, w0, w1, w2| scanline.push((y, x, w0, w1, w2)));
+            Raster::rasterize_triangle_halfspace(64, 48, c0, c1, c2, |x, y, w0, w1, w2| halfspace.push((y, x, w0, w1, w2)));
+            assert_eq!(scanline, halfspace, "{} {} {}", c0, c1, c2);
+        }
+    }
+}
+
+#[test]
+fn rasterize_triangle_halfspace_fills_shared_edges_once() {
+    let mut count = vec![0; 32 * 32];
+    let corners = [Vec2::new(3.5, 2.5), Vec2::new(27.5, 4.5), Vec2::new(25.5, 29.5), Vec2::new(1.5, 26.5)];
+    let center  = Vec2::new(15.5, 15.5);
+    for n in 0..4 {
+        let (c0, c1) = (&corners[n], &corners[(n + 1) % 4]);
+        Raster::rasterize_triangle_halfspace(32, 32, &center, c0, c1, |x, y, _, _, _| count[(x + y * 32) as usize] += 1);
+    }
+    assert!(count.iter().all(|n| *n <= 1));
+    assert_eq!(count[(15 + 15 * 32) as usize], 1);
+}
+
+#[test]
+fn halfspace_traversal_matches_scanline_triangle() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut target_0 = Target::new(48, 40);
+    let mut target_1 = Target::new(48, 40);
+    let mut depth_0  = DepthBuffer::new(48, 40);
+    let mut depth_1  = DepthBuffer::new(48, 40);
+    depth_0.clear();
+    depth_1.clear();
+    let vertex = |target: &Target, x: f32, y: f32, z: f32, color: Vec4| {
+        let mut position = screen(target, x, y);
+        position.z = z;
+        (position, color)
+    };
+    let triangles = [
+        [vertex(&target_0, 2.3, 1.7, 0.5, Vec4::new(1.0, 0.0, 0.0, 1.0)), vertex(&target_0, 3.1, 38.2, 0.5, Vec4::new(0.0, 1.0, 0.0, 1.0)), vertex(&target_0, 45.6, 20.4, 0.5, Vec4::new(0.0, 0.0, 1.0, 1.0))],
+        [vertex(&target_0, 10.5, 5.5, 0.3, Vec4::one()), vertex(&target_0, 40.5, 35.5, 0.7, Vec4::one()), vertex(&target_0, 40.5, 5.5, 0.3, Vec4::zero())],
+    ];
+    let halfspace = RasterOptions::default().traversal(Traversal::HalfSpace);
+    for [v0, v1, v2] in triangles.iter() {
+        Raster::triangle(&ColorVertexShader, &ColorFragmentShader, &mut depth_0, &mut target_0, &uniform, v0, v1, v2);
+        Raster::triangle_with_options(&halfspace, &ColorVertexShader, &ColorFragmentShader, &mut depth_1, &mut target_1, &uniform, v0, v1, v2);
+    }
+    assert!(target_0.data.iter().any(|n| *n > 0));
+    assert_eq!(target_0.data, target_1.data);
+    assert_eq!(target_0.colors, target_1.colors);
+    for y in 0..40 {
+        for x in 0..48 {
+            assert_eq!(depth_0.get(x, y), depth_1.get(x, y));
+        }
+    }
+}
+
+#[test]
+fn transform_vertices_runs_vertex_program_per_vertex() {
+    let uniform  = Uniform { matrix: Mat4::translation(&Vec3::new(1.0, 2.0, 3.0)) };
+    let vertices = vec![Vec4::new(0.0, 0.0, 0.0, 1.0), Vec4::new(1.0, -1.0, 2.0, 1.0)];
+    let transformed = Raster::transform_vertices(&VertexShader, &uniform, &vertices);
+    assert_eq!(transformed.len(), 2);
+    for (input, (position, varying)) in vertices.iter().zip(transformed.iter()) {
+        assert_eq!(*position, *input * uniform.matrix);
+        assert_eq!(varying.position, *input);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn transform_vertices_parallel_matches_serial() {
+    let uniform  = Uniform { matrix: Mat4::rotation_y(0.7) * Mat4::translation(&Vec3::new(0.5, -1.0, 4.0)) };
+    let vertices = (0..4096).map(|n| {
+        let n = n as f32;
+        Vec4::new(f32::sin(n), f32::cos(n * 0.7), n * 0.01, 1.0)
+    }).collect::<Vec<_>>();
+    let serial   = Raster::transform_vertices(&VertexShader, &uniform, &vertices);
+    let parallel = Raster::transform_vertices_parallel(&VertexShader, &uniform, &vertices);
+    assert_eq!(serial.len(), parallel.len());
+    for ((position_0, varying_0), (position_1, varying_1)) in serial.iter().zip(parallel.iter()) {
+        assert_eq!(position_0, position_1);
+        assert_eq!(varying_0.position, varying_1.position);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn mesh_parallel_matches_mesh() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut target_0 = Target::new(48, 40);
+    let mut target_1 = Target::new(48, 40);
+    let mut depth_0  = DepthBuffer::new(48, 40);
+    let mut depth_1  = DepthBuffer::new(48, 40);
+    depth_0.clear();
+    depth_1.clear();
+    let vertex = |x: f32, y: f32, z: f32, color: Vec4| {
+        let mut position = screen(&target_0, x, y);
+        position.z = z;
+        (position, color)
+    };
+    let vertices = vec![
+        vertex(2.3, 1.7, 0.5, Vec4::new(1.0, 0.0, 0.0, 1.0)),
+        vertex(45.6, 2.4, 0.2, Vec4::new(0.0, 1.0, 0.0, 1.0)),
+        vertex(44.1, 38.2, 0.5, Vec4::new(0.0, 0.0, 1.0, 1.0)),
+        vertex(3.1, 36.9, 0.8, Vec4::one()),
+    ];
+    let indices = vec![0, 2, 1, 0, 3, 2, 1, 3, 0];
+    Raster::mesh(&ColorVertexShader, &ColorFragmentShader, &mut depth_0, &mut target_0, &uniform, &vertices, &indices);
+    Raster::mesh_parallel(&ColorVertexShader, &ColorFragmentShader, &mut depth_1, &mut target_1, &uniform, &vertices, &indices);
+    assert!(target_0.data.iter().any(|n| *n > 0));
+    assert_eq!(target_0.data, target_1.data);
+    assert_eq!(target_0.colors, target_1.colors);
+}
+
+#[test]
+fn triangle_clip_matches_pass_through_vertex_shader() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut target_0 = Target::new(40, 32);
+    let mut target_1 = Target::new(40, 32);
+    let mut depth_0  = DepthBuffer::new(40, 32);
+    let mut depth_1  = DepthBuffer::new(40, 32);
+    depth_0.clear();
+    depth_1.clear();
+    // clip positions with differing w so the divide and correction are exercised.
+    let clip_pos = [
+        Vec4::new(-0.8, -0.7, 0.4, 1.0),
+        Vec4::new(1.2, -1.0, 1.0, 2.0),
+        Vec4::new(-0.1, 0.9, 0.3, 1.0),
+    ];
+    let colors = [Vec4::new(1.0, 0.0, 0.0, 1.0), Vec4::new(0.0, 1.0, 0.0, 1.0), Vec4::new(0.0, 0.0, 1.0, 1.0)];
+    let vertices = [(clip_pos[0], colors[0]), (clip_pos[1], colors[1]), (clip_pos[2], colors[2])];
+    Raster::triangle(&ColorVertexShader, &ColorFragmentShader, &mut depth_0, &mut target_0, &uniform, &vertices[0], &vertices[1], &vertices[2]);
+    Raster::triangle_clip(
+        &ColorFragmentShader,
+        &mut depth_1,
+        &mut target_1,
+        &uniform,
+        clip_pos,
+        [ColorVarying { color: colors[0] }, ColorVarying { color: colors[1] }, ColorVarying { color: colors[2] }],
+    );
+    assert!(target_0.data.iter().filter(|n| **n > 0).count() > 100);
+    assert_eq!(target_0.data, target_1.data);
+    assert_eq!(target_0.colors, target_1.colors);
+    for y in 0..32 {
+        for x in 0..40 {
+            assert_eq!(depth_0.get(x, y), depth_1.get(x, y));
+        }
+    }
+}
+
+#[test]
+fn point_of_size_3_shades_3x3_block() {
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let point = screen(&target, 10.5, 12.5);
+    Raster::point(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &point, 3.0);
+    for y in 0..32 {
+        for x in 0..32 {
+            let inside = (9..=11).contains(&x) && (11..=13).contains(&y);
+            assert_eq!(target.get(x, y), inside as u32, "at {}, {}", x, y);
+        }
+    }
+    assert_eq!(depth.get(10, 12), 1.0);
+}
+
+#[test]
+fn point_is_depth_tested_and_clipped_to_viewport() {
+    let mut target = Target::new(16, 16);
+    let mut depth  = DepthBuffer::new(16, 16);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut near = screen(&target, 8.0, 8.0);
+    near.z = 0.25;
+    Raster::point(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &near, 2.0);
+    // a larger point behind only fills around the nearer one.
+    let far = screen(&target, 8.0, 8.0);
+    Raster::point(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &far, 4.0);
+    assert_eq!(target.data.iter().filter(|n| **n > 0).count(), 16);
+    assert!(target.data.iter().all(|n| *n <= 1));
+    // a point overhanging the corner is clipped.
+    let mut target = Target::new(16, 16);
+    let corner = screen(&target, 0.5, 0.5);
+    depth.clear();
+    Raster::point(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &corner, 5.0);
+    assert_eq!(target.data.iter().filter(|n| **n > 0).count(), 9);
+}
+
+#[test]
+fn screen_door_option_dithers_by_alpha() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut target = Target::new(16, 16);
+    let mut depth  = DepthBuffer::new(16, 16);
+    depth.clear();
+    let color = Vec4::new(1.0, 1.0, 1.0, 0.5);
+    let v0 = (screen(&target, 0.0, 0.0), color);
+    let v1 = (screen(&target, 16.0, 0.0), color);
+    let v2 = (screen(&target, 16.0, 16.0), color);
+    let v3 = (screen(&target, 0.0, 16.0), color);
+    let options = RasterOptions::default().screen_door(true).cull(CullMode::None);
+    for (a, b, c) in [(&v0, &v1, &v2), (&v0, &v2, &v3)] {
+        Raster::triangle_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, a, b, c);
+    }
+    assert_eq!(target.data.iter().filter(|n| **n > 0).count(), 128);
+    for y in 0..16 {
+        for x in 0..16 {
+            let drawn = Dither::screen_door(0.5, x, y);
+            assert_eq!(target.get(x, y), drawn as u32);
+            // discarded fragments leave depth untouched.
+            assert_eq!(depth.get(x as usize, y as usize) == f32::MAX, !drawn);
+        }
+    }
+}
+
+#[test]
+fn clamp_weights_option_keeps_edge_weights_in_range() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut target = Target::new(64, 64);
+    let mut depth  = DepthBuffer::new(64, 64);
+    depth.clear();
+    // the shared edge runs through pixel centers, vertex colors are the
+    // unit axes so the interpolated color is the weights of each fragment.
+    let v0 = (screen(&target, 0.5, 0.5),   Vec4::new(1.0, 0.0, 0.0, 0.0));
+    let v1 = (screen(&target, 63.5, 63.5), Vec4::new(0.0, 1.0, 0.0, 0.0));
+    let v2 = (screen(&target, 63.7, 0.3),  Vec4::new(0.0, 0.0, 1.0, 0.0));
+    let v3 = (screen(&target, 0.1, 63.9),  Vec4::new(0.0, 0.0, 1.0, 0.0));
+    let options = RasterOptions::default().clamp_weights(true).cull(CullMode::None).depth_test(false);
+    let mut on_edge = 0;
+    for (a, b, c) in [(&v0, &v1, &v2), (&v0, &v1, &v3)] {
+        Raster::triangle_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, a, b, c);
+    }
+    for y in 0..64 {
+        for x in 0..64 {
+            if target.get(x, y) == 0 {
+                continue;
+            }
+            if x == y {
+                on_edge += 1;
+            }
+            let weights = target.color(x, y);
+            assert!(weights.x >= 0.0 && weights.y >= 0.0 && weights.z >= 0.0, "weights at {}, {} were {:?}", x, y, weights);
+            assert!((weights.x + weights.y + weights.z - 1.0).abs() < 1e-5, "weights at {}, {} were {:?}", x, y, weights);
+        }
+    }
+    assert!(on_edge > 0);
+}
+
+struct QuadColorFragmentShader;
+impl QuadFragmentProgram for QuadColorFragmentShader {
+    type Uniform = Uniform;
+    type Varying = ColorVarying;
+    fn main(&self, _uniform: &Uniform, varying: &ColorVarying, _quad: &Quad<ColorVarying>) -> Option<Vec4> {
+        Some(varying.color)
+    }
+}
+
+fn assert_screen_door_without_depth(name: &str, target: &Target, depth: &DepthBuffer) {
+    for y in 0..16 {
+        for x in 0..16 {
+            assert_eq!(target.get(x, y), Dither::screen_door(0.5, x, y) as u32, "{} at {}, {}", name, x, y);
+            assert_eq!(depth.get(x as usize, y as usize), f32::MAX, "{} wrote depth at {}, {}", name, x, y);
+        }
+    }
+}
+
+#[test]
+fn with_options_draw_paths_apply_options() {
+    let uniform  = Uniform { matrix: Mat4::identity() };
+    let buffers  = || {
+        let mut depth = DepthBuffer::new(16, 16);
+        depth.clear();
+        (Target::new(16, 16), depth)
+    };
+    let (target, _) = buffers();
+    let color    = Vec4::new(1.0, 1.0, 1.0, 0.5);
+    let vertices = [
+        (screen(&target, 0.0, 0.0), color),
+        (screen(&target, 16.0, 0.0), color),
+        (screen(&target, 16.0, 16.0), color),
+        (screen(&target, 0.0, 16.0), color),
+    ];
+    let indices  = [0, 1, 2, 0, 2, 3];
+    let options  = RasterOptions::default().screen_door(true).cull(CullMode::None).depth_write(false);
+
+    let (mut target, mut depth) = buffers();
+    Raster::mesh_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &vertices, &indices);
+    assert_screen_door_without_depth("mesh", &target, &depth);
+
+    let (mut target, mut depth) = buffers();
+    Raster::mesh_binned_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &vertices, &indices);
+    assert_screen_door_without_depth("mesh_binned", &target, &depth);
+
+    let (mut target, mut depth) = buffers();
+    Raster::mesh_quad_with_options(&options, &ColorVertexShader, &QuadColorFragmentShader, &mut depth, &mut target, &uniform, &vertices, &indices);
+    assert_screen_door_without_depth("mesh_quad", &target, &depth);
+
+    let (mut target, mut depth) = buffers();
+    let halfspace = options.traversal(Traversal::HalfSpace);
+    Raster::mesh_with_options(&halfspace, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &vertices, &indices);
+    assert_screen_door_without_depth("mesh halfspace", &target, &depth);
+
+    let (mut target, mut depth) = buffers();
+    for triangle in indices.chunks_exact(3) {
+        let clip_pos = [vertices[triangle[0] as usize].0, vertices[triangle[1] as usize].0, vertices[triangle[2] as usize].0];
+        let varyings = [ColorVarying { color }, ColorVarying { color }, ColorVarying { color }];
+        Raster::triangle_clip_with_options(&options, &ColorFragmentShader, &mut depth, &mut target, &uniform, clip_pos, varyings);
+    }
+    assert_screen_door_without_depth("triangle_clip", &target, &depth);
+
+    let (mut target, mut depth) = buffers();
+    let positions = [Vec2::new(0.0, 0.0), Vec2::new(16.0, 0.0), Vec2::new(16.0, 16.0), Vec2::new(0.0, 16.0)];
+    let varyings  = [ColorVarying { color }, ColorVarying { color }, ColorVarying { color }, ColorVarying { color }];
+    Raster::convex_polygon_with_options(&options, &positions, &varyings, &ColorFragmentShader, &mut depth, &mut target, &uniform);
+    assert_screen_door_without_depth("convex_polygon", &target, &depth);
+}
+
+#[test]
+fn with_options_line_point_instanced_and_mesh_apply_options() {
+    let uniform  = Uniform { matrix: Mat4::identity() };
+    let buffers  = || {
+        let mut depth = DepthBuffer::new(16, 16);
+        depth.clear();
+        (Target::new(16, 16), depth)
+    };
+    let (target, _) = buffers();
+    let color    = Vec4::new(1.0, 1.0, 1.0, 0.5);
+    let vertices = [
+        (screen(&target, 0.0, 0.0), color),
+        (screen(&target, 16.0, 0.0), color),
+        (screen(&target, 16.0, 16.0), color),
+        (screen(&target, 0.0, 16.0), color),
+    ];
+    let indices  = [0, 1, 2, 0, 2, 3];
+    let options  = RasterOptions::default().screen_door(true).cull(CullMode::None).depth_write(false);
+
+    let (mut target, mut depth) = buffers();
+    Raster::mesh_instanced_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &[uniform], &vertices, &indices);
+    assert_screen_door_without_depth("mesh_instanced", &target, &depth);
+
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let (mut target, mut depth) = buffers();
+    Mesh::new(vertices.to_vec(), indices.to_vec()).draw_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform);
+    assert_screen_door_without_depth("Mesh::draw", &target, &depth);
+
+    let (mut target, mut depth) = buffers();
+    let center = (screen(&target, 8.0, 8.0), color);
+    Raster::point_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &center, 16.0);
+    assert_screen_door_without_depth("point", &target, &depth);
+
+    let (mut target, mut depth) = buffers();
+    let v0 = (screen(&target, 0.0, 8.5), color);
+    let v1 = (screen(&target, 16.0, 8.5), color);
+    Raster::line_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &v0, &v1);
+    for x in 0..16 {
+        assert_eq!(target.get(x, 8), Dither::screen_door(0.5, x, 8) as u32, "line at {}, 8", x);
+        assert_eq!(depth.get(x as usize, 8), f32::MAX, "line wrote depth at {}, 8", x);
+    }
+
+    // depth bias moves a line in front of or behind a coplanar line.
+    let (mut target, mut depth) = buffers();
+    Raster::line(&ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &v0, &v1);
+    Raster::line_with_options(&RasterOptions::default().depth_bias(0.001), &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &v0, &v1);
+    assert!((0..16).all(|x| target.get(x, 8) == 1));
+    Raster::line_with_options(&RasterOptions::default().depth_bias(-0.001), &ColorVertexShader, &ColorFragmentShader, &mut depth, &mut target, &uniform, &v0, &v1);
+    assert!((0..16).all(|x| target.get(x, 8) == 2));
+}
+
+#[test]
+fn with_options_triangle_depth_and_occlusion_query_apply_options() {
+    let target  = Target::new(32, 32);
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let quad    = [
+        screen(&target, 8.0, 8.0),
+        screen(&target, 24.0, 8.0),
+        screen(&target, 24.0, 24.0),
+        screen(&target, 8.0, 24.0),
+    ];
+    let front   = vec![0, 2, 1, 0, 3, 2];
+
+    // triangle_depth writes both faces, the options variant culls.
+    let mut depth = DepthBuffer::new(32, 32);
+    Raster::triangle_depth_with_options(&RasterOptions::default(), &mut depth, &quad[0], &quad[1], &quad[2]);
+    assert!(depth_values(&depth).iter().all(|value| *value == f32::MAX));
+    Raster::triangle_depth_with_options(&RasterOptions::default().depth_write(false), &mut depth, &quad[0], &quad[2], &quad[1]);
+    assert!(depth_values(&depth).iter().all(|value| *value == f32::MAX));
+    Raster::triangle_depth_with_options(&RasterOptions::default().depth_bias(-0.25), &mut depth, &quad[0], &quad[2], &quad[1]);
+    assert_eq!(depth.get(20, 12), 0.75);
+
+    // a coplanar query fails the depth test unless biased toward the camera.
+    let mut depth  = DepthBuffer::new(32, 32);
+    let mut target = Target::new(32, 32);
+    Raster::mesh(&VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, &quad, &front);
+    let biased = RasterOptions::default().depth_bias(-0.001);
+    assert_eq!(Raster::occlusion_query(&VertexShader, &depth, &uniform, &quad, &front), 0);
+    assert_eq!(Raster::occlusion_query_with_options(&biased, &VertexShader, &depth, &uniform, &quad, &front), 256);
+    assert_eq!(Raster::occlusion_query_with_options(&biased.cull(CullMode::Front), &VertexShader, &depth, &uniform, &quad, &front), 0);
+    assert_eq!(Raster::occlusion_query_with_options(&RasterOptions::default().depth_test(false), &VertexShader, &depth, &uniform, &quad, &front), 256);
+}
+
+#[test]
+fn triangle_mrt_with_options_applies_cull() {
+    let mut targets = [Target::new(32, 32), Target::new(32, 32)];
+    let mut depth   = DepthBuffer::new(32, 32);
+    depth.clear();
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let v0 = screen(&targets[0], 0.0, 0.0);
+    let v1 = screen(&targets[0], 32.0, 0.0);
+    let v2 = screen(&targets[0], 0.0, 32.0);
+    let options = RasterOptions::default().cull(CullMode::Front);
+    Raster::triangle_mrt_with_options(&options, &VertexShader, &TwoOutputFragmentShader, &mut depth, &mut targets, &uniform, &v0, &v2, &v1);
+    assert!(targets.iter().all(|target| target.data.iter().all(|n| *n == 0)));
+    Raster::triangle_mrt_with_options(&options, &VertexShader, &TwoOutputFragmentShader, &mut depth, &mut targets, &uniform, &v0, &v1, &v2);
+    assert_eq!(targets[0].get(4, 4), 1);
+    assert_eq!(targets[1].get(4, 4), 1);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn mesh_parallel_with_options_matches_mesh_with_options() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut target_0 = Target::new(16, 16);
+    let mut target_1 = Target::new(16, 16);
+    let mut depth_0  = DepthBuffer::new(16, 16);
+    let mut depth_1  = DepthBuffer::new(16, 16);
+    depth_0.clear();
+    depth_1.clear();
+    let color    = Vec4::new(1.0, 1.0, 1.0, 0.5);
+    let vertices = vec![
+        (screen(&target_0, 0.0, 0.0), color),
+        (screen(&target_0, 16.0, 0.0), color),
+        (screen(&target_0, 16.0, 16.0), color),
+        (screen(&target_0, 0.0, 16.0), color),
+    ];
+    let indices = vec![0, 1, 2, 0, 2, 3];
+    let options = RasterOptions::default().screen_door(true).cull(CullMode::None).depth_write(false);
+    Raster::mesh_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth_0, &mut target_0, &uniform, &vertices, &indices);
+    Raster::mesh_parallel_with_options(&options, &ColorVertexShader, &ColorFragmentShader, &mut depth_1, &mut target_1, &uniform, &vertices, &indices);
+    assert_screen_door_without_depth("mesh_parallel", &target_1, &depth_1);
+    assert_eq!(target_0.data, target_1.data);
+}
+
+#[test]
+fn triangle_shared_edges_write_each_pixel_exactly_once() {
+    let uniform = Uniform { matrix: Mat4::identity() };
+    let mut target = Target::new(32, 32);
+    let mut depth  = DepthBuffer::new(32, 32);
+    depth.clear();
+    let options = RasterOptions::default().cull(CullMode::None).depth_test(false);
+    // a 4x4 grid of cells spanning the target, interior vertices jittered
+    // off pixel centers and edges, each cell split into two triangles.
+    let point = |i: usize, j: usize| {
+        let jitter = |n: usize, k: usize| if n == 0 || n == 4 { 0.0 } else { (((n * 7 + k * 13) % 5) as f32 - 2.0) * 0.73 };
+        screen(&target, (i * 8) as f32 + jitter(i, j), (j * 8) as f32 + jitter(j, i))
+    };
+    let points: Vec<Vec<Vec4>> = (0..5).map(|i| (0..5).map(|j| point(i, j)).collect()).collect();
+    for j in 0..4 {
+        for i in 0..4 {
+            let (v0, v1, v2, v3) = (points[i][j], points[i + 1][j], points[i + 1][j + 1], points[i][j + 1]);
+            let diagonal = if (i + j) % 2 == 0 { [(v0, v1, v2), (v0, v2, v3)] } else { [(v0, v1, v3), (v1, v2, v3)] };
+            for (a, b, c) in diagonal.iter() {
+                Raster::triangle_with_options(&options, &VertexShader, &FragmentShader, &mut depth, &mut target, &uniform, a, b, c);
+            }
+        }
+    }
+    for y in 0..32 {
+        for x in 0..32 {
+            assert_eq!(target.get(x, y), 1, "pixel {}, {} written {} times", x, y, target.get(x, y));
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "one varying per position")]
+fn convex_polygon_panics_on_mismatched_lengths_in_debug() {
+    let mut target = Target::new(16, 16);
+    let mut depth  = DepthBuffer::new(16, 16);
+    depth.clear();
+    let uniform   = Uniform { matrix: Mat4::identity() };
+    let positions = [Vec2::new(2.0, 2.0), Vec2::new(14.0, 2.0), Vec2::new(14.0, 14.0), Vec2::new(2.0, 14.0)];
+    let varyings  = [ColorVarying { color: Vec4::one() }, ColorVarying { color: Vec4::one() }, ColorVarying { color: Vec4::one() }];
+    Raster::convex_polygon(&positions, &varyings, &ColorFragmentShader, &mut depth, &mut target, &uniform);
+}
@@ -0,0 +1,81 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::Rect;
+
+#[test]
+fn contains_includes_top_left_and_excludes_bottom_right() {
+    let rect = Rect::new(2, 3, 4, 5);
+    assert!(rect.contains(2, 3));
+    assert!(rect.contains(5, 7));
+    assert!(!rect.contains(6, 7));
+    assert!(!rect.contains(5, 8));
+    assert!(!rect.contains(1, 3));
+    assert!(!Rect::new(2, 3, 0, 5).contains(2, 3));
+}
+
+#[test]
+fn is_empty_for_zero_or_negative_size() {
+    assert!(!Rect::new(0, 0, 1, 1).is_empty());
+    assert!(Rect::new(0, 0, 0, 1).is_empty());
+    assert!(Rect::new(0, 0, 1, 0).is_empty());
+    assert!(Rect::new(0, 0, -4, 4).is_empty());
+    assert!(Rect::default().is_empty());
+}
+
+#[test]
+fn intersect_returns_overlap() {
+    let a = Rect::new(0, 0, 10, 10);
+    let b = Rect::new(5, -2, 10, 4);
+    assert_eq!(a.intersect(&b), Rect::new(5, 0, 5, 2));
+    assert_eq!(b.intersect(&a), Rect::new(5, 0, 5, 2));
+    assert_eq!(a.intersect(&a), a);
+    // a rect inside another.
+    assert_eq!(a.intersect(&Rect::new(2, 2, 3, 3)), Rect::new(2, 2, 3, 3));
+}
+
+#[test]
+fn intersect_is_empty_when_disjoint_or_touching() {
+    let a = Rect::new(0, 0, 10, 10);
+    assert!(a.intersect(&Rect::new(20, 20, 5, 5)).is_empty());
+    // shares only the right edge, which is exclusive.
+    assert!(a.intersect(&Rect::new(10, 0, 5, 10)).is_empty());
+    assert!(a.intersect(&Rect::new(2, 2, 0, 0)).is_empty());
+    assert!(Rect::default().intersect(&a).is_empty());
+}
+
+#[test]
+fn union_bounds_both_and_ignores_empty() {
+    let a = Rect::new(0, 0, 4, 4);
+    let b = Rect::new(6, 2, 2, 6);
+    assert_eq!(a.union(&b), Rect::new(0, 0, 8, 8));
+    assert_eq!(b.union(&a), Rect::new(0, 0, 8, 8));
+    assert_eq!(a.union(&Rect::new(100, 100, 0, 0)), a);
+    assert_eq!(Rect::new(-50, -50, 0, 3).union(&b), b);
+    assert!(Rect::default().union(&Rect::new(5, 5, -1, 2)).is_empty());
+}
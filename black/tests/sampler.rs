@@ -0,0 +1,203 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{CheckerSampler, Cubemap, GradientSampler, Sampler, SolidSampler, Texture2D, Vec2, Vec3, Vec4, WrapMode};
+
+fn approx(v0: &Vec4, v1: &Vec4) -> bool {
+    (v0.x - v1.x).abs() < 1e-5 && (v0.y - v1.y).abs() < 1e-5 && (v0.z - v1.z).abs() < 1e-5 && (v0.w - v1.w).abs() < 1e-5
+}
+
+#[test]
+fn solid_sampler_returns_color() {
+    let color   = Vec4::new(0.1, 0.2, 0.3, 1.0);
+    let sampler = SolidSampler::new(color);
+    assert_eq!(sampler.sample(&Vec2::new(0.0, 0.0)), color);
+    assert_eq!(sampler.sample(&Vec2::new(0.7, 0.2)), color);
+}
+
+#[test]
+fn gradient_sampler_interpolates_along_u() {
+    let black   = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let white   = Vec4::new(1.0, 1.0, 1.0, 1.0);
+    let sampler = GradientSampler::new(black, white);
+    assert!(approx(&sampler.sample(&Vec2::new(0.0, 0.3)), &black));
+    assert!(approx(&sampler.sample(&Vec2::new(1.0, 0.3)), &white));
+    assert!(approx(&sampler.sample(&Vec2::new(0.5, 0.9)), &Vec4::new(0.5, 0.5, 0.5, 1.0)));
+}
+
+#[test]
+fn checker_sampler_alternates_cells() {
+    let black   = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let white   = Vec4::new(1.0, 1.0, 1.0, 1.0);
+    let sampler = CheckerSampler::new(4.0, white, black);
+
+    // cell centers are unfiltered.
+    assert!(approx(&sampler.sample(&Vec2::new(0.125, 0.125)), &white));
+    assert!(approx(&sampler.sample(&Vec2::new(0.375, 0.125)), &black));
+    assert!(approx(&sampler.sample(&Vec2::new(0.375, 0.375)), &white));
+
+    // cell edges are filtered between neighbours.
+    assert!(approx(&sampler.sample(&Vec2::new(0.25, 0.125)), &Vec4::new(0.5, 0.5, 0.5, 1.0)));
+}
+
+#[test]
+fn wrap_mode_repeat() {
+    assert!((WrapMode::Repeat.apply(1.25) - 0.25).abs() < 1e-6);
+    assert!((WrapMode::Repeat.apply(-0.25) - 0.75).abs() < 1e-6);
+    assert!((WrapMode::Repeat.apply(0.5) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn wrap_mode_clamp() {
+    assert_eq!(WrapMode::Clamp.apply(1.25), 1.0);
+    assert_eq!(WrapMode::Clamp.apply(-0.25), 0.0);
+    assert_eq!(WrapMode::Clamp.apply(0.5), 0.5);
+}
+
+#[test]
+fn wrap_mode_mirror() {
+    assert!((WrapMode::Mirror.apply(1.25) - 0.75).abs() < 1e-6);
+    assert!((WrapMode::Mirror.apply(-0.25) - 0.25).abs() < 1e-6);
+    assert!((WrapMode::Mirror.apply(2.25) - 0.25).abs() < 1e-6);
+    assert!((WrapMode::Mirror.apply(0.5) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn samplers_accept_out_of_range_coordinates() {
+    let black   = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let white   = Vec4::new(1.0, 1.0, 1.0, 1.0);
+    let checker = CheckerSampler::new(4.0, white, black);
+    assert!(approx(&checker.sample(&Vec2::new(-0.875, 1.125)), &white));
+    let gradient = GradientSampler::new(black, white);
+    assert!(approx(&gradient.sample(&Vec2::new(-3.0, 0.0)), &black));
+    assert!(approx(&gradient.sample_wrapped(&Vec2::new(1.25, 0.0), WrapMode::Repeat), &Vec4::new(0.25, 0.25, 0.25, 1.0)));
+}
+
+#[test]
+fn texture_samples_texel_centers_and_filters_between() {
+    let black = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let white = Vec4::new(1.0, 1.0, 1.0, 1.0);
+    let mut texture = Texture2D::from_data(2, 1, vec![black, white]);
+    texture.wrap = WrapMode::Clamp;
+    assert!(approx(&texture.sample(&Vec2::new(0.25, 0.5)), &black));
+    assert!(approx(&texture.sample(&Vec2::new(0.75, 0.5)), &white));
+    assert!(approx(&texture.sample(&Vec2::new(0.5, 0.5)), &Vec4::new(0.5, 0.5, 0.5, 1.0)));
+    assert!(approx(&texture.sample(&Vec2::new(1.5, 0.5)), &white));
+
+    // repeat filters across the edge.
+    texture.wrap = WrapMode::Repeat;
+    assert!(approx(&texture.sample(&Vec2::new(0.0, 0.5)), &Vec4::new(0.5, 0.5, 0.5, 1.0)));
+}
+
+#[test]
+fn texture_samples_huge_and_non_finite_uvs_without_panic() {
+    let black = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let white = Vec4::new(1.0, 1.0, 1.0, 1.0);
+    let mut texture = Texture2D::from_data(2, 1, vec![black, white]);
+    for wrap in [WrapMode::Repeat, WrapMode::Clamp, WrapMode::Mirror] {
+        texture.wrap = wrap;
+        for value in [1e10, -1e10, f32::MAX, f32::MIN, f32::INFINITY, f32::NEG_INFINITY, f32::NAN] {
+            texture.sample(&Vec2::new(value, 0.5));
+            texture.sample(&Vec2::new(0.5, value));
+        }
+    }
+    // clamped infinities resolve to the edge texels.
+    texture.wrap = WrapMode::Clamp;
+    assert!(approx(&texture.sample(&Vec2::new(f32::INFINITY, 0.5)), &white));
+    assert!(approx(&texture.sample(&Vec2::new(f32::NEG_INFINITY, 0.5)), &black));
+}
+
+fn face(center: Vec4) -> Texture2D {
+    let mut texture = Texture2D::new(3, 3);
+    texture.set(1, 1, center);
+    texture
+}
+
+#[test]
+fn cubemap_samples_center_of_dominant_face() {
+    let colors = [
+        Vec4::new(1.0, 0.0, 0.0, 1.0),
+        Vec4::new(0.0, 1.0, 0.0, 1.0),
+        Vec4::new(0.0, 0.0, 1.0, 1.0),
+        Vec4::new(1.0, 1.0, 0.0, 1.0),
+        Vec4::new(0.0, 1.0, 1.0, 1.0),
+        Vec4::new(1.0, 0.0, 1.0, 1.0),
+    ];
+    let cubemap = Cubemap::new([face(colors[0]), face(colors[1]), face(colors[2]), face(colors[3]), face(colors[4]), face(colors[5])]);
+    let directions = [
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(-2.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Vec3::new(0.0, -1.0, 0.0),
+        Vec3::new(0.0, 0.0, 3.0),
+        Vec3::new(0.0, 0.0, -1.0),
+    ];
+    for (direction, color) in directions.iter().zip(colors.iter()) {
+        assert!(approx(&cubemap.sample(direction), color));
+    }
+}
+
+#[test]
+fn cubemap_face_uv_spans_face() {
+    // +X face, s runs along -z and t along -y.
+    let (face, uv) = Cubemap::face_uv(&Vec3::new(1.0, 1.0, 1.0));
+    assert_eq!(face, 0);
+    assert_eq!(uv, Vec2::new(0.0, 0.0));
+    let (face, uv) = Cubemap::face_uv(&Vec3::new(1.0, -1.0, -1.0));
+    assert_eq!(face, 0);
+    assert_eq!(uv, Vec2::new(1.0, 1.0));
+    let (face, uv) = Cubemap::face_uv(&Vec3::new(0.5, 0.0, -1.0));
+    assert_eq!(face, 5);
+    assert_eq!(uv, Vec2::new(0.25, 0.5));
+}
+
+#[test]
+fn texture_lod_increases_by_one_when_uv_rate_doubles() {
+    let size = Vec2::new(256.0, 128.0);
+    let ddx  = Vec2::new(1.0 / 256.0, 0.0);
+    let ddy  = Vec2::new(0.0, 1.0 / 128.0);
+    assert_eq!(Texture2D::compute_lod(&ddx, &ddy, &size), 0.0);
+    let mut previous = Texture2D::compute_lod(&ddx, &ddy, &size);
+    for n in 1..6 {
+        let scale = (1 << n) as f32;
+        let lod   = Texture2D::compute_lod(&Vec2::scale(&ddx, scale), &Vec2::scale(&ddy, scale), &size);
+        assert_eq!(lod, previous + 1.0);
+        previous = lod;
+    }
+}
+
+#[test]
+fn texture_lod_uses_longer_footprint_axis() {
+    let texture = Texture2D::new(64, 64);
+    let ddx     = Vec2::new(4.0 / 64.0, 0.0);
+    let ddy     = Vec2::new(0.0, 1.0 / 64.0);
+    assert_eq!(texture.lod(&ddx, &ddy), 2.0);
+    assert_eq!(texture.lod(&ddy, &ddx), 2.0);
+    // magnification returns a negative level.
+    assert_eq!(texture.lod(&Vec2::scale(&ddy, 0.5), &Vec2::scale(&ddy, 0.5)), -1.0);
+}
@@ -0,0 +1,64 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use black::{Mat4, Mesh, ShadowMap, Vec3};
+
+fn quad(y: f32, size: f32) -> Mesh<Vec3> {
+    Mesh::new(
+        vec![
+            Vec3::new(-size, y, -size),
+            Vec3::new( size, y, -size),
+            Vec3::new( size, y,  size),
+            Vec3::new(-size, y,  size),
+        ],
+        vec![0, 1, 2, 0, 2, 3],
+    )
+}
+
+#[test]
+fn occluder_casts_shadow_on_receiver() {
+    let view       = Mat4::look_at(&Vec3::new(0.0, 10.0, 0.0), &Vec3::zero(), &Vec3::unit_z());
+    let projection = Mat4::perspective_fov(1.0, 1.0, 0.1, 100.0);
+    let light_vp   = view * projection;
+    let occluder   = quad(5.0, 1.0);
+    let receiver   = quad(0.0, 5.0);
+    let mut shadow = ShadowMap::new(128, 128);
+    shadow.render(&occluder, &light_vp, |v| *v);
+    shadow.render_additional(&receiver, |v| *v);
+
+    // receiver texels beneath the occluder are in shadow.
+    assert_eq!(shadow.sample(&Vec3::new(0.0, 0.0, 0.0)), 1.0);
+    assert_eq!(shadow.sample(&Vec3::new(1.5, 0.0, -1.5)), 1.0);
+
+    // receiver texels outside the occluder's shadow are lit.
+    assert_eq!(shadow.sample(&Vec3::new(3.0, 0.0, 3.0)), 0.0);
+    assert_eq!(shadow.sample(&Vec3::new(-3.0, 0.0, 0.5)), 0.0);
+
+    // the occluder itself is lit.
+    assert_eq!(shadow.sample(&Vec3::new(0.5, 5.0, 0.5)), 0.0);
+}
@@ -0,0 +1,136 @@
+//! Renders a model to a PPM image without a window and exits.
+//!
+//! cargo run --bin headless -- --model ./models/bunny.obj --output bunny.ppm
+//!
+//! Options are --model, --output, --width, --height, --frames and --eye x,y,z.
+//! The light animates as in the windowed example, with frames advancing a
+//! fixed step so renders are repeatable. Only the last frame is written.
+
+#[allow(dead_code)]
+#[path = "../helpers/geometry.rs"]
+mod geometry;
+
+use black::{ Raster, DepthBuffer, FragmentProgram, ImageBuffer, Interpolate, Light, VertexProgram };
+use black::{ Mat4, Vec3, Vec4 };
+use geometry::{ Geometry, Vertex };
+use std::env;
+use std::process;
+
+pub struct Uniform {
+    pub projection: Mat4,
+    pub view:       Mat4,
+    pub light:      Vec3
+}
+
+#[derive(Interpolate)]
+struct Varying {
+    pub color:  Vec4,
+    pub normal: Vec3,
+}
+
+struct VertexShader; impl VertexProgram for VertexShader {
+    type Uniform = Uniform;
+    type Vertex  = Vertex;
+    type Varying = Varying;
+    fn main(&self, uniform: &Uniform, input: &Vertex, varying: &mut Varying) -> Vec4 {
+        varying.color  = input.color;
+        varying.normal = input.normal;
+        input.position * (uniform.view * uniform.projection)
+    }
+}
+
+struct FragmentShader; impl FragmentProgram for FragmentShader {
+    type Uniform = Uniform;
+    type Varying = Varying;
+    fn main(&self, uniform: &Uniform, varying: &Varying) -> Option<Vec4> {
+        let normal  = Vec3::normalize_or_zero(&varying.normal);
+        let diffuse = Light::lambert(&normal, &Vec3::normalize(&uniform.light));
+        let shade   = 0.2 + diffuse * 0.8;
+        Some(Vec4::new(varying.color.x * shade, varying.color.y * shade, varying.color.z * shade, 1.0))
+    }
+}
+
+struct Options {
+    model:  String,
+    output: String,
+    width:  usize,
+    height: usize,
+    frames: usize,
+    eye:    Vec3,
+}
+
+fn parse_vec3(value: &str) -> Option<Vec3> {
+    let parts = value.split(',').map(|n| n.trim().parse::<f32>().ok()).collect::<Option<Vec<_>>>()?;
+    match parts.as_slice() {
+        [x, y, z] => Some(Vec3::new(*x, *y, *z)),
+        _ => None,
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        model:  "./models/bunny.obj".to_string(),
+        output: "headless.ppm".to_string(),
+        width:  960,
+        height: 480,
+        frames: 1,
+        eye:    Vec3::new(0.0, 2.25, 3.0),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(format!("missing value for {}", arg))?;
+        let invalid = || format!("invalid value for {}: {}", arg, value);
+        match arg.as_str() {
+            "--model"  => options.model  = value.clone(),
+            "--output" => options.output = value.clone(),
+            "--width"  => options.width  = value.parse().map_err(|_| invalid())?,
+            "--height" => options.height = value.parse().map_err(|_| invalid())?,
+            "--frames" => options.frames = value.parse().map_err(|_| invalid())?,
+            "--eye"    => options.eye    = parse_vec3(value).ok_or_else(invalid)?,
+            _ => return Err(format!("unknown option {}", arg)),
+        }
+    }
+    if options.width == 0 || options.height == 0 || options.frames == 0 {
+        return Err("width, height and frames must be greater than zero".to_string());
+    }
+    Ok(options)
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let options = parse_options(&args).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        eprintln!("usage: headless [--model path] [--output path] [--width n] [--height n] [--frames n] [--eye x,y,z]");
+        process::exit(1);
+    });
+    let geometry = Geometry::obj(&options.model).unwrap_or_else(|error| {
+        eprintln!("unable to load {}: {}", options.model, error);
+        process::exit(1);
+    });
+
+    let (width, height) = (options.width, options.height);
+    let mut image   = ImageBuffer::new(width, height);
+    let mut depth   = DepthBuffer::new(width, height);
+    let mut uniform = Uniform {
+        projection: Mat4::perspective_fov(70.0_f32.to_radians(), width as f32 / height as f32, 0.1, 1000.0),
+        view:       Mat4::look_at(&options.eye, &Vec3::new(0.0, 0.25, 0.0), &Vec3::unit_y()),
+        light:      Vec3::new(0.0, 10.0, 0.0),
+    };
+    let mut time = 0.0;
+    for _ in 0..options.frames {
+        image.clear(Vec4::new(0.067, 0.067, 0.067, 1.0));
+        depth.clear();
+        uniform.light = Vec3::rotate_around(&Vec3::new(10.0, 10.0, 0.0), &Vec3::zero(), &Vec3::unit_y(), -time * 4.2);
+        for n in (0..geometry.indices.len()).step_by(3) {
+            let v0 = &geometry.vertices[geometry.indices[n]];
+            let v1 = &geometry.vertices[geometry.indices[n + 1]];
+            let v2 = &geometry.vertices[geometry.indices[n + 2]];
+            Raster::triangle(&VertexShader, &FragmentShader, &mut depth, &mut image, &uniform, v0, v1, v2);
+        }
+        time += 0.01;
+    }
+    if let Err(error) = image.save_ppm(&options.output) {
+        eprintln!("unable to write {}: {}", options.output, error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,59 @@
+use black::{Mat4, Vec2, Vec3};
+
+/// Keeps the camera from passing over the poles, where look_at is undefined.
+const PITCH_LIMIT: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+/// ---------------------------------------------------
+/// OrbitCameraController
+/// ---------------------------------------------------
+/// Orbits a camera around a target on a sphere. Yaw turns about the y axis
+/// and pitch raises the camera above the target's horizon. Mouse drag
+/// deltas in pixels are scaled by sensitivity to radians.
+#[derive(Debug, Clone)]
+pub struct OrbitCameraController {
+    pub target:       Vec3,
+    pub distance:     f32,
+    pub yaw:          f32,
+    pub pitch:        f32,
+    pub sensitivity:  f32,
+    pub min_distance: f32,
+}
+impl OrbitCameraController {
+    /// Creates a controller orbiting target with the camera at position.
+    pub fn new(target: Vec3, position: Vec3) -> OrbitCameraController {
+        let offset   = position - target;
+        let distance = Vec3::length(&offset);
+        OrbitCameraController {
+            target,
+            distance,
+            yaw:          f32::atan2(offset.x, offset.z),
+            pitch:        f32::asin(offset.y / distance).clamp(-PITCH_LIMIT, PITCH_LIMIT),
+            sensitivity:  0.01,
+            min_distance: 0.1,
+        }
+    }
+    /// Rotates by a mouse drag delta in pixels. Dragging right moves the
+    /// camera left around the target and dragging down lowers it.
+    pub fn rotate(&mut self, delta: &Vec2) {
+        self.yaw   -= delta.x * self.sensitivity;
+        self.pitch  = (self.pitch - delta.y * self.sensitivity).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+    /// Scales the distance to the target, amounts below 1.0 move closer.
+    pub fn zoom(&mut self, amount: f32) {
+        self.distance = f32::max(self.distance * amount, self.min_distance);
+    }
+    /// Returns the camera position.
+    pub fn position(&self) -> Vec3 {
+        let n0 = f32::cos(self.pitch);
+        let offset = Vec3::new(
+            f32::sin(self.yaw) * n0,
+            f32::sin(self.pitch),
+            f32::cos(self.yaw) * n0,
+        );
+        self.target + (offset * self.distance)
+    }
+    /// Returns the view matrix looking from the camera position at the target.
+    pub fn view(&self) -> Mat4 {
+        Mat4::look_at(&self.position(), &self.target, &Vec3::unit_y())
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// The number of frames averaged for the fps readout.
+const FPS_SAMPLES: usize = 60;
+
+/// ---------------------------------------------------
+/// Clock
+/// ---------------------------------------------------
+/// Measures real frame times. Call tick() once per frame, then read
+/// delta_seconds() to animate independent of frame rate and fps() for
+/// a readout averaged over the last 60 frames.
+pub struct Clock {
+    start:   Instant,
+    last:    Option<f64>,
+    elapsed: f64,
+    delta:   f64,
+    samples: VecDeque<f64>,
+}
+impl Clock {
+    pub fn new() -> Clock {
+        Clock {
+            start:   Instant::now(),
+            last:    None,
+            elapsed: 0.0,
+            delta:   0.0,
+            samples: VecDeque::with_capacity(FPS_SAMPLES),
+        }
+    }
+    /// Records a frame at the current time.
+    #[allow(dead_code)]
+    pub fn tick(&mut self) {
+        let now = self.start.elapsed().as_secs_f64();
+        self.tick_at(now);
+    }
+    /// Records a frame at the given time in seconds. Times are expected to
+    /// increase, the first frame has a delta of zero.
+    pub fn tick_at(&mut self, seconds: f64) {
+        self.delta = match self.last {
+            Some(last) => f64::max(seconds - last, 0.0),
+            None => 0.0,
+        };
+        if self.last.is_some() {
+            if self.samples.len() == FPS_SAMPLES {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(self.delta);
+        }
+        self.elapsed += self.delta;
+        self.last = Some(seconds);
+    }
+    /// Returns the seconds between the last two ticks.
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta as f32
+    }
+    /// Returns the seconds accumulated since the first tick.
+    #[allow(dead_code)]
+    pub fn elapsed_seconds(&self) -> f32 {
+        self.elapsed as f32
+    }
+    /// Returns the frames per second averaged over recent frames, or zero
+    /// before two ticks have been recorded.
+    pub fn fps(&self) -> f32 {
+        let total: f64 = self.samples.iter().sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        (self.samples.len() as f64 / total) as f32
+    }
+}
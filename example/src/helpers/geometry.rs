@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
 use black::{Vec2, Vec3, Vec4};
@@ -16,9 +17,28 @@ impl GeometryParseError {
     }
 }
 #[derive(Debug)]
+pub struct GeometryValidationError {
+    message: String,
+}
+impl GeometryValidationError {
+    pub fn new(message: String) -> GeometryValidationError {
+        GeometryValidationError { message }
+    }
+}
+#[derive(Debug)]
 pub enum GeometryError {
     IoError(std::io::Error),
     ParseError(GeometryParseError),
+    Validation(GeometryValidationError),
+}
+impl std::fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GeometryError::IoError(error)         => write!(f, "io error: {}", error),
+            GeometryError::ParseError(error)      => write!(f, "parse error: {}", error.message),
+            GeometryError::Validation(error)      => write!(f, "validation error: {}", error.message),
+        }
+    }
 }
 impl From<std::num::ParseFloatError> for GeometryError {
     fn from(error: std::num::ParseFloatError) -> GeometryError {
@@ -209,6 +229,113 @@ impl Geometry {
         ];
         Geometry { vertices, indices }
     }
+    /// Splits each triangle into four by inserting a vertex at the midpoint
+    /// of each edge, repeated for the given number of levels. Midpoint
+    /// attributes are averaged from the edge end points and normals are
+    /// renormalized. Edges shared between triangles share midpoints.
+    #[allow(dead_code)]
+    pub fn subdivide(&self, levels: usize) -> Geometry {
+        let mut geometry = self.clone();
+        for _ in 0..levels {
+            let mut vertices = geometry.vertices.clone();
+            let mut indices  = Vec::with_capacity(geometry.indices.len() * 4);
+            let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+            let mut midpoint = |i0: usize, i1: usize, vertices: &mut Vec<Vertex>| -> usize {
+                let key = if i0 < i1 { (i0, i1) } else { (i1, i0) };
+                *midpoints.entry(key).or_insert_with(|| {
+                    let v0 = &vertices[i0];
+                    let v1 = &vertices[i1];
+                    let vertex = Vertex {
+                        position: Vec4::lerp(&v0.position, &v1.position, 0.5),
+                        color:    Vec4::lerp(&v0.color, &v1.color, 0.5),
+                        normal:   Vec3::normalize(&Vec3::add(&v0.normal, &v1.normal)),
+                        uv:       Vec2::lerp(&v0.uv, &v1.uv, 0.5),
+                    };
+                    vertices.push(vertex);
+                    vertices.len() - 1
+                })
+            };
+            for triangle in geometry.indices.chunks_exact(3) {
+                let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+                let ab = midpoint(a, b, &mut vertices);
+                let bc = midpoint(b, c, &mut vertices);
+                let ca = midpoint(c, a, &mut vertices);
+                indices.extend_from_slice(&[a, ab, ca, ab, b, bc, ca, bc, c, ab, bc, ca]);
+            }
+            geometry = Geometry { vertices, indices };
+        }
+        geometry
+    }
+
+    /// Subdivides the geometry then projects every vertex onto a sphere of
+    /// the given radius centered at the origin. Subdividing a cube this way
+    /// produces a geosphere.
+    #[allow(dead_code)]
+    pub fn subdivide_sphere(&self, levels: usize, radius: f32) -> Geometry {
+        let mut geometry = self.subdivide(levels);
+        for vertex in geometry.vertices.iter_mut() {
+            let normal = Vec3::normalize(&vertex.position.xyz());
+            vertex.position = Vec3::scale(&normal, radius).xyz1();
+            vertex.normal   = normal;
+        }
+        geometry
+    }
+
+    /// Checks the index count is a multiple of three and that every index
+    /// refers to a vertex, so the geometry can be drawn without panicking.
+    pub fn validate(&self) -> Result<(), GeometryError> {
+        if self.indices.len() % 3 != 0 {
+            let error = GeometryValidationError::new(format!(
+                "index count {} is not a multiple of three",
+                self.indices.len()
+            ));
+            return Err(GeometryError::Validation(error));
+        }
+        if let Some((offset, index)) = self.indices.iter().enumerate().find(|(_, index)| **index >= self.vertices.len()) {
+            let error = GeometryValidationError::new(format!(
+                "index {} at offset {} is out of range for {} vertices",
+                index,
+                offset,
+                self.vertices.len()
+            ));
+            return Err(GeometryError::Validation(error));
+        }
+        Ok(())
+    }
+
+    /// Returns the number of tokens, including the keyword, expected for the
+    /// given OBJ line keyword. Unknown keywords are ignored.
+    fn obj_token_count(keyword: &str) -> usize {
+        match keyword {
+            "v" | "vn" | "f" => 4,
+            "vt" => 3,
+            _ => 1,
+        }
+    }
+
+    /// Resolves a 1-based OBJ index into the given number of accumulated
+    /// values. Negative indices count backwards from the most recent value,
+    /// so -1 refers to the last.
+    fn obj_index(token: &str, count: usize, kind: &str) -> Result<usize, GeometryError> {
+        let index = token.parse::<isize>()?;
+        let resolved = if index < 0 {
+            count as isize + index
+        } else {
+            index - 1
+        };
+        if resolved < 0 || resolved >= count as isize {
+            let error = GeometryParseError::new(format!(
+                "Invalid {} index for face: {:?}",
+                kind,
+                token
+            ));
+            return Err(GeometryError::ParseError(error));
+        }
+        Ok(resolved as usize)
+    }
+
+    /// Loads geometry from a Wavefront OBJ file. The result is validated
+    /// before it is returned.
     pub fn obj(path: &str) -> Result<Geometry, GeometryError> {
         // geometry accumulators
         let mut acc_v: Vec<Vec4> = vec![];
@@ -224,63 +351,53 @@ impl Geometry {
         let mut file = File::open(path)?;
         let mut content: String = String::new();
         file.read_to_string(&mut content)?;
-        let lines = content.split("\n");
-        for line in lines {
-            let parts = line.split(" ").map(|x| x.trim()).collect::<Vec<_>>();
-            if parts.len() > 0 {
-                match parts[0] {
-                    "v" => {
-                        let x = parts[1].parse::<f32>()?;
-                        let y = parts[2].parse::<f32>()?;
-                        let z = parts[3].parse::<f32>()?;
-                        acc_v.push(Vec4::new(x, y, z, 1.0));
-                    }
-                    "vn" => {
-                        let x = parts[1].parse::<f32>()?;
-                        let y = parts[2].parse::<f32>()?;
-                        let z = parts[3].parse::<f32>()?;
-                        acc_vn.push(Vec3::new(x, y, z));
-                    }
-                    "vt" => {
-                        let x = parts[1].parse::<f32>()?;
-                        let y = parts[2].parse::<f32>()?;
-                        acc_vt.push(Vec2::new(x, y));
-                    }
-                    "f" => {
-                        for i in 1..=3 {
-                            let face = parts[i].split("/").collect::<Vec<_>>();
-                            let i_v = face[0].parse::<usize>()? - 1;
-                            let i_vt = face[1].parse::<usize>()? - 1;
-                            let i_vn = face[2].parse::<usize>()? - 1;
-                            if i_v > acc_v.len() - 1 {
-                                let error = GeometryParseError::new(format!(
-                                    "Invalid position index for face: {:?}",
-                                    parts[0]
-                                ));
-                                return Err(GeometryError::ParseError(error));
-                            }
-                            if i_vt > acc_vt.len() - 1 {
-                                let error = GeometryParseError::new(format!(
-                                    "Invalid texcoord index for face: {:?}",
-                                    parts[0]
-                                ));
-                                return Err(GeometryError::ParseError(error));
-                            }
-                            if i_vn > acc_vn.len() - 1 {
-                                let error = GeometryParseError::new(format!(
-                                    "Invalid normal index for face: {:?}",
-                                    parts[0]
-                                ));
-                                return Err(GeometryError::ParseError(error));
-                            }
-                            positions.push(acc_v[i_v]);
-                            normals.push(acc_vn[i_vn]);
-                            uvs.push(acc_vt[i_vt]);
-                            indices.push(indices.len());
+        for line in content.lines() {
+            // lines() strips \n and \r\n, split_whitespace ignores repeated,
+            // leading and trailing whitespace.
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            if parts.len() < Self::obj_token_count(parts[0]) {
+                let error = GeometryParseError::new(format!("Too few values for line: {:?}", line));
+                return Err(GeometryError::ParseError(error));
+            }
+            match parts[0] {
+                "v" => {
+                    let x = parts[1].parse::<f32>()?;
+                    let y = parts[2].parse::<f32>()?;
+                    let z = parts[3].parse::<f32>()?;
+                    acc_v.push(Vec4::new(x, y, z, 1.0));
+                }
+                "vn" => {
+                    let x = parts[1].parse::<f32>()?;
+                    let y = parts[2].parse::<f32>()?;
+                    let z = parts[3].parse::<f32>()?;
+                    acc_vn.push(Vec3::new(x, y, z));
+                }
+                "vt" => {
+                    let x = parts[1].parse::<f32>()?;
+                    let y = parts[2].parse::<f32>()?;
+                    acc_vt.push(Vec2::new(x, y));
+                }
+                "f" => {
+                    for vertex in &parts[1..=3] {
+                        let face = vertex.split("/").collect::<Vec<_>>();
+                        if face.len() < 3 {
+                            let error = GeometryParseError::new(format!("Invalid face vertex: {:?}", vertex));
+                            return Err(GeometryError::ParseError(error));
                         }
+                        let i_v  = Self::obj_index(face[0], acc_v.len(),  "position")?;
+                        let i_vt = Self::obj_index(face[1], acc_vt.len(), "texcoord")?;
+                        let i_vn = Self::obj_index(face[2], acc_vn.len(), "normal")?;
+                        positions.push(acc_v[i_v]);
+                        normals.push(acc_vn[i_vn]);
+                        uvs.push(acc_vt[i_vt]);
+                        indices.push(indices.len());
                     }
-                    _ => {}
                 }
+                _ => {}
             }
         }
         
@@ -294,9 +411,154 @@ impl Geometry {
                 color:   Vec4::one()
             })
         }
-        Ok(Geometry {
+        let geometry = Geometry {
             vertices, 
             indices
-        })
+        };
+        geometry.validate()?;
+        Ok(geometry)
+    }
+
+    /// Loads geometry from an ASCII PLY file. Vertices are read from the
+    /// x, y, z properties with optional nx, ny, nz normals, s, t (or u, v)
+    /// texcoords and red, green, blue, alpha colors. Integer colors are
+    /// scaled from 0..255. Faces with more than three vertices are split
+    /// into triangle fans. Other elements are skipped.
+    #[allow(dead_code)]
+    pub fn ply(path: &str) -> Result<Geometry, GeometryError> {
+        let parse_error = |message: String| GeometryError::ParseError(GeometryParseError::new(message));
+
+        let mut file = File::open(path)?;
+        let mut content: String = String::new();
+        file.read_to_string(&mut content)?;
+        let mut lines = content.lines().map(|line| line.trim()).filter(|line| !line.is_empty());
+
+        // header: elements in file order, each with its (name, type) properties.
+        struct Element {
+            name:       String,
+            count:      usize,
+            properties: Vec<(String, String)>,
+        }
+        if lines.next() != Some("ply") {
+            return Err(parse_error("missing ply header".to_string()));
+        }
+        let mut elements: Vec<Element> = vec![];
+        loop {
+            let line = lines.next().ok_or_else(|| parse_error("missing end_header".to_string()))?;
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            match parts[0] {
+                "end_header" => break,
+                "format" => {
+                    if parts.get(1) != Some(&"ascii") {
+                        return Err(parse_error(format!("unsupported ply format: {:?}", line)));
+                    }
+                }
+                "element" if parts.len() == 3 => {
+                    elements.push(Element {
+                        name:       parts[1].to_string(),
+                        count:      parts[2].parse::<usize>()?,
+                        properties: vec![],
+                    });
+                }
+                "property" if parts.len() >= 3 => {
+                    let element = elements.last_mut().ok_or_else(|| parse_error(format!("property without element: {:?}", line)))?;
+                    let property = if parts[1] == "list" && parts.len() == 5 {
+                        (parts[4].to_string(), "list".to_string())
+                    } else {
+                        (parts[2].to_string(), parts[1].to_string())
+                    };
+                    element.properties.push(property);
+                }
+                "comment" | "obj_info" => {}
+                _ => return Err(parse_error(format!("invalid ply header line: {:?}", line))),
+            }
+        }
+
+        // body: one line per element instance.
+        let mut vertices = vec![];
+        let mut indices  = vec![];
+        for Element { name, count, properties } in elements.iter() {
+            for _ in 0..*count {
+                let line = lines.next().ok_or_else(|| parse_error(format!("unexpected end of {} data", name)))?;
+                let values = line.split_whitespace().collect::<Vec<_>>();
+                match name.as_str() {
+                    "vertex" => {
+                        if values.len() < properties.len() {
+                            return Err(parse_error(format!("too few vertex values: {:?}", line)));
+                        }
+                        let mut vertex = Vertex {
+                            position: Vec4::new(0.0, 0.0, 0.0, 1.0),
+                            color:    Vec4::one(),
+                            normal:   Vec3::zero(),
+                            uv:       Vec2::zero(),
+                        };
+                        for ((property, kind), value) in properties.iter().zip(values.iter()) {
+                            let value = value.parse::<f32>()?;
+                            let color = match kind.as_str() {
+                                "uchar" | "uint8" | "char" | "int8" => value / 255.0,
+                                _ => value,
+                            };
+                            match property.as_str() {
+                                "x"           => vertex.position.x = value,
+                                "y"           => vertex.position.y = value,
+                                "z"           => vertex.position.z = value,
+                                "nx"          => vertex.normal.x = value,
+                                "ny"          => vertex.normal.y = value,
+                                "nz"          => vertex.normal.z = value,
+                                "s" | "u"     => vertex.uv.x = value,
+                                "t" | "v"     => vertex.uv.y = value,
+                                "red"         => vertex.color.x = color,
+                                "green"       => vertex.color.y = color,
+                                "blue"        => vertex.color.z = color,
+                                "alpha"       => vertex.color.w = color,
+                                _ => {}
+                            }
+                        }
+                        vertices.push(vertex);
+                    }
+                    "face" => {
+                        let count = values.first().ok_or_else(|| parse_error(format!("empty face: {:?}", line)))?.parse::<usize>()?;
+                        if count < 3 || values.len() < count + 1 {
+                            return Err(parse_error(format!("invalid face: {:?}", line)));
+                        }
+                        let face = values[1..=count].iter().map(|value| value.parse::<usize>()).collect::<Result<Vec<_>, _>>()?;
+                        for i in 1..count - 1 {
+                            indices.extend_from_slice(&[face[0], face[i], face[i + 1]]);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let geometry = Geometry { vertices, indices };
+        geometry.validate()?;
+        Ok(geometry)
+    }
+
+    /// Saves the geometry as a Wavefront OBJ file. Each vertex is written as
+    /// a position, texcoord and normal sharing one index, so the file loads
+    /// back through Geometry::obj. Vertex colors are not saved.
+    #[allow(dead_code)]
+    pub fn save_obj(&self, path: &str) -> Result<(), GeometryError> {
+        self.validate()?;
+        let mut content = String::new();
+        for vertex in self.vertices.iter() {
+            let p = &vertex.position;
+            content.push_str(&format!("v {} {} {}\n", p.x, p.y, p.z));
+        }
+        for vertex in self.vertices.iter() {
+            content.push_str(&format!("vt {} {}\n", vertex.uv.x, vertex.uv.y));
+        }
+        for vertex in self.vertices.iter() {
+            let n = &vertex.normal;
+            content.push_str(&format!("vn {} {} {}\n", n.x, n.y, n.z));
+        }
+        for triangle in self.indices.chunks_exact(3) {
+            let (a, b, c) = (triangle[0] + 1, triangle[1] + 1, triangle[2] + 1);
+            content.push_str(&format!("f {0}/{0}/{0} {1}/{1}/{1} {2}/{2}/{2}\n", a, b, c));
+        }
+        let mut file = File::create(path)?;
+        file.write_all(content.as_bytes())?;
+        Ok(())
     }
 }
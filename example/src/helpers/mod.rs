@@ -1,10 +1,12 @@
 mod window;
 mod geometry;
-mod sampler;
+mod clock;
+mod camera;
 
 pub use {
     window::Builder,
+    clock::Clock,
+    camera::OrbitCameraController,
     geometry::Geometry,
     geometry::Vertex,
-    sampler::Sampler
 };
\ No newline at end of file
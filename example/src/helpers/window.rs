@@ -1,24 +1,10 @@
-use black::{ TargetBuffer, Vec4 };
-use minifb::{ Key, Window, WindowOptions, Scale };
-use std::mem;
+use black::{ Buffer2D, Color, DirtyRegion, Rect, TargetBuffer, Vec2, Vec4 };
+use minifb::{ Key, MouseButton, MouseMode, Window, WindowOptions, Scale };
 
 /// ---------------------------------------------------
 /// Pixel buffer
 /// ---------------------------------------------------
-pub struct Buffer {
-    pub width:  usize,
-    pub height: usize,
-    pub data:   Vec<u32>
-}
-impl Buffer {
-    pub fn new(width: usize, height: usize) -> Buffer {
-        Buffer {width, height, data: vec![0; width * height] }
-    }
-    #[inline(always)]
-    pub fn set(&mut self, x: usize, y: usize, c: u32) {
-        self.data[x + (y * self.width)] = c;
-    }
-}
+pub type Buffer = Buffer2D<u32>;
 
 /// ---------------------------------------------------
 /// Errors
@@ -121,30 +107,68 @@ impl Builder {
 pub struct Context {
     window:  Window,
     buffer:  Buffer,
+    dirty:   DirtyRegion,
+    mouse:   Vec2,
+    delta:   Vec2,
 }
 impl Context {
     pub fn new(window: Window, buffer: Buffer) -> Context {
-        Context { window, buffer }
+        Context { window, buffer, dirty: DirtyRegion::new(), mouse: Vec2::zero(), delta: Vec2::zero() }
     }
     pub fn clear(&mut self, color: u32) {
-        for x in 0..self.buffer.data.len() {
-            self.buffer.data[x] = color;
-        }
+        self.buffer.clear(color);
+        self.dirty.add_rect(&Rect::from_size(self.buffer.width as i32, self.buffer.height as i32));
     }
     pub fn active(&self) -> bool {
         self.window.is_open() && !self.window.is_key_down(Key::Escape)
     }
+    #[allow(dead_code)]
+    pub fn is_key_down(&self, key: Key) -> bool {
+        self.window.is_key_down(key)
+    }
+    #[allow(dead_code)]
+    pub fn is_mouse_down(&self, button: MouseButton) -> bool {
+        self.window.get_mouse_down(button)
+    }
+    /// Returns the mouse position in buffer pixels, clamped to the window.
+    #[allow(dead_code)]
+    pub fn mouse_position(&self) -> Vec2 {
+        self.mouse
+    }
+    /// Returns the distance the mouse moved in buffer pixels between the
+    /// last two presents.
+    #[allow(dead_code)]
+    pub fn mouse_delta(&self) -> Vec2 {
+        self.delta
+    }
+    /// Samples the mouse after window events have been processed.
+    fn update_mouse(&mut self) {
+        if let Some((x, y)) = self.window.get_mouse_pos(MouseMode::Clamp) {
+            let mouse  = Vec2::new(x, y);
+            self.delta = mouse - self.mouse;
+            self.mouse = mouse;
+        }
+    }
 
     pub fn present(&mut self) -> Result<(), ContextError> {
+        self.dirty.reset();
         self.window
             .update_with_buffer(&self.buffer.data)
-            .map_err(|_| ContextError::new("Unable to present buffer to window."))
+            .map_err(|_| ContextError::new("Unable to present buffer to window."))?;
+        self.update_mouse();
+        Ok(())
     }
-    #[inline(always)]
-    fn clamp(&mut self, n: f32) -> f32 {
-        if n < 0.0 { return 0.0; }
-        if n > 1.0 { return 1.0; }
-        n
+    /// Presents the buffer only if pixels have been written since the last
+    /// present. Otherwise, only window events are processed. (minifb does not
+    /// support partial updates, so the whole buffer is pushed when dirty)
+    #[allow(dead_code)]
+    pub fn present_dirty(&mut self) -> Result<(), ContextError> {
+        if self.dirty.is_empty() {
+            self.window.update();
+            self.update_mouse();
+            return Ok(());
+        }
+        self.present()
     }
 }
 
@@ -165,16 +189,8 @@ impl TargetBuffer for Context {
     
     #[inline(always)]
     fn set(&mut self, x: i32, y: i32, color: Vec4) {
-        unsafe {
-            let s = [
-                (self.clamp(color.z) * 255.0) as u8, 
-                (self.clamp(color.y) * 255.0) as u8, 
-                (self.clamp(color.x) * 255.0) as u8, 
-                (self.clamp(color.w) * 255.0) as u8
-            ];
-            let c = mem::transmute::<[u8; 4], u32>(s);
-            self.buffer.set(x as usize, y as usize, c)
-        }
+        self.buffer.set(x as usize, y as usize, Color::from_vec4(&color).to_u32());
+        self.dirty.add(x, y);
     }
 
 }
@@ -1,19 +1,22 @@
 mod helpers;
-use black::{ Raster, DepthBuffer, FragmentProgram, Interpolate, VertexProgram };
+use black::{ Raster, DepthBuffer, Draw2D, FragmentProgram, Interpolate, VertexProgram, Sampler, CheckerSampler, Light };
 use black::{ Mat4, Vec2, Vec3, Vec4 };
-use helpers:: { Builder,  Geometry, Vertex, Sampler };
+use helpers:: { Builder, Clock, Geometry, OrbitCameraController, Vertex };
+use minifb::{ Key, MouseButton };
 
 pub struct Uniform {
     pub projection: Mat4,
     pub view:       Mat4,
     pub matrix:     Mat4,
-    pub sampler:    Sampler,
-    pub light:      Vec3
+    pub sampler:    CheckerSampler,
+    pub light:      Light,
+    pub eye:        Vec3,
 }
 
 #[derive(Interpolate)]
 struct Varying {
     pub position: Vec4,
+    pub color:    Vec4,
     pub normal:   Vec3,
     pub uv:       Vec2,
 }
@@ -24,6 +27,7 @@ struct VertexShader; impl VertexProgram for VertexShader {
     type Varying = Varying;
     fn main(&self, uniform: &Uniform, input: &Vertex, varying: &mut Varying) -> Vec4 {
         varying.position  = input.position;
+        varying.color     = input.color;
         varying.normal    = input.normal;
         varying.uv        = input.uv;
         input.position * 
@@ -35,23 +39,20 @@ struct VertexShader; impl VertexProgram for VertexShader {
 struct FragmentShader; impl FragmentProgram for FragmentShader {
     type Uniform = Uniform;
     type Varying = Varying;
-    fn main(&self, uniform: &Uniform, varying: &Varying) -> Vec4 {
-        // return uniform.sampler.get(varying.uv.x, varying.uv.y);
-        let e2p   = Vec3::normalize(&varying.position.xyz()) * -1.0;
-        let l2p   = Vec3::normalize(&(varying.position.xyz() - uniform.light));
-        let ldp   = Vec3::dot(&varying.normal, &l2p);
-        
-        // very rough specular
-        let specular_term  = Vec3::all(1.0) *  Vec3::new(1.0, 1.0, 1.0);
-        let reflect_term   = Vec3::dot(&Vec3::reflect(&l2p, &varying.normal), &e2p);
-        let specular       = (specular_term * reflect_term).xyzw() * ldp;
-        let color          = uniform.sampler.get(varying.uv.x, varying.uv.y);
-        
-        if ldp < 0.0 {
-            Vec4::new(0.0, 0.0, 0.0, 1.0)
-        } else {
-            color * specular //  * Vec4::new(varying.normal.x, varying.normal.y, varying.normal.z, 1.0)
-        }
+    fn main(&self, uniform: &Uniform, varying: &Varying) -> Option<Vec4> {
+        // return Some(uniform.sampler.sample(&varying.uv));
+        let position  = varying.position.xyz();
+        let normal    = Vec3::normalize_or_zero(&varying.normal);
+        let light_dir = uniform.light.direction(&position);
+        let view_dir  = Vec3::normalize_or_zero(&(uniform.eye - position));
+        let radiance  = uniform.light.radiance();
+        let diffuse   = radiance * Light::lambert(&normal, &light_dir);
+        let specular  = radiance * Light::blinn_phong(&normal, &view_dir, &light_dir, 32.0);
+        let color     = uniform.sampler.sample(&varying.uv) * varying.color;
+
+        // tone map so highlights roll off rather than clip.
+        let lit = Vec3::aces_approx(&(color.xyz() * diffuse + specular));
+        Some(Vec4::new(lit.x, lit.y, lit.z, color.w))
     }
 }
 //  clipping - https://en.wikipedia.org/wiki/Cohen%E2%80%93Sutherland_algorithm
@@ -61,6 +62,8 @@ fn main() {
     let width       = (120 * 8) / pixel_size;
     let height      = (60  * 8) / pixel_size;
     let mut time    = 0.0;
+    let mut clock   = Clock::new();
+    let mut camera  = OrbitCameraController::new(Vec3::new(0.0, 0.25, 0.0), Vec3::new(0.0, 2.25, 3.0));
 
     // window context
     let mut context = Builder::default()
@@ -72,8 +75,9 @@ fn main() {
 
     let mut depth   = DepthBuffer::new(width, height);
     let mut uniform = Uniform {
-        sampler:    Sampler::new(8.0),
-        light:      Vec3::new(0.0, -10.0, 0.0),
+        sampler:    CheckerSampler::new(8.0, Vec4::new(1.0, 1.0, 1.0, 1.0), Vec4::new(0.5, 0.5, 0.5, 0.5)),
+        light:      Light::new(Vec3::new(0.0, 10.0, 0.0), Vec3::one(), 1.0),
+        eye:        camera.position(),
         projection: Mat4::perspective_fov(70.0 * 3.14 / 180.0, width as f32 / height as f32, 0.1, 1000.0),
         matrix:     Mat4::identity(),
         view:       Mat4::look_at(
@@ -92,13 +96,20 @@ fn main() {
         depth.clear();
         
         // update uniforms
-        uniform.light.x = f32::cos(time * 4.2) * 10.0;
-        uniform.light.z = f32::sin(time * 4.2) * 10.0;
-        uniform.view =  Mat4::look_at(
-            &Vec3::new(f32::sin(time * 0.5) * 2.1, 2.25, f32::cos(time * 0.5) * 3.0),
-            &Vec3::new(0.0, 0.25, 0.0),
-            &Vec3::new(0.0, 1.0, 0.0),
-        );
+        uniform.light.position = Vec3::rotate_around(&Vec3::new(10.0, uniform.light.position.y, 0.0), &Vec3::zero(), &Vec3::unit_y(), -time * 4.2);
+
+        // drag with the left mouse button to orbit, up and down keys zoom.
+        if context.is_mouse_down(MouseButton::Left) {
+            camera.rotate(&context.mouse_delta());
+        }
+        if context.is_key_down(Key::Up) {
+            camera.zoom(1.0 - clock.delta_seconds());
+        }
+        if context.is_key_down(Key::Down) {
+            camera.zoom(1.0 + clock.delta_seconds());
+        }
+        uniform.view = camera.view();
+        uniform.eye  = camera.position();
         
         // render triangles
         uniform.matrix = Mat4::translation(&Vec3::new(0.0, 0.0, 0.0));
@@ -122,7 +133,11 @@ fn main() {
         }
 
 
+        Draw2D::text(&mut context, 8, 8, &format!("{:.0} fps", clock.fps()), Vec4::one());
         context.present().unwrap();
-        time = time + 0.01;
+
+        // advance in real time, 0.6 per second matches the previous 0.01 per frame at 60fps.
+        clock.tick();
+        time = time + clock.delta_seconds() * 0.6;
     }
 }
\ No newline at end of file
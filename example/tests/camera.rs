@@ -0,0 +1,86 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+#[allow(dead_code)]
+#[path = "../src/helpers/camera.rs"]
+mod camera;
+
+use black::{Vec2, Vec3};
+use camera::OrbitCameraController;
+
+fn approx(v0: &Vec3, v1: &Vec3) -> bool {
+    Vec3::distance(v0, v1) < 1e-4
+}
+
+#[test]
+fn new_preserves_camera_position() {
+    let target   = Vec3::new(0.0, 0.25, 0.0);
+    let position = Vec3::new(1.0, 2.25, 3.0);
+    let camera   = OrbitCameraController::new(target, position);
+    assert!(approx(&camera.position(), &position));
+    assert!((camera.distance - Vec3::distance(&position, &target)).abs() < 1e-5);
+}
+
+#[test]
+fn horizontal_drag_orbits_about_y() {
+    let mut camera = OrbitCameraController::new(Vec3::zero(), Vec3::new(0.0, 0.0, 2.0));
+    camera.sensitivity = 0.01;
+    // a quarter turn, the camera moves from +z to -x.
+    camera.rotate(&Vec2::new(std::f32::consts::FRAC_PI_2 * 100.0, 0.0));
+    assert!(approx(&camera.position(), &Vec3::new(-2.0, 0.0, 0.0)));
+}
+
+#[test]
+fn vertical_drag_is_clamped_below_the_pole() {
+    let mut camera = OrbitCameraController::new(Vec3::zero(), Vec3::new(0.0, 0.0, 2.0));
+    camera.rotate(&Vec2::new(0.0, -10000.0));
+    let position = camera.position();
+    assert!(position.y > 1.99 && position.y < 2.0);
+    assert!((Vec3::length(&position) - 2.0).abs() < 1e-5);
+    camera.rotate(&Vec2::new(0.0, 10000.0));
+    assert!(camera.position().y < -1.99);
+}
+
+#[test]
+fn zoom_scales_distance_and_respects_minimum() {
+    let mut camera = OrbitCameraController::new(Vec3::zero(), Vec3::new(0.0, 0.0, 2.0));
+    camera.zoom(0.5);
+    assert!(approx(&camera.position(), &Vec3::new(0.0, 0.0, 1.0)));
+    camera.zoom(0.0);
+    assert_eq!(camera.distance, camera.min_distance);
+}
+
+#[test]
+fn view_looks_at_target() {
+    let target = Vec3::new(1.0, 2.0, 3.0);
+    let camera = OrbitCameraController::new(target, Vec3::new(4.0, 5.0, 7.0));
+    let view   = camera.view();
+    let eye    = Vec3::transform(&target, &view);
+    // the target lies straight ahead on the view space -z axis.
+    assert!(approx(&eye, &Vec3::new(0.0, 0.0, -camera.distance)));
+}
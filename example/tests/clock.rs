@@ -0,0 +1,76 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+#[allow(dead_code)]
+#[path = "../src/helpers/clock.rs"]
+mod clock;
+
+use clock::Clock;
+
+#[test]
+fn first_tick_has_zero_delta() {
+    let mut clock = Clock::new();
+    clock.tick_at(5.0);
+    assert_eq!(clock.delta_seconds(), 0.0);
+    assert_eq!(clock.fps(), 0.0);
+}
+
+#[test]
+fn delta_is_time_between_ticks() {
+    let mut clock = Clock::new();
+    clock.tick_at(1.0);
+    clock.tick_at(1.25);
+    assert_eq!(clock.delta_seconds(), 0.25);
+    clock.tick_at(1.3);
+    assert!((clock.delta_seconds() - 0.05).abs() < 1e-6);
+    assert!((clock.elapsed_seconds() - 0.3).abs() < 1e-6);
+}
+
+#[test]
+fn fps_averages_recent_frames() {
+    let mut clock = Clock::new();
+    clock.tick_at(0.0);
+    clock.tick_at(0.01);
+    clock.tick_at(0.04);
+    // two frames over 0.04 seconds.
+    assert!((clock.fps() - 50.0).abs() < 1e-3);
+
+    // only the last 60 frames are averaged.
+    let mut clock = Clock::new();
+    let mut time = 0.0;
+    clock.tick_at(time);
+    for _ in 0..100 {
+        time += 0.1;
+        clock.tick_at(time);
+    }
+    for _ in 0..60 {
+        time += 0.02;
+        clock.tick_at(time);
+    }
+    assert!((clock.fps() - 50.0).abs() < 1e-2);
+}
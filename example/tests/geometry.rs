@@ -0,0 +1,164 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+#[allow(dead_code)]
+#[path = "../src/helpers/geometry.rs"]
+mod geometry;
+
+use black::{Vec3, Vec4};
+use geometry::{Geometry, GeometryError};
+
+#[test]
+fn subdivide_quadruples_triangle_count() {
+    let cube = Geometry::cube(1.0);
+    let once = cube.subdivide(1);
+    let twice = cube.subdivide(2);
+    assert_eq!(once.indices.len(), cube.indices.len() * 4);
+    assert_eq!(twice.indices.len(), cube.indices.len() * 16);
+    assert_eq!(cube.subdivide(0).indices, cube.indices);
+}
+
+#[test]
+fn subdivide_shares_edge_midpoints() {
+    // each cube face is a quad of 4 vertices and 5 edges, giving 9 vertices
+    // after one subdivision.
+    let once = Geometry::cube(1.0).subdivide(1);
+    assert_eq!(once.vertices.len(), 6 * 9);
+}
+
+#[test]
+fn subdivide_renormalizes_midpoint_normals() {
+    let mut cube = Geometry::cube(1.0);
+    // tilt the normals so the midpoint average is not unit length.
+    cube.vertices[0].normal = Vec3::normalize(&Vec3::new(1.0, 0.0, 1.0));
+    cube.vertices[1].normal = Vec3::normalize(&Vec3::new(-1.0, 0.0, 1.0));
+    let once = cube.subdivide(1);
+    for vertex in once.vertices.iter() {
+        assert!((Vec3::length(&vertex.normal) - 1.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn subdivide_sphere_projects_onto_radius() {
+    let sphere = Geometry::cube(1.0).subdivide_sphere(2, 3.0);
+    for vertex in sphere.vertices.iter() {
+        assert!((Vec3::length(&vertex.position.xyz()) - 3.0).abs() < 1e-4);
+        assert_eq!(vertex.position.w, 1.0);
+    }
+}
+
+#[test]
+fn validate_accepts_well_formed_geometry() {
+    assert!(Geometry::cube(1.0).validate().is_ok());
+    assert!(Geometry::cube(1.0).subdivide(2).validate().is_ok());
+}
+
+#[test]
+fn validate_reports_out_of_range_index() {
+    let mut cube = Geometry::cube(1.0);
+    cube.indices[7] = 24;
+    match cube.validate() {
+        Err(error @ GeometryError::Validation(_)) => {
+            let message = error.to_string();
+            assert!(message.contains("index 24 at offset 7"), "{}", message);
+        }
+        other => panic!("expected validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn validate_reports_partial_triangle() {
+    let mut cube = Geometry::cube(1.0);
+    cube.indices.pop();
+    assert!(matches!(cube.validate(), Err(GeometryError::Validation(_))));
+}
+
+#[test]
+fn obj_with_crlf_and_irregular_spacing_matches_clean_file() {
+    let clean = Geometry::obj("tests/fixtures/quad.obj").unwrap();
+    let messy = Geometry::obj("tests/fixtures/quad_crlf.obj").unwrap();
+    assert_eq!(clean.indices, messy.indices);
+    assert_eq!(clean.vertices.len(), 6);
+    assert_eq!(clean.vertices.len(), messy.vertices.len());
+    for (a, b) in clean.vertices.iter().zip(messy.vertices.iter()) {
+        assert_eq!(a.position, b.position);
+        assert_eq!(a.normal, b.normal);
+        assert_eq!(a.uv, b.uv);
+    }
+}
+
+#[test]
+fn obj_with_negative_indices_matches_absolute_file() {
+    let absolute = Geometry::obj("tests/fixtures/quad.obj").unwrap();
+    let relative = Geometry::obj("tests/fixtures/quad_relative.obj").unwrap();
+    assert_eq!(absolute.indices, relative.indices);
+    assert_eq!(absolute.vertices.len(), relative.vertices.len());
+    for (a, b) in absolute.vertices.iter().zip(relative.vertices.iter()) {
+        assert_eq!(a.position, b.position);
+        assert_eq!(a.normal, b.normal);
+        assert_eq!(a.uv, b.uv);
+    }
+}
+
+#[test]
+fn save_obj_round_trips_through_obj() {
+    let path = std::env::temp_dir().join("black_save_obj_round_trip.obj");
+    let path = path.to_str().unwrap();
+    let cube = Geometry::cube(1.0);
+    cube.save_obj(path).unwrap();
+    let loaded = Geometry::obj(path).unwrap();
+
+    // obj() emits one vertex per face corner.
+    assert_eq!(loaded.indices.len(), cube.indices.len());
+    assert_eq!(loaded.vertices.len(), cube.indices.len());
+    for (vertex, index) in loaded.vertices.iter().zip(cube.indices.iter()) {
+        assert_eq!(vertex.position, cube.vertices[*index].position);
+        assert_eq!(vertex.normal, cube.vertices[*index].normal);
+        assert_eq!(vertex.uv, cube.vertices[*index].uv);
+    }
+
+    // saving and loading again is stable.
+    loaded.save_obj(path).unwrap();
+    let reloaded = Geometry::obj(path).unwrap();
+    assert_eq!(reloaded.indices, loaded.indices);
+    assert_eq!(reloaded.vertices.len(), loaded.vertices.len());
+    for (a, b) in reloaded.vertices.iter().zip(loaded.vertices.iter()) {
+        assert_eq!(a.position, b.position);
+    }
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn ply_loads_tetrahedron() {
+    let tetrahedron = Geometry::ply("tests/fixtures/tetrahedron.ply").unwrap();
+    assert_eq!(tetrahedron.vertices.len(), 4);
+    assert_eq!(tetrahedron.indices.len(), 4 * 3);
+    assert_eq!(tetrahedron.indices, vec![0, 1, 2, 0, 3, 1, 0, 2, 3, 1, 3, 2]);
+    assert_eq!(tetrahedron.vertices[1].position, Vec4::new(-1.0, -1.0, 1.0, 1.0));
+    assert_eq!(tetrahedron.vertices[1].color, Vec4::new(0.0, 1.0, 0.0, 1.0));
+}
@@ -0,0 +1,58 @@
+/*--------------------------------------------------------------------------
+
+black
+
+The MIT License (MIT)
+
+Copyright (c) 2019 Haydn Paterson (sinclair) <haydn.developer@gmail.com>
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+
+---------------------------------------------------------------------------*/
+
+use std::process::Command;
+
+#[test]
+fn headless_render_writes_non_blank_image() {
+    let path = std::env::temp_dir().join("black_headless_smoke.ppm");
+    let status = Command::new(env!("CARGO_BIN_EXE_headless"))
+        .args(["--model", "tests/fixtures/quad.obj", "--width", "64", "--height", "32", "--frames", "2"])
+        .args(["--eye", "0,0,2", "--output", path.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let bytes = std::fs::read(&path).unwrap();
+    let header = b"P6\n64 32\n255\n";
+    assert_eq!(&bytes[..header.len()], header);
+    let pixels = &bytes[header.len()..];
+    assert_eq!(pixels.len(), 64 * 32 * 3);
+    let background = &pixels[0..3];
+    assert!(pixels.chunks(3).any(|pixel| pixel != background));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn headless_render_fails_on_missing_model() {
+    let status = Command::new(env!("CARGO_BIN_EXE_headless"))
+        .args(["--model", "tests/fixtures/missing.obj", "--output", "unused.ppm"])
+        .status()
+        .unwrap();
+    assert!(!status.success());
+}